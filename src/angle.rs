@@ -0,0 +1,228 @@
+//! A strongly-typed angle, removing any confusion between degrees and radians.
+//!
+//! # Example
+//! ```
+//! use vector2d::angle::Angle;
+//! use vector2d::Vector2D;
+//!
+//! let a = Angle::degrees(350.0f64);
+//! let b = Angle::degrees(10.0);
+//!
+//! // The shortest way from a to b is forwards through 0, not backwards
+//! assert!((a.shortest_arc_to(b).to_degrees() - 20.0).abs() < 1e-9);
+//!
+//! // Vectors can be created from, and converted to, angles
+//! let v = Vector2D::from_angle(Angle::degrees(90.0f64));
+//! assert!((v - Vector2D::new(0.0, 1.0)).length() < 1e-9);
+//! assert!((v.direction().to_degrees() - 90.0).abs() < 1e-9);
+//! ```
+
+use crate::float::Float;
+use crate::Vector2D;
+use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+
+/// An angle, stored in radians. Positive angles are counter-clockwise when the
+/// y axis points up.
+///
+/// Angles are not normalised automatically, so `Angle::degrees(720.0)` stays as
+/// two full turns until [`normalised_signed()`] or [`normalised_positive()`] is
+/// called.
+///
+/// [`normalised_signed()`]: struct.Angle.html#method.normalised_signed
+/// [`normalised_positive()`]: struct.Angle.html#method.normalised_positive
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd)]
+pub struct Angle<T> {
+    pub radians: T,
+}
+
+impl<T: Float> Angle<T> {
+    /// Create an angle from a value in radians.
+    pub fn radians(radians: T) -> Self {
+        Self { radians }
+    }
+
+    /// Create an angle from a value in degrees.
+    pub fn degrees(degrees: T) -> Self {
+        Self {
+            radians: degrees * T::PI / T::from_f64(180.0),
+        }
+    }
+
+    /// Get the angle of zero.
+    pub fn zero() -> Self {
+        Self { radians: T::ZERO }
+    }
+
+    /// Get the value of the angle in radians.
+    pub fn to_radians(self) -> T {
+        self.radians
+    }
+
+    /// Get the value of the angle in degrees.
+    pub fn to_degrees(self) -> T {
+        self.radians * T::from_f64(180.0) / T::PI
+    }
+
+    /// Get the equivalent angle within `[0, 2π)`.
+    pub fn normalised_positive(self) -> Self {
+        let r = self.radians - T::TAU * (self.radians / T::TAU).floor();
+        // Rounding can leave a value that is a hair below 0 landing on 2π
+        if r >= T::TAU || r < T::ZERO {
+            Self::zero()
+        } else {
+            Self { radians: r }
+        }
+    }
+
+    /// Get the equivalent angle within `(-π, π]`.
+    pub fn normalised_signed(self) -> Self {
+        let r = self.normalised_positive().radians;
+        if r > T::PI {
+            Self {
+                radians: r - T::TAU,
+            }
+        } else {
+            Self { radians: r }
+        }
+    }
+
+    /// Get the smallest signed rotation that turns this angle into `other`. The
+    /// result lies within `(-π, π]`.
+    pub fn shortest_arc_to(self, other: Self) -> Self {
+        (other - self).normalised_signed()
+    }
+
+    /// Interpolates between two angles along the shortest arc between them.
+    pub fn lerp(start: Self, end: Self, progress: T) -> Self {
+        start + start.shortest_arc_to(end) * progress
+    }
+
+    /// Get the sine of the angle.
+    pub fn sin(self) -> T {
+        self.radians.sin()
+    }
+
+    /// Get the cosine of the angle.
+    pub fn cos(self) -> T {
+        self.radians.cos()
+    }
+
+    /// Get the sine and cosine of the angle.
+    pub fn sin_cos(self) -> (T, T) {
+        (self.sin(), self.cos())
+    }
+}
+
+impl<T: Float> Vector2D<T> {
+    /// Get a vector of length 1.0 pointing in the direction of the provided
+    /// angle.
+    ///
+    /// # Example
+    /// ```
+    /// use vector2d::angle::Angle;
+    /// use vector2d::Vector2D;
+    /// let v = Vector2D::from_angle(Angle::radians(0.0));
+    /// assert_eq!(Vector2D::new(1.0, 0.0), v);
+    /// ```
+    pub fn from_angle(angle: Angle<T>) -> Self {
+        let (sin, cos) = angle.sin_cos();
+        Self { x: cos, y: sin }
+    }
+
+    /// Get the vector's direction as an [`Angle`] within `(-π, π]`. This is
+    /// equivalent to `angle()`, which returns the direction as a raw value in
+    /// radians.
+    ///
+    /// [`Angle`]: angle/struct.Angle.html
+    pub fn direction(self) -> Angle<T> {
+        Angle::radians(self.y.atan2(self.x))
+    }
+
+    /// Get the signed angle of the smallest rotation that turns the direction
+    /// of this vector into that of `other`.
+    pub fn angle_to(self, other: Self) -> Angle<T> {
+        let cross = self.x * other.y - self.y * other.x;
+        let dot = self.x * other.x + self.y * other.y;
+        Angle::radians(cross.atan2(dot))
+    }
+
+    /// Get this vector rotated counter-clockwise by the provided angle.
+    pub fn rotate(self, angle: Angle<T>) -> Self {
+        let (sin, cos) = angle.sin_cos();
+        Self {
+            x: self.x * cos - self.y * sin,
+            y: self.x * sin + self.y * cos,
+        }
+    }
+}
+
+// Ops Implementations
+
+impl<T: Float> Add<Angle<T>> for Angle<T> {
+    type Output = Angle<T>;
+    fn add(self, rhs: Angle<T>) -> Self::Output {
+        Angle {
+            radians: self.radians + rhs.radians,
+        }
+    }
+}
+
+impl<T: Float> AddAssign<Angle<T>> for Angle<T> {
+    fn add_assign(&mut self, rhs: Angle<T>) {
+        self.radians += rhs.radians;
+    }
+}
+
+impl<T: Float> Sub<Angle<T>> for Angle<T> {
+    type Output = Angle<T>;
+    fn sub(self, rhs: Angle<T>) -> Self::Output {
+        Angle {
+            radians: self.radians - rhs.radians,
+        }
+    }
+}
+
+impl<T: Float> SubAssign<Angle<T>> for Angle<T> {
+    fn sub_assign(&mut self, rhs: Angle<T>) {
+        self.radians -= rhs.radians;
+    }
+}
+
+impl<T: Float> Mul<T> for Angle<T> {
+    type Output = Angle<T>;
+    fn mul(self, rhs: T) -> Self::Output {
+        Angle {
+            radians: self.radians * rhs,
+        }
+    }
+}
+
+impl<T: Float> MulAssign<T> for Angle<T> {
+    fn mul_assign(&mut self, rhs: T) {
+        self.radians *= rhs;
+    }
+}
+
+impl<T: Float> Div<T> for Angle<T> {
+    type Output = Angle<T>;
+    fn div(self, rhs: T) -> Self::Output {
+        Angle {
+            radians: self.radians / rhs,
+        }
+    }
+}
+
+impl<T: Float> DivAssign<T> for Angle<T> {
+    fn div_assign(&mut self, rhs: T) {
+        self.radians /= rhs;
+    }
+}
+
+impl<T: Float> Neg for Angle<T> {
+    type Output = Angle<T>;
+    fn neg(self) -> Self::Output {
+        Angle {
+            radians: -self.radians,
+        }
+    }
+}
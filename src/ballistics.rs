@@ -0,0 +1,240 @@
+//! Aiming projectiles at moving targets.
+//!
+//! Given where a shot is fired from, how fast the projectile travels, and the
+//! position and velocity of a target, these functions find where the
+//! projectile and target meet, and the velocity to launch the projectile with
+//! to get there:
+//!   - [`intercept()`] aims a projectile travelling in a straight line.
+//!   - [`ballistic_arcs()`] aims a projectile falling under gravity, which can
+//!     usually reach the target along both a low, fast arc and a high, slow
+//!     one.
+//!
+//! Both return `None` when the target can't be reached, such as when it's out
+//! of range, or moving away faster than the projectile.
+//!
+//! # Example
+//! ```
+//! use vector2d::ballistics::ballistic_arcs;
+//! use vector2d::Vector2D;
+//!
+//! let shooter = Vector2D::new(0.0f64, 0.0);
+//! let target = Vector2D::new(20.0, 0.0);
+//! let gravity = Vector2D::new(0.0, -9.81);
+//!
+//! let arcs = ballistic_arcs(shooter, 20.0, target, Vector2D::new(0.0, 0.0), gravity).unwrap();
+//! // sin(2θ) = g * x / speed²
+//! let low = (9.81f64 * 20.0 / 400.0).asin() / 2.0;
+//! assert!((arcs.low.angle().to_radians() - low).abs() < 1e-6);
+//! assert!((arcs.high.angle().to_degrees() - (90.0 - low.to_degrees())).abs() < 1e-6);
+//!
+//! // Targets beyond the maximum range of speed² / g are out of reach
+//! let far = Vector2D::new(100.0, 0.0);
+//! assert!(ballistic_arcs(shooter, 20.0, far, Vector2D::new(0.0, 0.0), gravity).is_none());
+//! ```
+//!
+//! [`intercept()`]: fn.intercept.html
+//! [`ballistic_arcs()`]: fn.ballistic_arcs.html
+
+use crate::angle::Angle;
+use crate::float::Float;
+use crate::Vector2D;
+
+/// A way of firing a projectile so that it hits its target.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct Firing<T> {
+    /// The velocity to launch the projectile with.
+    pub velocity: Vector2D<T>,
+    /// The time the projectile takes to reach the target.
+    pub time: T,
+    /// Where the projectile meets the target.
+    pub point: Vector2D<T>,
+}
+
+impl<T: Float> Firing<T> {
+    /// Get the unit direction to launch the projectile in.
+    pub fn direction(&self) -> Vector2D<T> {
+        self.velocity / self.velocity.length_squared().sqrt()
+    }
+
+    /// Get the angle to launch the projectile at, measured counter-clockwise
+    /// from the positive x axis.
+    pub fn angle(&self) -> Angle<T> {
+        self.velocity.direction()
+    }
+}
+
+/// The two ways of firing a projectile along an arc to hit a target.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct Arcs<T> {
+    /// The arc reaching the target soonest.
+    pub low: Firing<T>,
+    /// The arc reaching the target latest. When the target is at the edge of
+    /// the projectile's range, there is only one arc, and this is the same as
+    /// `low`.
+    pub high: Firing<T>,
+}
+
+/// Find the earliest point at which a projectile fired from `shooter` at
+/// `speed`, travelling in a straight line, can hit a target at `target` moving
+/// with `target_velocity`. Returns `None` if the target can never be hit.
+pub fn intercept<T: Float>(
+    shooter: Vector2D<T>,
+    speed: T,
+    target: Vector2D<T>,
+    target_velocity: Vector2D<T>,
+) -> Option<Firing<T>> {
+    let zero = Vector2D::new(T::ZERO, T::ZERO);
+    ballistic_arcs(shooter, speed, target, target_velocity, zero).map(|arcs| arcs.low)
+}
+
+/// Find the arcs along which a projectile fired from `shooter` at `speed`, and
+/// accelerated by `gravity`, hits a target at `target` moving with
+/// `target_velocity`. Returns `None` if the target can never be hit.
+///
+/// If gravity is zero, the projectile travels in a straight line, and the arcs
+/// are the earliest and latest times it could meet the target, which differ
+/// when the target is moving away faster than the projectile.
+pub fn ballistic_arcs<T: Float>(
+    shooter: Vector2D<T>,
+    speed: T,
+    target: Vector2D<T>,
+    target_velocity: Vector2D<T>,
+    gravity: Vector2D<T>,
+) -> Option<Arcs<T>> {
+    // The projectile travels `velocity * t + gravity * t² / 2` while the target
+    // moves `offset + target_velocity * t`, so for them to meet at time t, the
+    // launch velocity times t must equal `offset + v * t + a * t²`, where
+    // `a = -gravity / 2`. Its length must be `speed * t`, so squaring both sides
+    // gives a quartic in t.
+    let offset = target - shooter;
+    let v = target_velocity;
+    let a = gravity * T::from_f64(-0.5);
+    let two = T::from_f64(2.0);
+    let coefficients = [
+        Vector2D::dot(offset, offset),
+        two * Vector2D::dot(offset, v),
+        Vector2D::dot(v, v) + two * Vector2D::dot(a, offset) - speed * speed,
+        two * Vector2D::dot(a, v),
+        Vector2D::dot(a, a),
+    ];
+    let times = positive_roots(&coefficients);
+
+    let firing = |time: T| {
+        let point = target + v * time;
+        Firing {
+            velocity: (offset + v * time + a * (time * time)) / time,
+            time,
+            point,
+        }
+    };
+    Some(Arcs {
+        low: firing(*times.first()?),
+        high: firing(*times.last()?),
+    })
+}
+
+/// Find the positive real roots of a polynomial, given its coefficients from
+/// the constant term upwards, in ascending order.
+///
+/// Roots are bracketed between the critical points of the polynomial, which
+/// are found recursively from its derivative, and then refined by bisection.
+/// A critical point where the polynomial is zero to within rounding error is
+/// taken as a double root, such as when a target is at the edge of range.
+fn positive_roots<T: Float>(coefficients: &[T]) -> Vec<T> {
+    let degree = match coefficients.iter().rposition(|&c| c != T::ZERO) {
+        Some(degree) => degree,
+        None => return Vec::new(),
+    };
+    let coefficients = &coefficients[..=degree];
+    if degree == 0 {
+        return Vec::new();
+    }
+
+    let derivative: Vec<T> = coefficients
+        .iter()
+        .enumerate()
+        .skip(1)
+        .map(|(i, &c)| c * T::from_f64(i as f64))
+        .collect();
+    let leading = coefficients[degree];
+    // Cauchy's bound on the magnitude of every root
+    let bound = T::ONE
+        + coefficients[..degree].iter().fold(T::ZERO, |max, &c| {
+            let ratio = (c / leading).abs();
+            if ratio > max {
+                ratio
+            } else {
+                max
+            }
+        });
+
+    let mut points = vec![T::ZERO];
+    points.extend(
+        positive_roots(&derivative)
+            .into_iter()
+            .filter(|&t| t < bound),
+    );
+    points.push(bound);
+
+    let evaluate = |t: T| {
+        coefficients
+            .iter()
+            .rev()
+            .fold(T::ZERO, |sum, &c| sum * t + c)
+    };
+    // The smallest step away from one, for judging rounding error
+    let mut epsilon = T::ONE;
+    while T::ONE + epsilon / T::from_f64(2.0) > T::ONE {
+        epsilon /= T::from_f64(2.0);
+    }
+    let last = points.len() - 1;
+    let values: Vec<T> = points
+        .iter()
+        .enumerate()
+        .map(|(i, &t)| {
+            let value = evaluate(t);
+            // Rounding error grows with the size of the terms
+            let scale = coefficients
+                .iter()
+                .rev()
+                .fold(T::ZERO, |sum, &c| sum * t + c.abs());
+            if i != 0 && i != last && value.abs() <= scale * epsilon * T::from_f64(64.0) {
+                T::ZERO
+            } else {
+                value
+            }
+        })
+        .collect();
+
+    let mut roots = Vec::new();
+    for (i, pair) in points.windows(2).enumerate() {
+        let (mut low, mut high) = (pair[0], pair[1]);
+        let (f_low, f_high) = (values[i], values[i + 1]);
+        if f_low == T::ZERO {
+            if low > T::ZERO {
+                roots.push(low);
+            }
+            continue;
+        }
+        if f_high == T::ZERO || (f_low < T::ZERO) == (f_high < T::ZERO) {
+            continue;
+        }
+        let rising = f_low < T::ZERO;
+        for _ in 0..128 {
+            let middle = (low + high) / T::from_f64(2.0);
+            if middle <= low || middle >= high {
+                break;
+            }
+            if (evaluate(middle) < T::ZERO) == rising {
+                low = middle;
+            } else {
+                high = middle;
+            }
+        }
+        roots.push((low + high) / T::from_f64(2.0));
+    }
+    if values[last] == T::ZERO {
+        roots.push(points[last]);
+    }
+    roots
+}
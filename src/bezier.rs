@@ -0,0 +1,347 @@
+//! Bezier curves of any degree, with `Vector2D` control points.
+//!
+//! # Example
+//! ```
+//! use vector2d::bezier::Bezier;
+//! use vector2d::Vector2D;
+//!
+//! let curve = Bezier::cubic(
+//!     Vector2D::new(0.0f64, 0.0),
+//!     Vector2D::new(0.0, 1.0),
+//!     Vector2D::new(1.0, 1.0),
+//!     Vector2D::new(1.0, 0.0),
+//! );
+//!
+//! assert_eq!(Vector2D::new(0.5, 0.75), curve.point_at(0.5));
+//!
+//! // Splitting produces two curves that together trace the original
+//! let (first, second) = curve.split(0.5);
+//! assert_eq!(curve.point_at(0.25), first.point_at(0.5));
+//! assert_eq!(curve.point_at(0.75), second.point_at(0.5));
+//!
+//! let bounds = curve.bounding_box();
+//! assert!((bounds.max.y - 0.75).abs() < 1e-9);
+//! ```
+
+use crate::box2d::Box2D;
+use crate::float::Float;
+use crate::Vector2D;
+
+/// A Bezier curve, defined by its control points. The degree of the curve is
+/// one less than the number of control points.
+///
+/// Curves are parameterised by `t` from 0 at the first control point to 1 at
+/// the last. Functions taking `t` do not clamp it, so values outside of
+/// `[0, 1]` extrapolate the curve.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Bezier<T> {
+    points: Vec<Vector2D<T>>,
+}
+
+impl<T: Float> Bezier<T> {
+    /// Create a curve from its control points.
+    ///
+    /// # Panics
+    /// Panics if `points` is empty.
+    pub fn new(points: Vec<Vector2D<T>>) -> Self {
+        assert!(
+            !points.is_empty(),
+            "a Bezier curve needs at least one control point"
+        );
+        Self { points }
+    }
+
+    /// Create a straight line from `p0` to `p1`.
+    pub fn linear(p0: Vector2D<T>, p1: Vector2D<T>) -> Self {
+        Self::new(vec![p0, p1])
+    }
+
+    /// Create a quadratic curve from `p0` to `p2`, pulled towards `p1`.
+    pub fn quadratic(p0: Vector2D<T>, p1: Vector2D<T>, p2: Vector2D<T>) -> Self {
+        Self::new(vec![p0, p1, p2])
+    }
+
+    /// Create a cubic curve from `p0` to `p3`, leaving in the direction of `p1`
+    /// and arriving from the direction of `p2`.
+    pub fn cubic(p0: Vector2D<T>, p1: Vector2D<T>, p2: Vector2D<T>, p3: Vector2D<T>) -> Self {
+        Self::new(vec![p0, p1, p2, p3])
+    }
+
+    /// Get the control points of the curve.
+    pub fn points(&self) -> &[Vector2D<T>] {
+        &self.points
+    }
+
+    /// Get the degree of the curve.
+    pub fn degree(&self) -> usize {
+        self.points.len() - 1
+    }
+
+    /// Get the first control point, where the curve begins.
+    pub fn start(&self) -> Vector2D<T> {
+        self.points[0]
+    }
+
+    /// Get the last control point, where the curve ends.
+    pub fn end(&self) -> Vector2D<T> {
+        self.points[self.points.len() - 1]
+    }
+
+    /// Get the point on the curve at `t`, using de Casteljau's algorithm.
+    pub fn point_at(&self, t: T) -> Vector2D<T> {
+        let mut scratch = self.points.clone();
+        for n in (1..scratch.len()).rev() {
+            for i in 0..n {
+                scratch[i] = Vector2D::lerp(scratch[i], scratch[i + 1], t);
+            }
+        }
+        scratch[0]
+    }
+
+    /// Get the derivative of the curve with respect to `t`, which is itself a
+    /// Bezier curve of one degree lower. The derivative of a single point is
+    /// the zero vector.
+    pub fn derivative(&self) -> Self {
+        if self.points.len() == 1 {
+            return Self::new(vec![Vector2D::new(T::ZERO, T::ZERO)]);
+        }
+        let degree = T::from_f64(self.degree() as f64);
+        Self::new(
+            self.points
+                .windows(2)
+                .map(|w| (w[1] - w[0]) * degree)
+                .collect(),
+        )
+    }
+
+    /// Get the rate of change of the point on the curve at `t`. Its direction
+    /// is the tangent of the curve, and its length is the speed at which `t`
+    /// travels along the curve.
+    pub fn velocity_at(&self, t: T) -> Vector2D<T> {
+        self.derivative().point_at(t)
+    }
+
+    /// Split the curve at `t`, returning the curves covering `[0, t]` and
+    /// `[t, 1]` respectively.
+    pub fn split(&self, t: T) -> (Self, Self) {
+        let mut scratch = self.points.clone();
+        let mut first = Vec::with_capacity(scratch.len());
+        let mut second = Vec::with_capacity(scratch.len());
+        for n in (0..scratch.len()).rev() {
+            first.push(scratch[0]);
+            second.push(scratch[n]);
+            for i in 0..n {
+                scratch[i] = Vector2D::lerp(scratch[i], scratch[i + 1], t);
+            }
+        }
+        second.reverse();
+        (Self::new(first), Self::new(second))
+    }
+
+    /// Get the smallest axis-aligned box containing the curve over `[0, 1]`.
+    /// This is found from the curve's extremities, so is tighter than the box
+    /// surrounding the control points.
+    pub fn bounding_box(&self) -> Box2D<T> {
+        let mut bounds = Box2D::new(self.start(), self.start()).include(self.end());
+        let derivative = self.derivative();
+        let xs: Vec<T> = derivative.points.iter().map(|p| p.x).collect();
+        let ys: Vec<T> = derivative.points.iter().map(|p| p.y).collect();
+        let mut roots = Vec::new();
+        find_roots(&xs, T::ZERO, T::ONE, 0, &mut roots);
+        find_roots(&ys, T::ZERO, T::ONE, 0, &mut roots);
+        for t in roots {
+            bounds = bounds.include(self.point_at(t));
+        }
+        bounds
+    }
+
+    /// Get the length of the curve over `[0, 1]`.
+    pub fn length(&self) -> T {
+        self.arc_length_table(DEFAULT_SAMPLES).length()
+    }
+
+    /// Build a table mapping distances along the curve to values of `t`, which
+    /// can be used to move along the curve at a constant speed. More samples
+    /// give a more accurate mapping.
+    pub fn arc_length_table(&self, samples: usize) -> ArcLengthTable<T> {
+        let derivative = self.derivative();
+        ArcLengthTable::new(samples, |t| speed(derivative.point_at(t)))
+    }
+
+    /// Get the value of `t` at which the curve passes closest to `p`.
+    pub fn nearest_t(&self, p: Vector2D<T>) -> T {
+        let derivative = self.derivative();
+        let second_derivative = derivative.derivative();
+
+        // Find a rough starting point from evenly spaced samples, then refine it
+        // with Newton's method on the derivative of the squared distance
+        let samples = 8 * self.degree().max(1);
+        let step = T::ONE / T::from_f64(samples as f64);
+        let mut best_t = T::ZERO;
+        let mut best_dist = (self.start() - p).length_squared();
+        for i in 1..=samples {
+            let t = step * T::from_f64(i as f64);
+            let dist = (self.point_at(t) - p).length_squared();
+            if dist < best_dist {
+                best_t = t;
+                best_dist = dist;
+            }
+        }
+
+        let mut t = best_t;
+        for _ in 0..8 {
+            let offset = self.point_at(t) - p;
+            let d1 = derivative.point_at(t);
+            let d2 = second_derivative.point_at(t);
+            let numerator = Vector2D::dot(offset, d1);
+            let denominator = Vector2D::dot(d1, d1) + Vector2D::dot(offset, d2);
+            if denominator == T::ZERO {
+                break;
+            }
+            let next = clamp01(t - numerator / denominator);
+            if (self.point_at(next) - p).length_squared() > best_dist {
+                break;
+            }
+            best_dist = (self.point_at(next) - p).length_squared();
+            t = next;
+        }
+        t
+    }
+
+    /// Get the point on the curve closest to `p`.
+    pub fn nearest_point(&self, p: Vector2D<T>) -> Vector2D<T> {
+        self.point_at(self.nearest_t(p))
+    }
+}
+
+/// The number of samples used by [`Bezier::length()`].
+///
+/// [`Bezier::length()`]: struct.Bezier.html#method.length
+const DEFAULT_SAMPLES: usize = 32;
+
+/// A lookup table mapping distance travelled along a curve to the curve's
+/// parameter, for moving along the curve at a constant speed.
+///
+/// The table is built by integrating the curve's speed over evenly spaced
+/// parameter intervals, and interpolating linearly between them.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ArcLengthTable<T> {
+    params: Vec<T>,
+    lengths: Vec<T>,
+}
+
+impl<T: Float> ArcLengthTable<T> {
+    /// Build a table for a curve parameterised over `[0, 1]`, given a function
+    /// returning the curve's speed at a parameter value.
+    ///
+    /// # Panics
+    /// Panics if `samples` is zero.
+    pub fn new<F: Fn(T) -> T>(samples: usize, speed: F) -> Self {
+        assert!(samples > 0, "an arc length table needs at least one sample");
+        let step = T::ONE / T::from_f64(samples as f64);
+        let mut params = Vec::with_capacity(samples + 1);
+        let mut lengths = Vec::with_capacity(samples + 1);
+        let mut total = T::ZERO;
+        params.push(T::ZERO);
+        lengths.push(T::ZERO);
+        for i in 0..samples {
+            let a = step * T::from_f64(i as f64);
+            let b = if i + 1 == samples { T::ONE } else { a + step };
+            total += integrate(&speed, a, b);
+            params.push(b);
+            lengths.push(total);
+        }
+        Self { params, lengths }
+    }
+
+    /// Get the total length of the curve.
+    pub fn length(&self) -> T {
+        self.lengths[self.lengths.len() - 1]
+    }
+
+    /// Get the parameter value at which the provided distance along the curve
+    /// is reached. Distances are clamped to the length of the curve.
+    pub fn t_at_length(&self, distance: T) -> T {
+        if distance <= T::ZERO {
+            return T::ZERO;
+        }
+        if distance >= self.length() {
+            return T::ONE;
+        }
+        // Index of the first entry beyond the distance
+        let i = self.lengths.partition_point(|&l| l <= distance);
+        let (l0, l1) = (self.lengths[i - 1], self.lengths[i]);
+        let (t0, t1) = (self.params[i - 1], self.params[i]);
+        if l1 == l0 {
+            t0
+        } else {
+            t0 + (t1 - t0) * (distance - l0) / (l1 - l0)
+        }
+    }
+
+    /// Get the parameter value at the provided fraction of the curve's length.
+    pub fn t_at_fraction(&self, fraction: T) -> T {
+        self.t_at_length(fraction * self.length())
+    }
+}
+
+/// Get the length of a velocity vector.
+pub(crate) fn speed<T: Float>(v: Vector2D<T>) -> T {
+    v.length_squared().sqrt()
+}
+
+/// Integrate `f` over `[a, b]` with three-point Gauss-Legendre quadrature.
+fn integrate<T: Float, F: Fn(T) -> T>(f: &F, a: T, b: T) -> T {
+    let half = T::from_f64(0.5);
+    let mid = (a + b) * half;
+    let radius = (b - a) * half;
+    let offset = radius * T::from_f64(0.6f64.sqrt());
+    let outer = T::from_f64(5.0 / 9.0);
+    let inner = T::from_f64(8.0 / 9.0);
+    radius * (outer * f(mid - offset) + inner * f(mid) + outer * f(mid + offset))
+}
+
+fn clamp01<T: Float>(t: T) -> T {
+    if t < T::ZERO {
+        T::ZERO
+    } else if t > T::ONE {
+        T::ONE
+    } else {
+        t
+    }
+}
+
+/// Find the roots over `[t0, t1]` of the 1D Bezier curve with the provided
+/// coefficients, by repeatedly splitting it wherever its control polygon
+/// crosses zero.
+fn find_roots<T: Float>(coeffs: &[T], t0: T, t1: T, depth: u32, roots: &mut Vec<T>) {
+    const MAX_DEPTH: u32 = 24;
+
+    let all_positive = coeffs.iter().all(|&c| c > T::ZERO);
+    let all_negative = coeffs.iter().all(|&c| c < T::ZERO);
+    let all_zero = coeffs.iter().all(|&c| c == T::ZERO);
+    if all_positive || all_negative || all_zero {
+        return;
+    }
+    let half = T::from_f64(0.5);
+    if depth == MAX_DEPTH {
+        roots.push((t0 + t1) * half);
+        return;
+    }
+
+    let mut scratch = coeffs.to_vec();
+    let mut left = Vec::with_capacity(coeffs.len());
+    let mut right = Vec::with_capacity(coeffs.len());
+    for n in (0..scratch.len()).rev() {
+        left.push(scratch[0]);
+        right.push(scratch[n]);
+        for i in 0..n {
+            scratch[i] = (scratch[i] + scratch[i + 1]) * half;
+        }
+    }
+    right.reverse();
+
+    let mid = (t0 + t1) * half;
+    find_roots(&left, t0, mid, depth + 1, roots);
+    find_roots(&right, mid, t1, depth + 1, roots);
+}
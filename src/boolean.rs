@@ -0,0 +1,615 @@
+//! Boolean operations on polygons.
+//!
+//! [`union()`], [`intersection()`], [`difference()`] and [`xor()`] combine two
+//! sets of [`Polygon`]s, each of which may have holes, and return the result as
+//! another set of polygons. As in the Martinez–Rueda algorithm, every edge is
+//! split wherever it meets an edge of the other set, and each piece is kept or
+//! dropped depending on whether it lies inside the other set. The kept pieces
+//! are then joined back up into rings. The geometric tests use the exact
+//! predicates from the [`predicates`] module, so edges which overlap or meet
+//! at a vertex, as happens when carving the same shape twice, are handled
+//! consistently. They work in `f64`, so polygons of other types are converted
+//! to `f64` and back.
+//!
+//! The polygons within each set shouldn't overlap each other or themselves,
+//! which holds for the results of these operations. Rings may wind either way.
+//! In the results, exteriors wind counter-clockwise and holes clockwise.
+//!
+//! For clipping by a single convex polygon, such as a rectangle or the view
+//! frustum, [`clip_convex()`] is a simpler and faster alternative using the
+//! Sutherland–Hodgman algorithm.
+//!
+//! # Example
+//! ```
+//! use vector2d::boolean::{difference, Polygon};
+//! use vector2d::Vector2D;
+//!
+//! let rectangle = |x0: f64, y0: f64, x1: f64, y1: f64| {
+//!     Polygon::new(vec![
+//!         Vector2D::new(x0, y0),
+//!         Vector2D::new(x1, y0),
+//!         Vector2D::new(x1, y1),
+//!         Vector2D::new(x0, y1),
+//!     ])
+//! };
+//! // Blast a crater out of the middle of a piece of terrain, and then a
+//! // trench cutting it in two
+//! let terrain = [rectangle(0.0, 0.0, 10.0, 10.0)];
+//! let terrain = difference(&terrain, &[rectangle(4.0, 4.0, 6.0, 6.0)]);
+//! assert_eq!(1, terrain.len());
+//! assert_eq!(1, terrain[0].holes.len());
+//! assert_eq!(96.0, terrain[0].area());
+//!
+//! let terrain = difference(&terrain, &[rectangle(4.5, -1.0, 5.5, 11.0)]);
+//! assert_eq!(2, terrain.len());
+//! assert!(terrain.iter().all(|piece| piece.holes.is_empty() && piece.area() == 44.0));
+//! ```
+//!
+//! [`union()`]: fn.union.html
+//! [`intersection()`]: fn.intersection.html
+//! [`difference()`]: fn.difference.html
+//! [`xor()`]: fn.xor.html
+//! [`clip_convex()`]: fn.clip_convex.html
+//! [`Polygon`]: struct.Polygon.html
+//! [`predicates`]: ../predicates/index.html
+
+use crate::float::Float;
+use crate::predicates::{orient2d, Exact, Orientation};
+use crate::shape::{cross, signed_area};
+use crate::Vector2D;
+use std::cmp::Ordering;
+use std::collections::{HashMap, HashSet};
+
+/// A polygon with an exterior ring and any number of holes.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Polygon<T> {
+    /// The vertices of the outside of the polygon.
+    pub exterior: Vec<Vector2D<T>>,
+    /// The vertices of each hole, which should lie inside the exterior.
+    pub holes: Vec<Vec<Vector2D<T>>>,
+}
+
+impl<T> Polygon<T> {
+    /// Create a new `Polygon` without holes.
+    pub fn new(exterior: Vec<Vector2D<T>>) -> Self {
+        Self {
+            exterior,
+            holes: Vec::new(),
+        }
+    }
+
+    /// Add a hole to the polygon. Holes with fewer than three vertices are
+    /// ignored.
+    pub fn with_hole(mut self, hole: Vec<Vector2D<T>>) -> Self {
+        if hole.len() >= 3 {
+            self.holes.push(hole);
+        }
+        self
+    }
+}
+
+impl<T: Float> Polygon<T> {
+    /// Get the area of the polygon, excluding its holes.
+    pub fn area(&self) -> T {
+        self.holes
+            .iter()
+            .fold(signed_area(&self.exterior).abs(), |area, hole| {
+                area - signed_area(hole).abs()
+            })
+    }
+}
+
+impl<T: Exact + PartialOrd> Polygon<T> {
+    /// Check whether a point lies inside the polygon, and not inside any of
+    /// its holes. Points on an edge may be counted either way.
+    pub fn contains(&self, point: Vector2D<T>) -> bool {
+        winding(&self.exterior, point) != 0
+            && self.holes.iter().all(|hole| winding(hole, point) == 0)
+    }
+}
+
+/// A boolean operation on two sets of polygons.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum Operation {
+    /// The area covered by either set.
+    Union,
+    /// The area covered by both sets.
+    Intersection,
+    /// The area covered by the first set but not the second.
+    Difference,
+    /// The area covered by exactly one of the sets.
+    Xor,
+}
+
+/// Find the area covered by either set of polygons.
+pub fn union<T: Float>(subject: &[Polygon<T>], clip: &[Polygon<T>]) -> Vec<Polygon<T>> {
+    boolean(subject, clip, Operation::Union)
+}
+
+/// Find the area covered by both sets of polygons.
+pub fn intersection<T: Float>(subject: &[Polygon<T>], clip: &[Polygon<T>]) -> Vec<Polygon<T>> {
+    boolean(subject, clip, Operation::Intersection)
+}
+
+/// Find the area covered by the `subject` polygons but not the `clip`
+/// polygons.
+pub fn difference<T: Float>(subject: &[Polygon<T>], clip: &[Polygon<T>]) -> Vec<Polygon<T>> {
+    boolean(subject, clip, Operation::Difference)
+}
+
+/// Find the area covered by exactly one of the sets of polygons.
+pub fn xor<T: Float>(subject: &[Polygon<T>], clip: &[Polygon<T>]) -> Vec<Polygon<T>> {
+    boolean(subject, clip, Operation::Xor)
+}
+
+/// Apply a boolean operation to two sets of polygons. See the [module
+/// documentation] for details.
+///
+/// Every edge of one set is tested against every edge of the other, so this
+/// takes O(nm) time in their numbers of edges.
+///
+/// [module documentation]: index.html
+pub fn boolean<T: Float>(
+    subject: &[Polygon<T>],
+    clip: &[Polygon<T>],
+    operation: Operation,
+) -> Vec<Polygon<T>> {
+    let subject_rings = rings(subject);
+    let clip_rings = rings(clip);
+    let mut all_edges = edges(&subject_rings);
+    let subject_count = all_edges.len();
+    all_edges.extend(edges(&clip_rings));
+    let mut cuts = split(&all_edges, |i, j| {
+        (i < subject_count) != (j < subject_count)
+    });
+    let clip_pieces = cut(&all_edges[subject_count..], cuts.split_off(subject_count));
+    let subject_pieces = cut(&all_edges[..subject_count], cuts);
+
+    // Find the pieces the two sets share, and which way they run
+    let key = |(a, b): (Vector2D<f64>, Vector2D<f64>)| (bits(a), bits(b));
+    let subject_keys: HashSet<_> = subject_pieces.iter().copied().map(key).collect();
+    let clip_keys: HashSet<_> = clip_pieces.iter().copied().map(key).collect();
+
+    let mut kept = Vec::new();
+    for (pieces, others, rings, is_subject) in [
+        (&subject_pieces, &clip_keys, &clip_rings, true),
+        (&clip_pieces, &subject_keys, &subject_rings, false),
+    ] {
+        for &(a, b) in pieces {
+            let (forward, backward) = ((bits(a), bits(b)), (bits(b), bits(a)));
+            let placement = if others.contains(&forward) {
+                Placement::SameEdge
+            } else if others.contains(&backward) {
+                Placement::OppositeEdge
+            } else if rings
+                .iter()
+                .map(|ring| winding(ring, (a + b) / 2.0))
+                .sum::<i32>()
+                != 0
+            {
+                Placement::Inside
+            } else {
+                Placement::Outside
+            };
+            match keep(operation, placement, is_subject) {
+                Some(true) => kept.push((a, b)),
+                Some(false) => kept.push((b, a)),
+                None => {}
+            }
+        }
+    }
+    assemble(kept).into_iter().map(from_f64).collect()
+}
+
+/// Where a piece of one set's boundary lies relative to the other set.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+enum Placement {
+    Inside,
+    Outside,
+    /// Along an edge of the other set running the same way, so both sets lie
+    /// on the same side of it.
+    SameEdge,
+    /// Along an edge of the other set running the opposite way, so the sets
+    /// lie on either side of it.
+    OppositeEdge,
+}
+
+/// Decide whether to keep a piece of boundary in the result, and whether
+/// forwards (`true`) or reversed (`false`). Pieces shared by both sets are only
+/// kept from the subject.
+fn keep(operation: Operation, placement: Placement, is_subject: bool) -> Option<bool> {
+    use Operation::*;
+    use Placement::*;
+    match (operation, placement) {
+        (Union, Outside) | (Intersection, Inside) => Some(true),
+        (Union, SameEdge) | (Intersection, SameEdge) if is_subject => Some(true),
+        (Difference, Outside) if is_subject => Some(true),
+        (Difference, Inside) if !is_subject => Some(false),
+        (Difference, OppositeEdge) if is_subject => Some(true),
+        (Xor, Outside) => Some(true),
+        (Xor, Inside) => Some(false),
+        _ => None,
+    }
+}
+
+/// Find the area with a positive winding number inside a set of rings, which
+/// may cross themselves and each other, and split it into polygons.
+pub(crate) fn fill_positive(rings: &[Vec<Vector2D<f64>>]) -> Vec<Polygon<f64>> {
+    let edges = edges(rings);
+    let cuts = split(&edges, |_, _| true);
+    let pieces = cut(&edges, cuts);
+
+    // Pieces running along the same line, either way, are treated together
+    let mut groups: Vec<(usize, i32)> = Vec::new();
+    let mut group_of: HashMap<(Key, Key), usize> = HashMap::new();
+    for (i, &(a, b)) in pieces.iter().enumerate() {
+        let (from, to) = (bits(a), bits(b));
+        if let Some(&group) = group_of.get(&(from, to)) {
+            groups[group].1 += 1;
+        } else if let Some(&group) = group_of.get(&(to, from)) {
+            groups[group].1 -= 1;
+        } else {
+            group_of.insert((from, to), groups.len());
+            groups.push((i, 1));
+        }
+    }
+
+    let mut kept = Vec::new();
+    for (i, count) in groups {
+        if count == 0 {
+            continue;
+        }
+        // Turn shallow pieces on their side, so that the horizontal ray used
+        // to find the winding number crosses them cleanly
+        let (a, b) = pieces[i];
+        let (from, to) = (bits(a), bits(b));
+        let shallow = (b.y - a.y).abs() < (b.x - a.x).abs();
+        let turn = |p: Vector2D<f64>| if shallow { Vector2D::new(-p.y, p.x) } else { p };
+        let (ta, tb) = (turn(a), turn(b));
+        let middle = (ta + tb) / 2.0;
+        let mut winding = 0;
+        for &(c, d) in &pieces {
+            let (bc, bd) = (bits(c), bits(d));
+            if (bc, bd) == (from, to) || (bc, bd) == (to, from) {
+                continue;
+            }
+            let (c, d) = (turn(c), turn(d));
+            if c.y <= middle.y
+                && d.y > middle.y
+                && orient2d(c, d, middle) == Orientation::CounterClockwise
+            {
+                winding += 1;
+            } else if d.y <= middle.y
+                && c.y > middle.y
+                && orient2d(c, d, middle) == Orientation::Clockwise
+            {
+                winding -= 1;
+            }
+        }
+        // Rays from just beside an upward piece only cross it from its left
+        let (left, right) = if tb.y > ta.y {
+            (winding + count, winding)
+        } else {
+            (winding, winding - count)
+        };
+        if left > 0 && right <= 0 {
+            kept.push((a, b));
+        } else if right > 0 && left <= 0 {
+            kept.push((b, a));
+        }
+    }
+    assemble(kept)
+}
+
+/// Gather the rings of a set of polygons in `f64`, leaving out repeated
+/// vertices, with exteriors wound counter-clockwise and holes clockwise.
+fn rings<T: Float>(polygons: &[Polygon<T>]) -> Vec<Vec<Vector2D<f64>>> {
+    let mut rings = Vec::new();
+    for polygon in polygons {
+        for (ring, exterior) in std::iter::once((&polygon.exterior, true))
+            .chain(polygon.holes.iter().map(|hole| (hole, false)))
+        {
+            let mut ring: Vec<Vector2D<f64>> = ring.iter().map(|&p| to_f64(p)).collect();
+            ring.dedup();
+            while ring.len() > 1 && ring.first() == ring.last() {
+                ring.pop();
+            }
+            if ring.len() < 3 {
+                continue;
+            }
+            if (signed_area(&ring) > 0.0) != exterior {
+                ring.reverse();
+            }
+            rings.push(ring);
+        }
+    }
+    rings
+}
+
+/// Convert a point to `f64`, which the exact predicates work in.
+pub(crate) fn to_f64<T: Float>(point: Vector2D<T>) -> Vector2D<f64> {
+    Vector2D::new(point.x.to_f64(), point.y.to_f64())
+}
+
+/// Convert a polygon worked out in `f64` back to another type.
+pub(crate) fn from_f64<T: Float>(polygon: Polygon<f64>) -> Polygon<T> {
+    let convert = |ring: Vec<Vector2D<f64>>| -> Vec<Vector2D<T>> {
+        ring.into_iter()
+            .map(|p| Vector2D::new(T::from_f64(p.x), T::from_f64(p.y)))
+            .collect()
+    };
+    Polygon {
+        exterior: convert(polygon.exterior),
+        holes: polygon.holes.into_iter().map(convert).collect(),
+    }
+}
+
+/// Get the edges of a set of rings.
+fn edges(rings: &[Vec<Vector2D<f64>>]) -> Vec<(Vector2D<f64>, Vector2D<f64>)> {
+    rings
+        .iter()
+        .flat_map(|ring| (0..ring.len()).map(move |i| (ring[i], ring[(i + 1) % ring.len()])))
+        .collect()
+}
+
+/// Find where to cut each edge so that the pieces only meet at their ends,
+/// comparing the pairs of edges accepted by `test`.
+fn split<F: Fn(usize, usize) -> bool>(
+    edges: &[(Vector2D<f64>, Vector2D<f64>)],
+    test: F,
+) -> Vec<Vec<Vector2D<f64>>> {
+    let mut cuts = vec![Vec::new(); edges.len()];
+    for (i, &(a, b)) in edges.iter().enumerate() {
+        for (j, &(c, d)) in edges.iter().enumerate().skip(i + 1) {
+            if !test(i, j)
+                || a.x.max(b.x) < c.x.min(d.x)
+                || c.x.max(d.x) < a.x.min(b.x)
+                || a.y.max(b.y) < c.y.min(d.y)
+                || c.y.max(d.y) < a.y.min(b.y)
+            {
+                continue;
+            }
+            let (c_side, d_side) = (orient2d(a, b, c), orient2d(a, b, d));
+            let (a_side, b_side) = (orient2d(c, d, a), orient2d(c, d, b));
+            if opposite(c_side, d_side) && opposite(a_side, b_side) {
+                // The edges cross, so both are cut at the same point
+                let t = cross(c - a, d - c) / cross(b - a, d - c);
+                let mut point = a + (b - a) * t;
+                // Snap crossings within rounding error of an end onto it, so
+                // that no piece is too short to tell which side it lies on
+                let ends = [a, b, c, d];
+                let scale = ends
+                    .iter()
+                    .fold(0.0f64, |scale, p| scale.max(p.x.abs()).max(p.y.abs()));
+                let tolerance = scale * 4.0 * f64::EPSILON;
+                if let Some(&end) = ends
+                    .iter()
+                    .find(|&&end| (point - end).length() <= tolerance)
+                {
+                    point = end;
+                }
+                cuts[i].push(point);
+                cuts[j].push(point);
+                continue;
+            }
+            // Cut each edge wherever the other's ends touch it
+            for (point, side) in [(c, c_side), (d, d_side)] {
+                if side == Orientation::Collinear && between(a, b, point) {
+                    cuts[i].push(point);
+                }
+            }
+            for (point, side) in [(a, a_side), (b, b_side)] {
+                if side == Orientation::Collinear && between(c, d, point) {
+                    cuts[j].push(point);
+                }
+            }
+        }
+    }
+    cuts
+}
+
+/// Cut each edge into pieces at the provided points.
+fn cut(
+    edges: &[(Vector2D<f64>, Vector2D<f64>)],
+    cuts: Vec<Vec<Vector2D<f64>>>,
+) -> Vec<(Vector2D<f64>, Vector2D<f64>)> {
+    let mut pieces = Vec::with_capacity(edges.len());
+    for (&(a, b), mut points) in edges.iter().zip(cuts) {
+        let along = |p: &Vector2D<f64>| Vector2D::dot(*p - a, b - a);
+        points.sort_by(|p, q| along(p).partial_cmp(&along(q)).unwrap_or(Ordering::Equal));
+        let mut start = a;
+        for point in points.into_iter().chain(std::iter::once(b)) {
+            if point != start {
+                pieces.push((start, point));
+                start = point;
+            }
+        }
+    }
+    pieces
+}
+
+/// Join pieces of boundary into rings, and sort the rings into polygons.
+fn assemble(pieces: Vec<(Vector2D<f64>, Vector2D<f64>)>) -> Vec<Polygon<f64>> {
+    let mut outgoing: HashMap<Key, Vec<usize>> = HashMap::new();
+    for (i, &(a, _)) in pieces.iter().enumerate() {
+        outgoing.entry(bits(a)).or_default().push(i);
+    }
+
+    let mut used = vec![false; pieces.len()];
+    let mut exteriors = Vec::new();
+    let mut holes = Vec::new();
+    for first in 0..pieces.len() {
+        if used[first] {
+            continue;
+        }
+        let mut ring = Vec::new();
+        let mut current = first;
+        loop {
+            used[current] = true;
+            let (from, to) = pieces[current];
+            ring.push(from);
+            // Where rings touch at a vertex, turn as far right as possible, so
+            // that they come out separately
+            let back = from - to;
+            let next = outgoing
+                .get(&bits(to))
+                .into_iter()
+                .flatten()
+                .copied()
+                .filter(|&i| !used[i] || i == first)
+                .min_by(|&i, &j| {
+                    let (p, q) = (pieces[i].1 - to, pieces[j].1 - to);
+                    clockwise_angle(back, p)
+                        .partial_cmp(&clockwise_angle(back, q))
+                        .unwrap_or(Ordering::Equal)
+                });
+            match next {
+                Some(next) if next != first => current = next,
+                _ => break,
+            }
+        }
+        simplify(&mut ring);
+        let area = signed_area(&ring);
+        if ring.len() < 3 || area == 0.0 {
+            continue;
+        }
+        if area > 0.0 {
+            exteriors.push((area, Polygon::new(ring)));
+        } else {
+            holes.push(ring);
+        }
+    }
+
+    // Put each hole in the smallest exterior around it
+    exteriors.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(Ordering::Equal));
+    for hole in holes {
+        let inside = exteriors.iter_mut().find(|(_, polygon)| {
+            hole.iter()
+                .find_map(|&p| on_or_inside(&polygon.exterior, p))
+                .unwrap_or(false)
+        });
+        if let Some((_, polygon)) = inside {
+            polygon.holes.push(hole);
+        }
+    }
+    exteriors.into_iter().map(|(_, polygon)| polygon).collect()
+}
+
+/// Remove vertices lying on a straight line between their neighbours.
+fn simplify(ring: &mut Vec<Vector2D<f64>>) {
+    let mut i = 0;
+    while ring.len() >= 3 && i < ring.len() {
+        let n = ring.len();
+        let (prev, here, next) = (ring[(i + n - 1) % n], ring[i], ring[(i + 1) % n]);
+        if orient2d(prev, here, next) == Orientation::Collinear
+            && Vector2D::dot(here - prev, next - here) > 0.0
+        {
+            ring.remove(i);
+            i = i.saturating_sub(1);
+        } else {
+            i += 1;
+        }
+    }
+}
+
+/// Get the angle turned clockwise from `from` to `to`, in `(0, 2π]`.
+fn clockwise_angle(from: Vector2D<f64>, to: Vector2D<f64>) -> f64 {
+    let angle = cross(to, from).atan2(Vector2D::dot(from, to));
+    if angle <= 0.0 {
+        angle + f64::TAU
+    } else {
+        angle
+    }
+}
+
+/// Get the winding number of a ring around a point.
+fn winding<T: Exact + PartialOrd>(ring: &[Vector2D<T>], point: Vector2D<T>) -> i32 {
+    let mut winding = 0;
+    for (i, &a) in ring.iter().enumerate() {
+        let b = ring[(i + 1) % ring.len()];
+        if a.y <= point.y && b.y > point.y && orient2d(a, b, point) == Orientation::CounterClockwise
+        {
+            winding += 1;
+        } else if b.y <= point.y && a.y > point.y && orient2d(a, b, point) == Orientation::Clockwise
+        {
+            winding -= 1;
+        }
+    }
+    winding
+}
+
+/// Check whether a point lies inside a ring, or `None` if it lies on an edge.
+fn on_or_inside(ring: &[Vector2D<f64>], point: Vector2D<f64>) -> Option<bool> {
+    let on_edge = ring.iter().enumerate().any(|(i, &a)| {
+        let b = ring[(i + 1) % ring.len()];
+        orient2d(a, b, point) == Orientation::Collinear && (point == a || between(a, b, point))
+    });
+    if on_edge {
+        None
+    } else {
+        Some(winding(ring, point) != 0)
+    }
+}
+
+/// Check whether two orientations are strictly opposite.
+fn opposite(a: Orientation, b: Orientation) -> bool {
+    matches!(
+        (a, b),
+        (Orientation::Clockwise, Orientation::CounterClockwise)
+            | (Orientation::CounterClockwise, Orientation::Clockwise)
+    )
+}
+
+/// Check whether a point on the line through `a` and `b` lies strictly between
+/// them.
+fn between(a: Vector2D<f64>, b: Vector2D<f64>, point: Vector2D<f64>) -> bool {
+    Vector2D::dot(point - a, b - a) > 0.0 && Vector2D::dot(point - b, a - b) > 0.0
+}
+
+/// A hashable key for a point.
+type Key = (u64, u64);
+
+/// Get a hashable key for a point, treating 0 and -0 as the same.
+fn bits(p: Vector2D<f64>) -> Key {
+    ((p.x + 0.0).to_bits(), (p.y + 0.0).to_bits())
+}
+
+/// Clip a polygon to a convex polygon with the Sutherland–Hodgman algorithm,
+/// returning the vertices of the part inside. Either polygon may wind either
+/// way, and the result winds the same way as the `subject`.
+///
+/// The subject may be concave, but if clipping would split it into several
+/// pieces, they come out joined by edges running along the clip polygon's
+/// boundary. A clip polygon with fewer than three vertices encloses nothing,
+/// so the result is empty.
+pub fn clip_convex<T: Float>(subject: &[Vector2D<T>], clip: &[Vector2D<T>]) -> Vec<Vector2D<T>> {
+    if clip.len() < 3 {
+        return Vec::new();
+    }
+    let direction = if signed_area(clip) < T::ZERO {
+        -T::ONE
+    } else {
+        T::ONE
+    };
+    let mut result = subject.to_vec();
+    for (i, &a) in clip.iter().enumerate() {
+        if result.is_empty() {
+            break;
+        }
+        let b = clip[(i + 1) % clip.len()];
+        // Positive on the inside of the clip edge
+        let side = |p: Vector2D<T>| cross(b - a, p - a) * direction;
+        let input = std::mem::take(&mut result);
+        for (j, &start) in input.iter().enumerate() {
+            let end = input[(j + 1) % input.len()];
+            let (s, e) = (side(start), side(end));
+            if s >= T::ZERO {
+                result.push(start);
+            }
+            if (s < T::ZERO && e > T::ZERO) || (s > T::ZERO && e < T::ZERO) {
+                result.push(start + (end - start) * (s / (s - e)));
+            }
+        }
+    }
+    result
+}
@@ -0,0 +1,127 @@
+//! Axis-aligned bounding boxes.
+//!
+//! # Example
+//! ```
+//! use vector2d::box2d::Box2D;
+//! use vector2d::Vector2D;
+//!
+//! let points = [Vector2D::new(1, 5), Vector2D::new(-2, 3), Vector2D::new(4, -1)];
+//! let bounds = Box2D::from_points(points.iter().copied()).unwrap();
+//!
+//! assert_eq!(Box2D::new(Vector2D::new(-2, -1), Vector2D::new(4, 5)), bounds);
+//! assert!(bounds.contains(Vector2D::new(0, 0)));
+//! ```
+
+use crate::Vector2D;
+use std::ops::{Add, Div, Sub};
+
+/// An axis-aligned box spanning from `min` to `max`, inclusive.
+///
+/// A box whose `min` is greater than its `max` on either axis is empty.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct Box2D<T> {
+    pub min: Vector2D<T>,
+    pub max: Vector2D<T>,
+}
+
+impl<T: PartialOrd + Copy + Clone> Box2D<T> {
+    /// Create a new `Box2D` from its minimum and maximum corners.
+    pub fn new(min: Vector2D<T>, max: Vector2D<T>) -> Self {
+        Self { min, max }
+    }
+
+    /// Get the smallest box containing all of the provided points, or `None`
+    /// if there are none.
+    pub fn from_points<I: IntoIterator<Item = Vector2D<T>>>(points: I) -> Option<Self> {
+        let mut iter = points.into_iter();
+        let first = iter.next()?;
+        let mut bounds = Self::new(first, first);
+        for p in iter {
+            bounds = bounds.include(p);
+        }
+        Some(bounds)
+    }
+
+    /// Check whether the box contains no points.
+    pub fn is_empty(&self) -> bool {
+        !(self.min.x <= self.max.x && self.min.y <= self.max.y)
+    }
+
+    /// Check whether the point lies within or on the edge of the box.
+    pub fn contains(&self, p: Vector2D<T>) -> bool {
+        p.x >= self.min.x && p.x <= self.max.x && p.y >= self.min.y && p.y <= self.max.y
+    }
+
+    /// Check whether the two boxes overlap or touch.
+    pub fn intersects(&self, other: &Self) -> bool {
+        self.min.x <= other.max.x
+            && other.min.x <= self.max.x
+            && self.min.y <= other.max.y
+            && other.min.y <= self.max.y
+    }
+
+    /// Get the smallest box containing both this box and the point.
+    pub fn include(self, p: Vector2D<T>) -> Self {
+        Self {
+            min: Vector2D::new(min(self.min.x, p.x), min(self.min.y, p.y)),
+            max: Vector2D::new(max(self.max.x, p.x), max(self.max.y, p.y)),
+        }
+    }
+
+    /// Get the smallest box containing both boxes.
+    pub fn union(self, other: Self) -> Self {
+        self.include(other.min).include(other.max)
+    }
+
+    /// Get the region covered by both boxes, which may be empty.
+    pub fn intersection(self, other: Self) -> Self {
+        Self {
+            min: Vector2D::new(max(self.min.x, other.min.x), max(self.min.y, other.min.y)),
+            max: Vector2D::new(min(self.max.x, other.max.x), min(self.max.y, other.max.y)),
+        }
+    }
+
+    /// Get the point within the box nearest to `p`.
+    pub fn clamp(&self, p: Vector2D<T>) -> Vector2D<T> {
+        Vector2D::new(
+            min(max(p.x, self.min.x), self.max.x),
+            min(max(p.y, self.min.y), self.max.y),
+        )
+    }
+}
+
+impl<T> Box2D<T>
+where
+    T: Sub<T, Output = T> + Copy + Clone,
+{
+    /// Get the width and height of the box.
+    pub fn size(&self) -> Vector2D<T> {
+        self.max - self.min
+    }
+}
+
+impl<T> Box2D<T>
+where
+    T: Add<T, Output = T> + Div<T, Output = T> + From<u8> + Copy + Clone,
+{
+    /// Get the point at the centre of the box.
+    pub fn center(&self) -> Vector2D<T> {
+        (self.min + self.max) / T::from(2)
+    }
+}
+
+fn min<T: PartialOrd>(a: T, b: T) -> T {
+    if b < a {
+        b
+    } else {
+        a
+    }
+}
+
+fn max<T: PartialOrd>(a: T, b: T) -> T {
+    if b > a {
+        b
+    } else {
+        a
+    }
+}
@@ -0,0 +1,344 @@
+//! Narrow-phase collision detection between [`Shape`]s.
+//!
+//! [`collide()`] checks whether two posed shapes overlap, and if they do,
+//! describes the overlap as a [`Contact`]: the direction to push the shapes
+//! apart in, and up to two points where they touch, each with its own
+//! penetration depth. Polygons are tested with the separating axis theorem,
+//! and touching edges are clipped against each other so that a box resting on
+//! a surface is supported at both of its corners.
+//!
+//! # Example
+//! ```
+//! use vector2d::collision::collide;
+//! use vector2d::shape::{Pose, Shape};
+//! use vector2d::angle::Angle;
+//! use vector2d::Vector2D;
+//!
+//! let ground = Shape::rectangle(Vector2D::new(10.0f64, 1.0));
+//! let ground_pose = Pose::identity();
+//! let ball = Shape::circle(0.5);
+//! let ball_pose = Pose::new(Vector2D::new(2.0, 1.25), Angle::zero());
+//!
+//! let contact = collide(&ground, &ground_pose, &ball, &ball_pose).unwrap();
+//! assert_eq!(Vector2D::new(0.0, 1.0), contact.normal);
+//! assert_eq!(1, contact.points.len());
+//! assert!((contact.points[0].depth - 0.25).abs() < 1e-9);
+//! ```
+//!
+//! [`Shape`]: ../shape/enum.Shape.html
+//! [`collide()`]: fn.collide.html
+//! [`Contact`]: struct.Contact.html
+
+use crate::float::Float;
+use crate::shape::{Pose, Shape};
+use crate::Vector2D;
+
+/// A point where two shapes touch.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct ContactPoint<T> {
+    /// The position of the point, in world coordinates.
+    pub position: Vector2D<T>,
+    /// How far the shapes overlap at this point, along the contact normal.
+    pub depth: T,
+}
+
+/// The overlap between two shapes.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Contact<T> {
+    /// The unit direction from the first shape towards the second, along which
+    /// they can be pushed apart.
+    pub normal: Vector2D<T>,
+    /// The points where the shapes touch, of which there are one or two.
+    pub points: Vec<ContactPoint<T>>,
+}
+
+impl<T: Float> Contact<T> {
+    /// Get the deepest penetration of any of the contact points.
+    pub fn depth(&self) -> T {
+        self.points.iter().fold(
+            T::ZERO,
+            |deepest, p| if p.depth > deepest { p.depth } else { deepest },
+        )
+    }
+
+    /// Swap the roles of the two shapes, reversing the normal.
+    fn flipped(mut self) -> Self {
+        self.normal = -self.normal;
+        self
+    }
+}
+
+/// Check whether shape `a` at `pose_a` overlaps shape `b` at `pose_b`,
+/// returning the contact between them if so. Shapes that only just touch are
+/// considered to overlap, with a depth of zero.
+pub fn collide<T: Float>(
+    a: &Shape<T>,
+    pose_a: &Pose<T>,
+    b: &Shape<T>,
+    pose_b: &Pose<T>,
+) -> Option<Contact<T>> {
+    match (a, b) {
+        (Shape::Circle(ra), Shape::Circle(rb)) => {
+            circle_circle(pose_a.position, *ra, pose_b.position, *rb)
+        }
+        (Shape::Polygon(vertices), Shape::Circle(r)) => {
+            polygon_circle(vertices, pose_a, pose_b.position, *r)
+        }
+        (Shape::Circle(r), Shape::Polygon(vertices)) => {
+            polygon_circle(vertices, pose_b, pose_a.position, *r).map(Contact::flipped)
+        }
+        (Shape::Polygon(va), Shape::Polygon(vb)) => polygon_polygon(
+            &WorldPolygon::new(va, pose_a),
+            &WorldPolygon::new(vb, pose_b),
+        ),
+    }
+}
+
+fn circle_circle<T: Float>(ca: Vector2D<T>, ra: T, cb: Vector2D<T>, rb: T) -> Option<Contact<T>> {
+    let offset = cb - ca;
+    let radii = ra + rb;
+    let distance_squared = offset.length_squared();
+    if distance_squared > radii * radii {
+        return None;
+    }
+    let distance = distance_squared.sqrt();
+    let normal = if distance > T::ZERO {
+        offset / distance
+    } else {
+        // Concentric circles can be pushed apart in any direction
+        Vector2D::new(T::ZERO, T::ONE)
+    };
+    let depth = radii - distance;
+    Some(Contact {
+        normal,
+        points: vec![ContactPoint {
+            position: ca + normal * (ra - depth / T::from_f64(2.0)),
+            depth,
+        }],
+    })
+}
+
+fn polygon_circle<T: Float>(
+    vertices: &[Vector2D<T>],
+    pose: &Pose<T>,
+    centre: Vector2D<T>,
+    radius: T,
+) -> Option<Contact<T>> {
+    let c = pose.inverse_transform_point(centre);
+
+    // Find the face the centre is furthest outside of
+    let face_separation = |i: usize| Vector2D::dot(edge_normal(vertices, i), c - vertices[i]);
+    let mut best = 0;
+    let mut separation = face_separation(0);
+    for i in 1..vertices.len() {
+        let s = face_separation(i);
+        if s > separation {
+            best = i;
+            separation = s;
+        }
+    }
+    if separation > radius {
+        return None;
+    }
+
+    let v1 = vertices[best];
+    let v2 = vertices[(best + 1) % vertices.len()];
+    let face_normal = edge_normal(vertices, best);
+    let (normal, point, depth) = if separation <= T::ZERO {
+        // The centre is inside the polygon
+        (
+            face_normal,
+            c - face_normal * separation,
+            radius - separation,
+        )
+    } else if Vector2D::dot(c - v1, v2 - v1) <= T::ZERO {
+        vertex_contact(v1, c, radius)?
+    } else if Vector2D::dot(c - v2, v1 - v2) <= T::ZERO {
+        vertex_contact(v2, c, radius)?
+    } else {
+        (
+            face_normal,
+            c - face_normal * separation,
+            radius - separation,
+        )
+    };
+
+    Some(Contact {
+        normal: normal.rotate(pose.rotation),
+        points: vec![ContactPoint {
+            position: pose.transform_point(point),
+            depth,
+        }],
+    })
+}
+
+/// Get the contact between a polygon's vertex and a circle nearest to it, in
+/// the polygon's local coordinates.
+fn vertex_contact<T: Float>(
+    vertex: Vector2D<T>,
+    centre: Vector2D<T>,
+    radius: T,
+) -> Option<(Vector2D<T>, Vector2D<T>, T)> {
+    let offset = centre - vertex;
+    let distance_squared = offset.length_squared();
+    if distance_squared > radius * radius {
+        return None;
+    }
+    let distance = distance_squared.sqrt();
+    Some((offset / distance, vertex, radius - distance))
+}
+
+/// A polygon's vertices and outward face normals, in world coordinates.
+struct WorldPolygon<T> {
+    vertices: Vec<Vector2D<T>>,
+    normals: Vec<Vector2D<T>>,
+}
+
+impl<T: Float> WorldPolygon<T> {
+    fn new(local: &[Vector2D<T>], pose: &Pose<T>) -> Self {
+        let vertices: Vec<_> = local.iter().map(|&v| pose.transform_point(v)).collect();
+        let normals = (0..vertices.len())
+            .map(|i| edge_normal(&vertices, i))
+            .collect();
+        Self { vertices, normals }
+    }
+
+    fn vertex(&self, i: usize) -> Vector2D<T> {
+        self.vertices[i % self.vertices.len()]
+    }
+
+    /// Find the face of this polygon that `other` is furthest outside of,
+    /// returning its index and the separation. Negative separations mean that
+    /// the polygons overlap on every face.
+    fn max_separation(&self, other: &Self) -> (usize, T) {
+        let face_separation = |i: usize| {
+            let (normal, v) = (self.normals[i], self.vertices[i]);
+            let mut deepest = Vector2D::dot(normal, other.vertices[0] - v);
+            for &w in &other.vertices[1..] {
+                let s = Vector2D::dot(normal, w - v);
+                if s < deepest {
+                    deepest = s;
+                }
+            }
+            deepest
+        };
+        let mut best = (0, face_separation(0));
+        for i in 1..self.normals.len() {
+            let s = face_separation(i);
+            if s > best.1 {
+                best = (i, s);
+            }
+        }
+        best
+    }
+}
+
+fn polygon_polygon<T: Float>(a: &WorldPolygon<T>, b: &WorldPolygon<T>) -> Option<Contact<T>> {
+    let (edge_a, separation_a) = a.max_separation(b);
+    if separation_a > T::ZERO {
+        return None;
+    }
+    let (edge_b, separation_b) = b.max_separation(a);
+    if separation_b > T::ZERO {
+        return None;
+    }
+
+    // Prefer the first polygon's face, so that contacts don't flip between
+    // faces from step to step when the separations are nearly equal
+    let tolerance = T::from_f64(1e-3);
+    let (reference, incident, edge, flip) = if separation_b > separation_a + tolerance {
+        (b, a, edge_b, true)
+    } else {
+        (a, b, edge_a, false)
+    };
+
+    let normal = reference.normals[edge];
+    let v1 = reference.vertex(edge);
+    let v2 = reference.vertex(edge + 1);
+
+    // The incident face is the one most opposed to the reference face
+    let mut incident_edge = 0;
+    let mut min_dot = Vector2D::dot(incident.normals[0], normal);
+    for (i, &n) in incident.normals.iter().enumerate().skip(1) {
+        let d = Vector2D::dot(n, normal);
+        if d < min_dot {
+            incident_edge = i;
+            min_dot = d;
+        }
+    }
+    let segment = [
+        incident.vertex(incident_edge),
+        incident.vertex(incident_edge + 1),
+    ];
+
+    // Clip the incident face to the sides of the reference face
+    let tangent = unit(v2 - v1);
+    let segment = clip_segment(segment, -tangent, -Vector2D::dot(tangent, v1))?;
+    let segment = clip_segment(segment, tangent, Vector2D::dot(tangent, v2))?;
+
+    // Keep the points below the reference face, moving them halfway up to it
+    let front = Vector2D::dot(normal, v1);
+    let half = T::from_f64(0.5);
+    let points: Vec<_> = segment
+        .iter()
+        .filter_map(|&p| {
+            let separation = Vector2D::dot(normal, p) - front;
+            if separation <= T::ZERO {
+                Some(ContactPoint {
+                    position: p - normal * (separation * half),
+                    depth: -separation,
+                })
+            } else {
+                None
+            }
+        })
+        .collect();
+    if points.is_empty() {
+        return None;
+    }
+
+    Some(Contact {
+        normal: if flip { -normal } else { normal },
+        points,
+    })
+}
+
+/// Clip a segment to the half-plane where `dot(normal, p) <= offset`,
+/// returning `None` if less than a single point remains.
+fn clip_segment<T: Float>(
+    segment: [Vector2D<T>; 2],
+    normal: Vector2D<T>,
+    offset: T,
+) -> Option<[Vector2D<T>; 2]> {
+    let d0 = Vector2D::dot(normal, segment[0]) - offset;
+    let d1 = Vector2D::dot(normal, segment[1]) - offset;
+    match (d0 <= T::ZERO, d1 <= T::ZERO) {
+        (true, true) => Some(segment),
+        (false, false) => None,
+        (inside0, _) => {
+            let crossing = segment[0] + (segment[1] - segment[0]) * (d0 / (d0 - d1));
+            if inside0 {
+                Some([segment[0], crossing])
+            } else {
+                Some([crossing, segment[1]])
+            }
+        }
+    }
+}
+
+/// Get the outward unit normal of the `i`th edge of a counter-clockwise
+/// polygon.
+fn edge_normal<T: Float>(vertices: &[Vector2D<T>], i: usize) -> Vector2D<T> {
+    let edge = vertices[(i + 1) % vertices.len()] - vertices[i];
+    unit(Vector2D::new(edge.y, -edge.x))
+}
+
+/// Scale a vector to unit length, leaving zero vectors unchanged.
+pub(crate) fn unit<T: Float>(v: Vector2D<T>) -> Vector2D<T> {
+    let length = v.length_squared().sqrt();
+    if length > T::ZERO {
+        v / length
+    } else {
+        v
+    }
+}
@@ -0,0 +1,664 @@
+//! Delaunay triangulations and Voronoi diagrams of point sets.
+//!
+//! A [`Delaunay`] triangulation connects a set of points ("sites") with
+//! triangles whose circumcircles contain no other site, which avoids long thin
+//! triangles wherever possible. Edges can be forced into the triangulation
+//! with [`add_constraint()`], such as to follow rivers or walls, making it a
+//! constrained Delaunay triangulation.
+//!
+//! Its dual, the [`Voronoi`] diagram, divides the plane into a cell for each
+//! site, containing the area closer to that site than any other. Cells are
+//! clipped to a rectangle, so that the cells of sites on the outside of the set
+//! are bounded.
+//!
+//! Both use the exact predicates from the [`predicates`] module, so grids and
+//! other point sets full of collinear and cocircular sites are handled
+//! consistently.
+//!
+//! # Example
+//! ```
+//! use vector2d::box2d::Box2D;
+//! use vector2d::delaunay::{Delaunay, Voronoi};
+//! use vector2d::Vector2D;
+//!
+//! let sites = [
+//!     Vector2D::new(0.0, 0.0),
+//!     Vector2D::new(4.0, 0.0),
+//!     Vector2D::new(2.0, 3.0),
+//!     Vector2D::new(2.0, 1.0),
+//! ];
+//! let delaunay = Delaunay::new(&sites);
+//! // The site in the middle is joined to each of the others
+//! assert_eq!(3, delaunay.triangles().count());
+//! assert_eq!(3, delaunay.neighbours(3).len());
+//!
+//! let bounds = Box2D::new(Vector2D::new(-10.0, -10.0), Vector2D::new(10.0, 10.0));
+//! let voronoi = Voronoi::new(&sites, bounds);
+//! let mut neighbours = voronoi.neighbours(0).to_vec();
+//! neighbours.sort();
+//! assert_eq!(vec![1, 2, 3], neighbours);
+//! ```
+//!
+//! [`Delaunay`]: struct.Delaunay.html
+//! [`add_constraint()`]: struct.Delaunay.html#method.add_constraint
+//! [`Voronoi`]: struct.Voronoi.html
+//! [`predicates`]: ../predicates/index.html
+
+use crate::box2d::Box2D;
+use crate::predicates::{incircle, orient2d, CirclePosition, Orientation};
+use crate::Vector2D;
+use std::cmp::Ordering;
+use std::collections::{HashSet, VecDeque};
+
+/// Marks a missing half-edge or vertex.
+const NONE: usize = usize::MAX;
+
+/// A constrained Delaunay triangulation. See the [module documentation] for
+/// details.
+///
+/// Triangles are stored as half-edges: half-edge `e` belongs to triangle
+/// `e / 3`, and the three half-edges of a triangle run counter-clockwise
+/// around it.
+///
+/// [module documentation]: index.html
+#[derive(Clone, Debug, PartialEq)]
+pub struct Delaunay {
+    points: Vec<Vector2D<f64>>,
+    /// The vertex each half-edge starts from.
+    vertices: Vec<usize>,
+    /// The opposite half-edge of each half-edge, or `NONE` on the hull.
+    opposite: Vec<usize>,
+    /// A half-edge starting from each vertex, or `NONE` if the vertex isn't
+    /// part of the triangulation.
+    outgoing: Vec<usize>,
+    /// The constrained edges, with the lower vertex index first.
+    constraints: HashSet<(usize, usize)>,
+}
+
+impl Delaunay {
+    /// Triangulate a set of points. Points which repeat an earlier point are
+    /// left out, and if every point lies on a single line, there are no
+    /// triangles.
+    ///
+    /// The points are added in order of their distance from a starting
+    /// triangle, which keeps them outside of the triangles added so far, so
+    /// this takes O(n log n) time for evenly spread points.
+    pub fn new(points: &[Vector2D<f64>]) -> Self {
+        let mut delaunay = Self {
+            points: points.to_vec(),
+            vertices: Vec::with_capacity(points.len() * 6),
+            opposite: Vec::with_capacity(points.len() * 6),
+            outgoing: vec![NONE; points.len()],
+            constraints: HashSet::new(),
+        };
+
+        // Leave out repeated points, treating 0 and -0 as the same
+        let mut seen = HashSet::new();
+        let unique: Vec<usize> = (0..points.len())
+            .filter(|&i| {
+                seen.insert(((points[i].x + 0.0).to_bits(), (points[i].y + 0.0).to_bits()))
+            })
+            .collect();
+        let seed = match seed_triangle(points, &unique) {
+            Some(seed) => seed,
+            None => return delaunay,
+        };
+        let [i0, i1, i2] = seed;
+
+        // Sweep outwards from the circumcentre of the seed triangle
+        let centre = circumcentre(points[i0], points[i1], points[i2]);
+        let distance = |i: usize| (points[i] - centre).length_squared();
+        let mut order: Vec<usize> = unique.into_iter().filter(|i| !seed.contains(i)).collect();
+        order.sort_by(|&a, &b| {
+            distance(a)
+                .partial_cmp(&distance(b))
+                .unwrap_or(Ordering::Equal)
+        });
+
+        // The convex hull, as a counter-clockwise linked list of vertices, with
+        // the half-edge starting from each hull vertex along the hull
+        let mut hull_next = vec![NONE; points.len()];
+        let mut hull_prev = vec![NONE; points.len()];
+        let mut hull_edge = vec![NONE; points.len()];
+        for (k, &v) in seed.iter().enumerate() {
+            hull_next[v] = seed[(k + 1) % 3];
+            hull_prev[seed[(k + 1) % 3]] = v;
+            hull_edge[v] = k;
+        }
+        delaunay.add_triangle([i0, i1, i2], [NONE; 3]);
+        let mut hull_start = i0;
+
+        for p in order {
+            let position = points[p];
+            let visible = |u: usize, w: usize| {
+                orient2d(points[u], points[w], position) == Orientation::Clockwise
+            };
+            // Find an edge of the hull which can be seen from the point, then
+            // back up to the start of the run of visible edges
+            let mut first = hull_start;
+            while !visible(first, hull_next[first]) {
+                first = hull_next[first];
+                if first == hull_start {
+                    break;
+                }
+            }
+            if !visible(first, hull_next[first]) {
+                // Rounding in the sort put the point inside the hull
+                continue;
+            }
+            while visible(hull_prev[first], first) {
+                first = hull_prev[first];
+            }
+
+            // Join the point to every visible edge
+            let mut new_edges = Vec::new();
+            let mut previous_triangle = NONE;
+            let mut u = first;
+            let mut first_triangle = NONE;
+            loop {
+                let w = hull_next[u];
+                let t = delaunay.add_triangle([u, p, w], [previous_triangle, NONE, hull_edge[u]]);
+                if first_triangle == NONE {
+                    first_triangle = t;
+                }
+                new_edges.push(t + 2);
+                previous_triangle = t + 1;
+                let next = hull_next[w];
+                if !visible(w, next) {
+                    // Remove the hidden vertices from the hull and add the point
+                    hull_next[first] = p;
+                    hull_prev[p] = first;
+                    hull_next[p] = w;
+                    hull_prev[w] = p;
+                    hull_edge[first] = first_triangle;
+                    hull_edge[p] = t + 1;
+                    break;
+                }
+                u = w;
+            }
+            hull_start = p;
+
+            delaunay.legalise(new_edges, &mut hull_edge);
+        }
+        delaunay
+    }
+
+    /// Get the sites being triangulated.
+    pub fn points(&self) -> &[Vector2D<f64>] {
+        &self.points
+    }
+
+    /// Iterate over the triangles, as indices of their sites in
+    /// counter-clockwise order. The index of each triangle is its position in
+    /// the iterator.
+    pub fn triangles(&self) -> impl Iterator<Item = [usize; 3]> + '_ {
+        self.vertices.chunks(3).map(|t| [t[0], t[1], t[2]])
+    }
+
+    /// Get the triangles sharing each edge of a triangle, or `None` for edges
+    /// on the convex hull. Edge `i` runs from the triangle's site `i` to the
+    /// next site.
+    pub fn triangle_neighbours(&self, triangle: usize) -> [Option<usize>; 3] {
+        let mut neighbours = [None; 3];
+        for (i, neighbour) in neighbours.iter_mut().enumerate() {
+            let opposite = self.opposite[triangle * 3 + i];
+            if opposite != NONE {
+                *neighbour = Some(opposite / 3);
+            }
+        }
+        neighbours
+    }
+
+    /// Get the sites joined to `site` by an edge, in counter-clockwise order
+    /// around it.
+    pub fn neighbours(&self, site: usize) -> Vec<usize> {
+        let start = self.outgoing[site];
+        if start == NONE {
+            return Vec::new();
+        }
+        // Turn counter-clockwise around the site until reaching the hull or
+        // coming back to the start, then clockwise from the start if needed
+        let mut ccw = Vec::new();
+        let mut e = start;
+        loop {
+            ccw.push(self.vertices[next(e)]);
+            let turned = self.opposite[prev(e)];
+            if turned == start {
+                return ccw;
+            }
+            if turned == NONE {
+                ccw.push(self.vertices[prev(e)]);
+                break;
+            }
+            e = turned;
+        }
+        let mut cw = Vec::new();
+        let mut e = start;
+        while self.opposite[e] != NONE {
+            e = next(self.opposite[e]);
+            cw.push(self.vertices[next(e)]);
+        }
+        cw.reverse();
+        cw.extend(ccw);
+        cw
+    }
+
+    /// Check whether the edge between two sites has been constrained.
+    pub fn is_constrained(&self, a: usize, b: usize) -> bool {
+        self.constraints.contains(&(a.min(b), a.max(b)))
+    }
+
+    /// Force the triangulation to include the edge between sites `a` and `b`,
+    /// flipping the edges crossing it out of the way. Edges which don't cross
+    /// a constraint are then restored to being Delaunay. Sites lying exactly
+    /// on the edge split it into several constrained edges.
+    ///
+    /// Returns `false`, leaving the triangulation unchanged, if the edge
+    /// would cross an edge which is already constrained, or either site isn't
+    /// part of the triangulation.
+    pub fn add_constraint(&mut self, a: usize, b: usize) -> bool {
+        if a == b || self.outgoing[a] == NONE || self.outgoing[b] == NONE {
+            return false;
+        }
+        let (pa, pb) = (self.points[a], self.points[b]);
+
+        // Split the edge at any sites lying on it, nearest to `a` first
+        let mut between: Vec<usize> = (0..self.points.len())
+            .filter(|&v| {
+                let p = self.points[v];
+                self.outgoing[v] != NONE
+                    && v != a
+                    && v != b
+                    && orient2d(pa, pb, p) == Orientation::Collinear
+                    && Vector2D::dot(p - pa, pb - pa) > 0.0
+                    && Vector2D::dot(p - pb, pa - pb) > 0.0
+            })
+            .collect();
+        if !between.is_empty() {
+            between.sort_by(|&u, &v| {
+                let (du, dv) = (
+                    (self.points[u] - pa).length_squared(),
+                    (self.points[v] - pa).length_squared(),
+                );
+                du.partial_cmp(&dv).unwrap_or(Ordering::Equal)
+            });
+            let mut stops = vec![a];
+            stops.extend(between);
+            stops.push(b);
+            if stops
+                .windows(2)
+                .any(|pair| self.crossing(pair[0], pair[1]).is_none())
+            {
+                return false;
+            }
+            for pair in stops.windows(2) {
+                self.add_constraint(pair[0], pair[1]);
+            }
+            return true;
+        }
+
+        let crossing = match self.crossing(a, b) {
+            Some(crossing) => crossing,
+            None => return false,
+        };
+        // Flip crossing edges whose quadrilateral is convex, coming back to
+        // the others later, until none cross
+        let mut queue: VecDeque<(usize, usize)> = crossing.into_iter().collect();
+        let mut new_edges = Vec::new();
+        while let Some((u, v)) = queue.pop_front() {
+            let e = self.find_edge(u, v).expect("crossing edges exist");
+            let o = self.opposite[e];
+            let (p, q) = (self.vertices[prev(e)], self.vertices[prev(o)]);
+            let convex = orient2d(self.points[p], self.points[q], self.points[u])
+                != orient2d(self.points[p], self.points[q], self.points[v])
+                && orient2d(self.points[p], self.points[q], self.points[u])
+                    != Orientation::Collinear
+                && orient2d(self.points[p], self.points[q], self.points[v])
+                    != Orientation::Collinear;
+            if !convex {
+                queue.push_back((u, v));
+                continue;
+            }
+            self.flip(e, &mut []);
+            if p != a && p != b && q != a && q != b && self.crosses(a, b, p, q) {
+                queue.push_back((p, q));
+            } else {
+                new_edges.push((p, q));
+            }
+        }
+
+        self.constraints.insert((a.min(b), a.max(b)));
+        let edges = new_edges
+            .into_iter()
+            .filter_map(|(u, v)| self.find_edge(u, v))
+            .collect();
+        self.legalise(edges, &mut []);
+        true
+    }
+
+    /// Find the edges crossing the line from `a` to `b`, or `None` if any of
+    /// them are constrained.
+    fn crossing(&self, a: usize, b: usize) -> Option<Vec<(usize, usize)>> {
+        let mut crossing = Vec::new();
+        for e in 0..self.vertices.len() {
+            let (u, v) = (self.vertices[e], self.vertices[next(e)]);
+            // Count each edge once, from its lower vertex
+            if u > v && self.opposite[e] != NONE {
+                continue;
+            }
+            if u != a && u != b && v != a && v != b && self.crosses(a, b, u, v) {
+                if self.is_constrained(u, v) {
+                    return None;
+                }
+                crossing.push((u, v));
+            }
+        }
+        Some(crossing)
+    }
+
+    /// Check whether the line from `a` to `b` crosses the line from `u` to `v`
+    /// at a single point inside both of them.
+    fn crosses(&self, a: usize, b: usize, u: usize, v: usize) -> bool {
+        let p = &self.points;
+        let opposite = |x: Orientation, y: Orientation| {
+            (x == Orientation::Clockwise && y == Orientation::CounterClockwise)
+                || (x == Orientation::CounterClockwise && y == Orientation::Clockwise)
+        };
+        opposite(orient2d(p[a], p[b], p[u]), orient2d(p[a], p[b], p[v]))
+            && opposite(orient2d(p[u], p[v], p[a]), orient2d(p[u], p[v], p[b]))
+    }
+
+    /// Find the half-edge from `u` to `v`, if there is one.
+    fn find_edge(&self, u: usize, v: usize) -> Option<usize> {
+        let start = self.outgoing[u];
+        if start == NONE {
+            return None;
+        }
+        let mut e = start;
+        loop {
+            if self.vertices[next(e)] == v {
+                return Some(e);
+            }
+            e = self.opposite[prev(e)];
+            if e == start {
+                return None;
+            }
+            if e == NONE {
+                break;
+            }
+        }
+        let mut e = start;
+        while self.opposite[e] != NONE {
+            e = next(self.opposite[e]);
+            if self.vertices[next(e)] == v {
+                return Some(e);
+            }
+        }
+        None
+    }
+
+    /// Add a triangle, given its vertices in counter-clockwise order and the
+    /// half-edges opposite each of its edges. Returns its first half-edge.
+    fn add_triangle(&mut self, vertices: [usize; 3], opposite: [usize; 3]) -> usize {
+        let t = self.vertices.len();
+        for i in 0..3 {
+            self.vertices.push(vertices[i]);
+            self.opposite.push(NONE);
+            self.link(t + i, opposite[i]);
+            self.outgoing[vertices[i]] = t + i;
+        }
+        t
+    }
+
+    /// Make two half-edges each other's opposite.
+    fn link(&mut self, a: usize, b: usize) {
+        self.opposite[a] = b;
+        if b != NONE {
+            self.opposite[b] = a;
+        }
+    }
+
+    /// Flip edges which aren't locally Delaunay, starting from the provided
+    /// half-edges and spreading to the edges around each flip, until every
+    /// unconstrained edge is Delaunay.
+    fn legalise(&mut self, mut stack: Vec<usize>, hull_edge: &mut [usize]) {
+        while let Some(a) = stack.pop() {
+            let b = self.opposite[a];
+            if b == NONE {
+                continue;
+            }
+            let (pr, pl) = (self.vertices[a], self.vertices[next(a)]);
+            let (p0, p1) = (self.vertices[prev(a)], self.vertices[prev(b)]);
+            if self.is_constrained(pr, pl) {
+                continue;
+            }
+            let [p0, pr, pl, p1] = [p0, pr, pl, p1].map(|v| self.points[v]);
+            if incircle(p0, pr, pl, p1) == CirclePosition::Inside {
+                self.flip(a, hull_edge);
+                let (a0, b0) = (a - a % 3, b - b % 3);
+                stack.extend_from_slice(&[a, a0 + (a + 1) % 3, b, b0 + (b + 1) % 3]);
+            }
+        }
+    }
+
+    /// Flip the edge shared by two triangles to join their other two vertices.
+    ///
+    /// Before the flip, half-edge `a` runs from `pr` to `pl` with `p0` opposite
+    /// it, and half-edge `b` runs back with `p1` opposite. After it, the
+    /// triangles are `p1, pl, p0` and `p0, pr, p1`, and the new edge is made of
+    /// the half-edges before `a` and before `b`.
+    fn flip(&mut self, a: usize, hull_edge: &mut [usize]) {
+        let b = self.opposite[a];
+        let (al, ar) = (next(a), prev(a));
+        let (bl, br) = (prev(b), next(b));
+        let (pr, pl) = (self.vertices[a], self.vertices[al]);
+        let (p0, p1) = (self.vertices[ar], self.vertices[bl]);
+
+        let (outer_bl, outer_ar) = (self.opposite[bl], self.opposite[ar]);
+        self.vertices[a] = p1;
+        self.vertices[b] = p0;
+        self.link(a, outer_bl);
+        self.link(b, outer_ar);
+        self.link(ar, bl);
+        // Half-edges on the hull moved with their edges
+        if outer_bl == NONE && hull_edge.get(p1) == Some(&bl) {
+            hull_edge[p1] = a;
+        }
+        if outer_ar == NONE && hull_edge.get(p0) == Some(&ar) {
+            hull_edge[p0] = b;
+        }
+        self.outgoing[p1] = a;
+        self.outgoing[pl] = al;
+        self.outgoing[p0] = b;
+        self.outgoing[pr] = br;
+    }
+}
+
+/// Get the next half-edge around a triangle.
+fn next(e: usize) -> usize {
+    match e % 3 {
+        2 => e - 2,
+        _ => e + 1,
+    }
+}
+
+/// Get the previous half-edge around a triangle.
+fn prev(e: usize) -> usize {
+    match e % 3 {
+        0 => e + 2,
+        _ => e - 1,
+    }
+}
+
+/// Choose a small triangle near the middle of the points to start from, with
+/// its vertices in counter-clockwise order, or `None` if the points are all
+/// collinear.
+fn seed_triangle(points: &[Vector2D<f64>], unique: &[usize]) -> Option<[usize; 3]> {
+    let bounds = Box2D::from_points(unique.iter().map(|&i| points[i]))?;
+    let middle = bounds.center();
+    let nearest = |to: Vector2D<f64>, skip: &[usize]| {
+        unique
+            .iter()
+            .copied()
+            .filter(|i| !skip.contains(i))
+            .min_by(|&a, &b| {
+                let (da, db) = (
+                    (points[a] - to).length_squared(),
+                    (points[b] - to).length_squared(),
+                );
+                da.partial_cmp(&db).unwrap_or(Ordering::Equal)
+            })
+    };
+    let i0 = nearest(middle, &[])?;
+    let i1 = nearest(points[i0], &[i0])?;
+    let radius = |i: usize| {
+        let centre = circumcentre(points[i0], points[i1], points[i]);
+        (points[i0] - centre).length_squared()
+    };
+    let i2 = unique
+        .iter()
+        .copied()
+        .filter(|&i| orient2d(points[i0], points[i1], points[i]) != Orientation::Collinear)
+        .min_by(|&a, &b| radius(a).partial_cmp(&radius(b)).unwrap_or(Ordering::Equal))?;
+    if orient2d(points[i0], points[i1], points[i2]) == Orientation::Clockwise {
+        Some([i0, i2, i1])
+    } else {
+        Some([i0, i1, i2])
+    }
+}
+
+/// Get the centre of the circle through three points.
+fn circumcentre(a: Vector2D<f64>, b: Vector2D<f64>, c: Vector2D<f64>) -> Vector2D<f64> {
+    let (ab, ac) = (b - a, c - a);
+    let (ab2, ac2) = (ab.length_squared(), ac.length_squared());
+    let d = 2.0 * (ab.x * ac.y - ab.y * ac.x);
+    a + Vector2D::new(ac.y * ab2 - ab.y * ac2, ab.x * ac2 - ac.x * ab2) / d
+}
+
+/// A Voronoi diagram clipped to a rectangle. See the [module documentation] for
+/// details.
+///
+/// [module documentation]: index.html
+#[derive(Clone, Debug, PartialEq)]
+pub struct Voronoi {
+    cells: Vec<Vec<Vector2D<f64>>>,
+    neighbours: Vec<Vec<usize>>,
+}
+
+impl Voronoi {
+    /// Find the Voronoi cell of each site within `bounds`. Each cell is the
+    /// rectangle clipped by the lines halfway between its site and each of
+    /// its neighbours in the Delaunay triangulation.
+    pub fn new(sites: &[Vector2D<f64>], bounds: Box2D<f64>) -> Self {
+        let delaunay = Delaunay::new(sites);
+        let triangulated = delaunay.triangles().next().is_some();
+        let tolerance = bounds.size().length_squared() * 1e-20;
+        let mut cells = Vec::with_capacity(sites.len());
+        let mut neighbours = Vec::with_capacity(sites.len());
+        for site in 0..sites.len() {
+            // Without any triangles, the sites lie on a line, and each cell may
+            // border any of the others
+            let candidates = if triangulated {
+                delaunay.neighbours(site)
+            } else if sites[..site].contains(&sites[site]) {
+                Vec::new()
+            } else {
+                (0..sites.len())
+                    .filter(|&other| sites[other] != sites[site])
+                    .collect()
+            };
+            if candidates.is_empty() && (triangulated || sites[..site].contains(&sites[site])) {
+                // A repeated site has an empty cell
+                cells.push(Vec::new());
+                neighbours.push(Vec::new());
+                continue;
+            }
+
+            let mut cell: Vec<(Vector2D<f64>, Option<usize>)> = vec![
+                (bounds.min, None),
+                (Vector2D::new(bounds.max.x, bounds.min.y), None),
+                (bounds.max, None),
+                (Vector2D::new(bounds.min.x, bounds.max.y), None),
+            ];
+            let p = sites[site];
+            for other in candidates {
+                let q = sites[other];
+                let normal = q - p;
+                let offset = Vector2D::dot(normal, (p + q) / 2.0);
+                cell = clip(&cell, normal, offset, other);
+            }
+
+            // Cells meeting at a single corner, such as on a grid, only share an
+            // edge as long as the rounding error in its ends
+            let mut borders: Vec<usize> = Vec::new();
+            for (i, &(start, label)) in cell.iter().enumerate() {
+                let end = cell[(i + 1) % cell.len()].0;
+                if let Some(other) = label {
+                    if (end - start).length_squared() > tolerance && !borders.contains(&other) {
+                        borders.push(other);
+                    }
+                }
+            }
+            cells.push(cell.into_iter().map(|(vertex, _)| vertex).collect());
+            neighbours.push(borders);
+        }
+        // Only keep neighbours which agree with each other
+        let agreed: Vec<Vec<usize>> = (0..sites.len())
+            .map(|site| {
+                neighbours[site]
+                    .iter()
+                    .copied()
+                    .filter(|&other| neighbours[other].contains(&site))
+                    .collect()
+            })
+            .collect();
+        let neighbours = agreed;
+        Self { cells, neighbours }
+    }
+
+    /// Get the vertices of a site's cell, in counter-clockwise order. The cell
+    /// is empty if it lies entirely outside of the bounds, or the site repeats
+    /// an earlier site.
+    pub fn cell(&self, site: usize) -> &[Vector2D<f64>] {
+        &self.cells[site]
+    }
+
+    /// Get the sites whose cells share an edge with a site's cell, within the
+    /// bounds, in counter-clockwise order around it.
+    pub fn neighbours(&self, site: usize) -> &[usize] {
+        &self.neighbours[site]
+    }
+}
+
+/// Clip a convex polygon to the half-plane where `dot(normal, p) <= offset`,
+/// labelling the new edge along the boundary with `label`. Each vertex is
+/// paired with the label of the edge starting from it.
+fn clip(
+    polygon: &[(Vector2D<f64>, Option<usize>)],
+    normal: Vector2D<f64>,
+    offset: f64,
+    label: usize,
+) -> Vec<(Vector2D<f64>, Option<usize>)> {
+    let mut result = Vec::with_capacity(polygon.len() + 1);
+    for (i, &(start, start_label)) in polygon.iter().enumerate() {
+        let end = polygon[(i + 1) % polygon.len()].0;
+        let (ds, de) = (
+            Vector2D::dot(normal, start) - offset,
+            Vector2D::dot(normal, end) - offset,
+        );
+        if ds <= 0.0 {
+            result.push((start, start_label));
+        }
+        if (ds < 0.0 && de > 0.0) || (ds > 0.0 && de < 0.0) {
+            let crossing = start + (end - start) * (ds / (ds - de));
+            // Leaving the half-plane, the edge continues along the boundary
+            let crossing_label = if ds < 0.0 { Some(label) } else { start_label };
+            result.push((crossing, crossing_label));
+        } else if ds <= 0.0 && de > 0.0 {
+            // Leaving from a vertex on the boundary
+            let last = result.len() - 1;
+            result[last].1 = Some(label);
+        }
+    }
+    result
+}
@@ -0,0 +1,185 @@
+//! Standard easing curves, for animations that speed up and slow down more
+//! naturally than a linear interpolation.
+//!
+//! Each curve maps progress through an animation, from 0 to 1, to how far the
+//! animated value has moved from its start to its end. The curves follow the
+//! common Robert Penner definitions. `Back` and `Elastic` curves overshoot, so
+//! their results can fall outside of `[0, 1]`.
+//!
+//! # Example
+//! ```
+//! use vector2d::easing::Easing;
+//! use vector2d::Vector2D;
+//!
+//! let start = Vector2D::new(0.0, 0.0);
+//! let end = Vector2D::new(100.0, 50.0);
+//!
+//! let eased = Easing::QuadIn.apply(0.5f64);
+//! assert_eq!(Vector2D::new(25.0, 12.5), Vector2D::lerp(start, end, eased));
+//! ```
+
+use crate::float::Float;
+
+/// An easing curve. See the [module documentation] for details.
+///
+/// Curves ending in `In` start slowly, those ending in `Out` finish slowly,
+/// and those ending in `InOut` do both.
+///
+/// [module documentation]: index.html
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum Easing {
+    Linear,
+    QuadIn,
+    QuadOut,
+    QuadInOut,
+    CubicIn,
+    CubicOut,
+    CubicInOut,
+    QuartIn,
+    QuartOut,
+    QuartInOut,
+    QuintIn,
+    QuintOut,
+    QuintInOut,
+    SineIn,
+    SineOut,
+    SineInOut,
+    ExpoIn,
+    ExpoOut,
+    ExpoInOut,
+    CircIn,
+    CircOut,
+    CircInOut,
+    BackIn,
+    BackOut,
+    BackInOut,
+    ElasticIn,
+    ElasticOut,
+    ElasticInOut,
+    BounceIn,
+    BounceOut,
+    BounceInOut,
+}
+
+impl Easing {
+    /// Apply the curve to `t`, which is clamped to `[0, 1]`. Every curve maps 0
+    /// to 0 and 1 to 1.
+    pub fn apply<T: Float>(self, t: T) -> T {
+        let t = if t < T::ZERO {
+            T::ZERO
+        } else if t > T::ONE {
+            T::ONE
+        } else {
+            t
+        };
+        match self {
+            Easing::Linear => t,
+            Easing::QuadIn => power_in(t, 2),
+            Easing::QuadOut => out(t, |t| power_in(t, 2)),
+            Easing::QuadInOut => in_out(t, |t| power_in(t, 2)),
+            Easing::CubicIn => power_in(t, 3),
+            Easing::CubicOut => out(t, |t| power_in(t, 3)),
+            Easing::CubicInOut => in_out(t, |t| power_in(t, 3)),
+            Easing::QuartIn => power_in(t, 4),
+            Easing::QuartOut => out(t, |t| power_in(t, 4)),
+            Easing::QuartInOut => in_out(t, |t| power_in(t, 4)),
+            Easing::QuintIn => power_in(t, 5),
+            Easing::QuintOut => out(t, |t| power_in(t, 5)),
+            Easing::QuintInOut => in_out(t, |t| power_in(t, 5)),
+            Easing::SineIn => sine_in(t),
+            Easing::SineOut => out(t, sine_in),
+            Easing::SineInOut => in_out(t, sine_in),
+            Easing::ExpoIn => expo_in(t),
+            Easing::ExpoOut => out(t, expo_in),
+            Easing::ExpoInOut => in_out(t, expo_in),
+            Easing::CircIn => circ_in(t),
+            Easing::CircOut => out(t, circ_in),
+            Easing::CircInOut => in_out(t, circ_in),
+            Easing::BackIn => back_in(t, T::from_f64(BACK_OVERSHOOT)),
+            Easing::BackOut => out(t, |t| back_in(t, T::from_f64(BACK_OVERSHOOT))),
+            Easing::BackInOut => in_out(t, |t| back_in(t, T::from_f64(BACK_OVERSHOOT * 1.525))),
+            Easing::ElasticIn => elastic_in(t, T::from_f64(10.75), T::TAU / T::from_f64(3.0)),
+            Easing::ElasticOut => out(t, |t| {
+                elastic_in(t, T::from_f64(10.75), T::TAU / T::from_f64(3.0))
+            }),
+            Easing::ElasticInOut => in_out(t, |t| {
+                elastic_in(t, T::from_f64(11.125), T::TAU / T::from_f64(4.5))
+            }),
+            Easing::BounceIn => out(t, bounce_out),
+            Easing::BounceOut => bounce_out(t),
+            Easing::BounceInOut => in_out(t, |t| T::ONE - bounce_out(T::ONE - t)),
+        }
+    }
+}
+
+/// Mirror an `In` curve to get the corresponding `Out` curve.
+fn out<T: Float, F: Fn(T) -> T>(t: T, ease_in: F) -> T {
+    T::ONE - ease_in(T::ONE - t)
+}
+
+/// Join an `In` curve with its `Out` counterpart, each taking half the time.
+fn in_out<T: Float, F: Fn(T) -> T>(t: T, ease_in: F) -> T {
+    let two = T::from_f64(2.0);
+    let half = T::from_f64(0.5);
+    if t < half {
+        ease_in(t * two) * half
+    } else {
+        T::ONE - ease_in((T::ONE - t) * two) * half
+    }
+}
+
+fn power_in<T: Float>(t: T, power: u32) -> T {
+    (1..power).fold(t, |acc, _| acc * t)
+}
+
+fn sine_in<T: Float>(t: T) -> T {
+    T::ONE - (t * T::FRAC_PI_2).cos()
+}
+
+fn expo_in<T: Float>(t: T) -> T {
+    if t == T::ZERO {
+        T::ZERO
+    } else {
+        // 2^(10t - 10)
+        ((t * T::from_f64(10.0) - T::from_f64(10.0)) * T::from_f64(std::f64::consts::LN_2)).exp()
+    }
+}
+
+fn circ_in<T: Float>(t: T) -> T {
+    T::ONE - (T::ONE - t * t).sqrt()
+}
+
+/// How far the `Back` curves overshoot.
+const BACK_OVERSHOOT: f64 = 1.70158;
+
+fn back_in<T: Float>(t: T, overshoot: T) -> T {
+    (overshoot + T::ONE) * t * t * t - overshoot * t * t
+}
+
+/// An exponentially growing sine wave, with the phase offset and angular
+/// frequency chosen so that it ends at 1.
+fn elastic_in<T: Float>(t: T, phase: T, frequency: T) -> T {
+    if t == T::ZERO || t == T::ONE {
+        return t;
+    }
+    let ten = T::from_f64(10.0);
+    let decay = ((t * ten - ten) * T::from_f64(std::f64::consts::LN_2)).exp();
+    -decay * ((t * ten - phase) * frequency).sin()
+}
+
+fn bounce_out<T: Float>(t: T) -> T {
+    let n1 = T::from_f64(7.5625);
+    let d1 = T::from_f64(2.75);
+    if t < T::ONE / d1 {
+        n1 * t * t
+    } else if t < T::from_f64(2.0) / d1 {
+        let t = t - T::from_f64(1.5) / d1;
+        n1 * t * t + T::from_f64(0.75)
+    } else if t < T::from_f64(2.5) / d1 {
+        let t = t - T::from_f64(2.25) / d1;
+        n1 * t * t + T::from_f64(0.9375)
+    } else {
+        let t = t - T::from_f64(2.625) / d1;
+        n1 * t * t + T::from_f64(0.984375)
+    }
+}
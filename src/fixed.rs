@@ -0,0 +1,536 @@
+//! A deterministic fixed-point number type, usable as the component type of a
+//! [`Vector2D`].
+//!
+//! [`Fixed`] stores its value as an `i64` with a configurable number of
+//! fractional bits. Every operation, including the square root and trig
+//! functions, is performed with integer arithmetic, so results are identical
+//! across platforms and compilers.
+//!
+//! [`Vector2D`]: ../struct.Vector2D.html
+//! [`Fixed`]: struct.Fixed.html
+
+use crate::Vector2D;
+use std::cmp::Ordering;
+use std::convert::TryFrom;
+use std::fmt;
+use std::ops::{
+    Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Rem, RemAssign, Sub, SubAssign,
+};
+
+/// Pi with 61 fractional bits, from which all other constants are derived.
+const PI_Q61: i64 = 7_244_019_458_077_122_842;
+
+/// The natural logarithm of 2 with 61 fractional bits.
+const LN_2_Q61: i64 = 1_598_288_580_650_331_957;
+
+/// Number of fractional bits used for intermediate results in the square root
+/// and trig functions.
+const INTERNAL_FRAC: u32 = 32;
+const INTERNAL_ONE: i128 = 1 << INTERNAL_FRAC;
+const INTERNAL_PI: i128 = (PI_Q61 >> (61 - INTERNAL_FRAC)) as i128;
+const INTERNAL_FRAC_PI_2: i128 = INTERNAL_PI / 2;
+const INTERNAL_TAU: i128 = INTERNAL_PI * 2;
+const INTERNAL_LN_2: i128 = (LN_2_Q61 >> (61 - INTERNAL_FRAC)) as i128;
+
+/// A signed fixed-point number with `FRAC` fractional bits, stored in an `i64`.
+///
+/// `FRAC` defaults to 16, giving a range of roughly ±1.4e14 with a precision of
+/// about 1.5e-5. It must be no greater than 61, which is checked at compile
+/// time.
+///
+/// `Fixed` implements all of the operator traits that `Vector2D` relies upon,
+/// and `Vector2D<Fixed>` provides `length()`, `normalise()`, and `angle()`, so it
+/// can be used in place of a `Vector2D<f32>` wherever determinism matters.
+/// Overflow and division by zero behave as they do for `i64`: overflow panics
+/// in debug builds and wraps in release builds, including when the result of
+/// a multiplication or division doesn't fit, and division by zero always
+/// panics.
+///
+/// # Example
+/// ```
+/// use vector2d::{Fixed, Vector2D};
+/// let v: Vector2D<Fixed> = Vector2D::new(Fixed::from_int(3), Fixed::from_int(4));
+/// assert_eq!(Fixed::from_int(5), v.length());
+/// ```
+///
+/// Too many fractional bits is a compile-time error:
+/// ```compile_fail
+/// use vector2d::Fixed;
+/// let _ = Fixed::<62>::from_int(1);
+/// ```
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct Fixed<const FRAC: u32 = 16> {
+    bits: i64,
+}
+
+impl<const FRAC: u32> Fixed<FRAC> {
+    /// Fails to compile when `FRAC` is out of range, wherever a value is made.
+    const VALID_FRAC: () = assert!(FRAC <= 61, "Fixed allows at most 61 fractional bits");
+
+    /// The value 0.
+    pub const ZERO: Self = Self::from_bits(0);
+
+    /// The value 1.
+    pub const ONE: Self = Self::from_bits(1 << FRAC);
+
+    /// The smallest representable value.
+    pub const MIN: Self = Self::from_bits(i64::MIN);
+
+    /// The largest representable value.
+    pub const MAX: Self = Self::from_bits(i64::MAX);
+
+    /// Archimedes' constant (π).
+    pub const PI: Self = Self::from_bits(from_q61(PI_Q61, FRAC));
+
+    /// π/2
+    pub const FRAC_PI_2: Self = Self::from_bits(from_q61(PI_Q61 / 2, FRAC));
+
+    /// The full circle constant (τ = 2π).
+    pub const TAU: Self = Self::from_bits(from_q61(PI_Q61, FRAC) * 2);
+
+    /// Create a `Fixed` from its raw underlying representation.
+    pub const fn from_bits(bits: i64) -> Self {
+        #[allow(clippy::let_unit_value)]
+        let () = Self::VALID_FRAC;
+        Self { bits }
+    }
+
+    /// Get the raw underlying representation of this value.
+    pub const fn to_bits(self) -> i64 {
+        self.bits
+    }
+
+    /// Create a `Fixed` holding the provided integer.
+    pub const fn from_int(n: i32) -> Self {
+        Self::from_bits((n as i64) << FRAC)
+    }
+
+    /// Create a `Fixed` from an `f32`, rounding to the nearest representable
+    /// value. Float conversions are not guaranteed to be deterministic across
+    /// platforms, so they are best kept to initialisation and display.
+    pub fn from_f32(n: f32) -> Self {
+        Self::from_f64(n as f64)
+    }
+
+    /// Create a `Fixed` from an `f64`, rounding to the nearest representable
+    /// value. Float conversions are not guaranteed to be deterministic across
+    /// platforms, so they are best kept to initialisation and display.
+    pub fn from_f64(n: f64) -> Self {
+        Self::from_bits((n * (1u64 << FRAC) as f64).round() as i64)
+    }
+
+    /// Convert this value to the nearest `f32`.
+    pub fn to_f32(self) -> f32 {
+        self.to_f64() as f32
+    }
+
+    /// Convert this value to the nearest `f64`.
+    pub fn to_f64(self) -> f64 {
+        self.bits as f64 / (1u64 << FRAC) as f64
+    }
+
+    /// Get the integer part of this value, rounding towards negative infinity.
+    pub const fn to_int(self) -> i64 {
+        self.bits >> FRAC
+    }
+
+    /// Get the absolute value.
+    pub const fn abs(self) -> Self {
+        Self {
+            bits: self.bits.abs(),
+        }
+    }
+
+    /// Get the largest integer less than or equal to this value.
+    pub const fn floor(self) -> Self {
+        Self {
+            bits: (self.bits >> FRAC) << FRAC,
+        }
+    }
+
+    /// Get the smallest integer greater than or equal to this value.
+    pub const fn ceil(self) -> Self {
+        Self {
+            bits: -((-self.bits >> FRAC) << FRAC),
+        }
+    }
+
+    /// Get the nearest integer to this value, rounding half-way cases towards
+    /// positive infinity.
+    pub const fn round(self) -> Self {
+        Self {
+            bits: ((self.bits + (1 << FRAC >> 1)) >> FRAC) << FRAC,
+        }
+    }
+
+    /// Get the square root of this value. Negative values have a square root of
+    /// zero.
+    pub fn sqrt(self) -> Self {
+        if self.bits <= 0 {
+            return Self::ZERO;
+        }
+        Self {
+            bits: isqrt((self.bits as u128) << FRAC) as i64,
+        }
+    }
+
+    /// Get the length of the hypotenuse of a right-angle triangle with sides of
+    /// length `self` and `other`, without overflowing on large inputs. Lengths
+    /// too large to represent saturate at [`MAX`].
+    ///
+    /// [`MAX`]: #associatedconstant.MAX
+    pub fn hypot(self, other: Self) -> Self {
+        // Each square is at most 2¹²⁶, so their sum fits in a u128
+        let x = self.bits.unsigned_abs() as u128;
+        let y = other.bits.unsigned_abs() as u128;
+        let root = isqrt(x * x + y * y);
+        Self {
+            bits: i64::try_from(root).unwrap_or(i64::MAX),
+        }
+    }
+
+    /// Get the sine of this value, in radians.
+    pub fn sin(self) -> Self {
+        Self::from_internal(internal_sin(self.to_internal()))
+    }
+
+    /// Get the cosine of this value, in radians.
+    pub fn cos(self) -> Self {
+        Self::from_internal(internal_sin(self.to_internal() + INTERNAL_FRAC_PI_2))
+    }
+
+    /// Get the tangent of this value, in radians.
+    pub fn tan(self) -> Self {
+        self.sin() / self.cos()
+    }
+
+    /// Get the arctangent of this value, in radians.
+    pub fn atan(self) -> Self {
+        Self::from_internal(internal_atan(self.to_internal()))
+    }
+
+    /// Get the four quadrant arctangent of `self` (y) and `other` (x), in
+    /// radians. The result lies within `(-π, π]`, and is zero when both values
+    /// are zero.
+    pub fn atan2(self, other: Self) -> Self {
+        let y = self.to_internal();
+        let x = other.to_internal();
+        let angle = match (x.cmp(&0), y.cmp(&0)) {
+            (Ordering::Equal, Ordering::Equal) => 0,
+            (Ordering::Equal, Ordering::Greater) => INTERNAL_FRAC_PI_2,
+            (Ordering::Equal, Ordering::Less) => -INTERNAL_FRAC_PI_2,
+            (Ordering::Greater, _) => internal_atan((y << INTERNAL_FRAC) / x),
+            (Ordering::Less, Ordering::Less) => {
+                internal_atan((y << INTERNAL_FRAC) / x) - INTERNAL_PI
+            }
+            (Ordering::Less, _) => internal_atan((y << INTERNAL_FRAC) / x) + INTERNAL_PI,
+        };
+        Self::from_internal(angle)
+    }
+
+    /// Get e raised to the power of this value. Results too large to be
+    /// represented saturate to `MAX`.
+    pub fn exp(self) -> Self {
+        let x = self.to_internal();
+
+        // Split x into k*ln(2) + r, so that e^x = 2^k * e^r with |r| <= ln(2)/2
+        let k = (x + INTERNAL_LN_2 / 2).div_euclid(INTERNAL_LN_2);
+        if k > 63 {
+            return Self::MAX;
+        }
+        if k < -(INTERNAL_FRAC as i128) - 1 {
+            return Self::ZERO;
+        }
+        let r = x - k * INTERNAL_LN_2;
+
+        let mut term = INTERNAL_ONE;
+        let mut sum = INTERNAL_ONE;
+        let mut n = 0;
+        while term != 0 {
+            n += 1;
+            term = internal_mul(term, r) / n;
+            sum += term;
+        }
+
+        let shifted = if k >= 0 { sum << k } else { sum >> -k };
+        let max = Self::MAX.to_internal();
+        if shifted > max {
+            Self::MAX
+        } else {
+            Self::from_internal(shifted)
+        }
+    }
+
+    fn to_internal(self) -> i128 {
+        if FRAC <= INTERNAL_FRAC {
+            (self.bits as i128) << (INTERNAL_FRAC - FRAC)
+        } else {
+            (self.bits as i128) >> (FRAC - INTERNAL_FRAC)
+        }
+    }
+
+    fn from_internal(n: i128) -> Self {
+        let bits = if FRAC <= INTERNAL_FRAC {
+            let shift = INTERNAL_FRAC - FRAC;
+            if shift == 0 {
+                n
+            } else {
+                (n + (1 << (shift - 1))) >> shift
+            }
+        } else {
+            n << (FRAC - INTERNAL_FRAC)
+        };
+        Self { bits: bits as i64 }
+    }
+}
+
+/// Convert a constant with 61 fractional bits to one with `frac` fractional
+/// bits, rounding to nearest.
+const fn from_q61(n: i64, frac: u32) -> i64 {
+    if frac >= 61 {
+        n
+    } else {
+        let shift = 61 - frac;
+        (n + (1 << (shift - 1))) >> shift
+    }
+}
+
+/// Integer square root, rounded down.
+fn isqrt(n: u128) -> u128 {
+    if n < 2 {
+        return n;
+    }
+    let mut bit = 1u128 << ((127 - n.leading_zeros()) & !1);
+    let mut rem = n;
+    let mut root = 0u128;
+    while bit != 0 {
+        if rem >= root + bit {
+            rem -= root + bit;
+            root = (root >> 1) + bit;
+        } else {
+            root >>= 1;
+        }
+        bit >>= 2;
+    }
+    root
+}
+
+/// Narrow a wide intermediate result to an `i64` as its arithmetic would,
+/// panicking on overflow in debug builds and wrapping in release builds.
+fn narrow(n: i128) -> i64 {
+    if cfg!(debug_assertions) {
+        i64::try_from(n).expect("Fixed arithmetic overflowed")
+    } else {
+        n as i64
+    }
+}
+
+fn internal_mul(a: i128, b: i128) -> i128 {
+    (a * b) >> INTERNAL_FRAC
+}
+
+/// Sine of a value with `INTERNAL_FRAC` fractional bits.
+fn internal_sin(x: i128) -> i128 {
+    // Reduce to [-π, π], then to [-π/2, π/2] where the series converges quickly
+    let mut x = x.rem_euclid(INTERNAL_TAU);
+    if x > INTERNAL_PI {
+        x -= INTERNAL_TAU;
+    }
+    if x > INTERNAL_FRAC_PI_2 {
+        x = INTERNAL_PI - x;
+    } else if x < -INTERNAL_FRAC_PI_2 {
+        x = -INTERNAL_PI - x;
+    }
+
+    let x2 = internal_mul(x, x);
+    let mut term = x;
+    let mut sum = x;
+    for n in 1..=8 {
+        term = -internal_mul(term, x2) / ((2 * n) * (2 * n + 1));
+        sum += term;
+    }
+    sum
+}
+
+/// Arctangent of a value with `INTERNAL_FRAC` fractional bits.
+fn internal_atan(x: i128) -> i128 {
+    if x < 0 {
+        return -internal_atan(-x);
+    }
+    if x > INTERNAL_ONE {
+        return INTERNAL_FRAC_PI_2 - internal_atan((INTERNAL_ONE << INTERNAL_FRAC) / x);
+    }
+
+    // Halve the angle once using atan(x) = 2atan(x / (1 + sqrt(1 + x²))), so
+    // that the series is only evaluated for x <= tan(π/8)
+    let root = isqrt(((INTERNAL_ONE + internal_mul(x, x)) << INTERNAL_FRAC) as u128) as i128;
+    let x = (x << INTERNAL_FRAC) / (INTERNAL_ONE + root);
+
+    let x2 = internal_mul(x, x);
+    let mut power = x;
+    let mut sum = x;
+    let mut n = 1;
+    while power != 0 {
+        power = -internal_mul(power, x2);
+        n += 2;
+        sum += power / n;
+    }
+    sum * 2
+}
+
+impl<const FRAC: u32> fmt::Debug for Fixed<FRAC> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Debug::fmt(&self.to_f64(), f)
+    }
+}
+
+impl<const FRAC: u32> fmt::Display for Fixed<FRAC> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(&self.to_f64(), f)
+    }
+}
+
+// From/Into Implementations
+
+macro_rules! impl_from_int {
+    ($($ty:ty),*) => {
+        $(
+            impl<const FRAC: u32> From<$ty> for Fixed<FRAC> {
+                fn from(n: $ty) -> Self {
+                    Self::from_bits((n as i64) << FRAC)
+                }
+            }
+        )*
+    };
+}
+
+impl_from_int!(i8, i16, i32, u8, u16);
+
+impl<const FRAC: u32> From<Fixed<FRAC>> for f32 {
+    fn from(n: Fixed<FRAC>) -> f32 {
+        n.to_f32()
+    }
+}
+
+impl<const FRAC: u32> From<Fixed<FRAC>> for f64 {
+    fn from(n: Fixed<FRAC>) -> f64 {
+        n.to_f64()
+    }
+}
+
+impl<const FRAC: u32> Default for Fixed<FRAC> {
+    fn default() -> Self {
+        Self::ZERO
+    }
+}
+
+// Ops Implementations
+
+impl<const FRAC: u32> Add for Fixed<FRAC> {
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self {
+        Self {
+            bits: self.bits + rhs.bits,
+        }
+    }
+}
+
+impl<const FRAC: u32> Sub for Fixed<FRAC> {
+    type Output = Self;
+    fn sub(self, rhs: Self) -> Self {
+        Self {
+            bits: self.bits - rhs.bits,
+        }
+    }
+}
+
+impl<const FRAC: u32> Mul for Fixed<FRAC> {
+    type Output = Self;
+    fn mul(self, rhs: Self) -> Self {
+        Self {
+            bits: narrow((self.bits as i128 * rhs.bits as i128) >> FRAC),
+        }
+    }
+}
+
+impl<const FRAC: u32> Div for Fixed<FRAC> {
+    type Output = Self;
+    fn div(self, rhs: Self) -> Self {
+        Self {
+            bits: narrow(((self.bits as i128) << FRAC) / rhs.bits as i128),
+        }
+    }
+}
+
+impl<const FRAC: u32> Rem for Fixed<FRAC> {
+    type Output = Self;
+    fn rem(self, rhs: Self) -> Self {
+        Self {
+            bits: self.bits % rhs.bits,
+        }
+    }
+}
+
+impl<const FRAC: u32> Neg for Fixed<FRAC> {
+    type Output = Self;
+    fn neg(self) -> Self {
+        Self { bits: -self.bits }
+    }
+}
+
+impl<const FRAC: u32> AddAssign for Fixed<FRAC> {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl<const FRAC: u32> SubAssign for Fixed<FRAC> {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+impl<const FRAC: u32> MulAssign for Fixed<FRAC> {
+    fn mul_assign(&mut self, rhs: Self) {
+        *self = *self * rhs;
+    }
+}
+
+impl<const FRAC: u32> DivAssign for Fixed<FRAC> {
+    fn div_assign(&mut self, rhs: Self) {
+        *self = *self / rhs;
+    }
+}
+
+impl<const FRAC: u32> RemAssign for Fixed<FRAC> {
+    fn rem_assign(&mut self, rhs: Self) {
+        *self = *self % rhs;
+    }
+}
+
+// Vector2D Implementations
+
+impl<const FRAC: u32> Vector2D<Fixed<FRAC>> {
+    /// Get the length of the vector. If possible, favour `length_squared()` over
+    /// this function, as it is more performant.
+    pub fn length(self) -> Fixed<FRAC> {
+        self.x.hypot(self.y)
+    }
+
+    /// Get a new vector with the same direction as this vector, but with a length
+    /// of 1.0. If the the length of the vector is 0, then the original vector is
+    /// returned.
+    pub fn normalise(self) -> Self {
+        let len = self.length();
+        if len == Fixed::ZERO {
+            self
+        } else {
+            self / len
+        }
+    }
+
+    /// Get the vector's direction in radians.
+    pub fn angle(self) -> Fixed<FRAC> {
+        self.y.atan2(self.x)
+    }
+}
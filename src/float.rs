@@ -0,0 +1,141 @@
+//! A trait abstracting over the real number types usable with the
+//! functionality that needs more than the basic arithmetic operators.
+//!
+//! [`Float`] is implemented for `f32`, `f64`, and [`Fixed`], so types such as
+//! [`Angle`] can be used with any of them.
+//!
+//! [`Float`]: trait.Float.html
+//! [`Fixed`]: ../fixed/struct.Fixed.html
+//! [`Angle`]: ../angle/struct.Angle.html
+
+use crate::Fixed;
+use std::fmt::Debug;
+use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+
+/// A real number type supporting square roots and trigonometry.
+pub trait Float:
+    Add<Self, Output = Self>
+    + Sub<Self, Output = Self>
+    + Mul<Self, Output = Self>
+    + Div<Self, Output = Self>
+    + Neg<Output = Self>
+    + AddAssign
+    + SubAssign
+    + MulAssign
+    + DivAssign
+    + PartialOrd
+    + Default
+    + Debug
+    + Copy
+    + Clone
+{
+    /// The value 0.
+    const ZERO: Self;
+    /// The value 1.
+    const ONE: Self;
+    /// Archimedes' constant (π).
+    const PI: Self;
+    /// π/2
+    const FRAC_PI_2: Self;
+    /// The full circle constant (τ = 2π).
+    const TAU: Self;
+
+    /// Convert from an `f64`, rounding to the nearest representable value.
+    fn from_f64(n: f64) -> Self;
+    /// Convert to the nearest `f64`.
+    fn to_f64(self) -> f64;
+    /// Get the absolute value.
+    fn abs(self) -> Self;
+    /// Get the largest integer less than or equal to the value.
+    fn floor(self) -> Self;
+    /// Get the square root of the value.
+    fn sqrt(self) -> Self;
+    /// Get the sine of the value, in radians.
+    fn sin(self) -> Self;
+    /// Get the cosine of the value, in radians.
+    fn cos(self) -> Self;
+    /// Get the four quadrant arctangent of `self` (y) and `other` (x), in
+    /// radians.
+    fn atan2(self, other: Self) -> Self;
+    /// Get e raised to the power of the value.
+    fn exp(self) -> Self;
+}
+
+macro_rules! impl_float {
+    ($ty:ident) => {
+        impl Float for $ty {
+            const ZERO: Self = 0.0;
+            const ONE: Self = 1.0;
+            const PI: Self = std::$ty::consts::PI;
+            const FRAC_PI_2: Self = std::$ty::consts::FRAC_PI_2;
+            const TAU: Self = std::$ty::consts::PI * 2.0;
+
+            fn from_f64(n: f64) -> Self {
+                n as $ty
+            }
+            fn to_f64(self) -> f64 {
+                self as f64
+            }
+            fn abs(self) -> Self {
+                $ty::abs(self)
+            }
+            fn floor(self) -> Self {
+                $ty::floor(self)
+            }
+            fn sqrt(self) -> Self {
+                $ty::sqrt(self)
+            }
+            fn sin(self) -> Self {
+                $ty::sin(self)
+            }
+            fn cos(self) -> Self {
+                $ty::cos(self)
+            }
+            fn atan2(self, other: Self) -> Self {
+                $ty::atan2(self, other)
+            }
+            fn exp(self) -> Self {
+                $ty::exp(self)
+            }
+        }
+    };
+}
+
+impl_float!(f32);
+impl_float!(f64);
+
+impl<const FRAC: u32> Float for Fixed<FRAC> {
+    const ZERO: Self = Fixed::ZERO;
+    const ONE: Self = Fixed::ONE;
+    const PI: Self = Fixed::PI;
+    const FRAC_PI_2: Self = Fixed::FRAC_PI_2;
+    const TAU: Self = Fixed::TAU;
+
+    fn from_f64(n: f64) -> Self {
+        Fixed::from_f64(n)
+    }
+    fn to_f64(self) -> f64 {
+        Fixed::to_f64(self)
+    }
+    fn abs(self) -> Self {
+        Fixed::abs(self)
+    }
+    fn floor(self) -> Self {
+        Fixed::floor(self)
+    }
+    fn sqrt(self) -> Self {
+        Fixed::sqrt(self)
+    }
+    fn sin(self) -> Self {
+        Fixed::sin(self)
+    }
+    fn cos(self) -> Self {
+        Fixed::cos(self)
+    }
+    fn atan2(self, other: Self) -> Self {
+        Fixed::atan2(self, other)
+    }
+    fn exp(self) -> Self {
+        Fixed::exp(self)
+    }
+}
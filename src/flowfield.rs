@@ -0,0 +1,283 @@
+//! Flow fields, for moving crowds of agents towards shared goals.
+//!
+//! Rather than finding a path for every agent, a flow field stores the
+//! direction to move in from every cell of a grid, so any number of agents can
+//! look up where to go from wherever they are. Building one takes three steps:
+//!   - A [`CostMap`] records the cost of entering each cell of a bounded grid,
+//!     or that a cell is blocked.
+//!   - An [`IntegrationField`] stores the cost of reaching the nearest goal from
+//!     every cell, found with Dijkstra's algorithm outwards from the goals.
+//!   - A [`FlowField`] points each cell towards its cheapest neighbour, and can
+//!     be sampled between cell centres with bilinear interpolation.
+//!
+//! Cells are addressed with `Vector2D<i32>` coordinates, and moves between
+//! them follow the same rules as the [`pathfinding`] module.
+//!
+//! # Example
+//! ```
+//! use vector2d::flowfield::{CostMap, FlowField, IntegrationField};
+//! use vector2d::pathfinding::Neighbourhood;
+//! use vector2d::Vector2D;
+//!
+//! let mut map = CostMap::new(5, 3);
+//! // A wall with a gap at the bottom
+//! map.set(Vector2D::new(2, 0), None);
+//! map.set(Vector2D::new(2, 1), None);
+//!
+//! let goals = [Vector2D::new(4, 0)];
+//! let integration = IntegrationField::new(&map, &goals, Neighbourhood::Four);
+//! assert_eq!(Some(8.0), integration.cost(Vector2D::new(0, 0)));
+//!
+//! let flow: FlowField<f32> = FlowField::new(&integration);
+//! // Agents on the far side of the wall head down towards the gap
+//! assert_eq!(Vector2D::new(0.0, 1.0), flow.direction(Vector2D::new(1, 1)));
+//! assert_eq!(Vector2D::new(1.0, 0.0), flow.direction(Vector2D::new(1, 2)));
+//! ```
+//!
+//! [`CostMap`]: struct.CostMap.html
+//! [`IntegrationField`]: struct.IntegrationField.html
+//! [`FlowField`]: struct.FlowField.html
+//! [`pathfinding`]: ../pathfinding/index.html
+
+use crate::float::Float;
+use crate::pathfinding::{GridPathfinder, Neighbourhood, OpenCell, DIAGONAL};
+use crate::Vector2D;
+use std::collections::BinaryHeap;
+
+/// The cost of entering each cell of a bounded grid.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CostMap {
+    width: usize,
+    height: usize,
+    costs: Vec<Option<f64>>,
+}
+
+impl CostMap {
+    /// Create a map of the provided size, where every cell costs 1 to enter.
+    pub fn new(width: usize, height: usize) -> Self {
+        Self {
+            width,
+            height,
+            costs: vec![Some(1.0); width * height],
+        }
+    }
+
+    /// Create a map of the provided size, with the cost of each cell, or
+    /// `None` if it is blocked, given by `cost`.
+    pub fn from_fn<F: FnMut(Vector2D<i32>) -> Option<f64>>(
+        width: usize,
+        height: usize,
+        mut cost: F,
+    ) -> Self {
+        let costs = (0..width * height)
+            .map(|i| cost(Vector2D::new((i % width) as i32, (i / width) as i32)))
+            .collect();
+        Self {
+            width,
+            height,
+            costs,
+        }
+    }
+
+    /// Get the width of the map, in cells.
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Get the height of the map, in cells.
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Get the cost of entering `cell`, or `None` if it is blocked or outside
+    /// of the map.
+    pub fn cost(&self, cell: Vector2D<i32>) -> Option<f64> {
+        self.costs[index(self.width, self.height, cell)?]
+    }
+
+    /// Set the cost of entering `cell`, or block it with `None`.
+    ///
+    /// # Panics
+    /// Panics if `cell` is outside of the map.
+    pub fn set(&mut self, cell: Vector2D<i32>, cost: Option<f64>) {
+        let i = index(self.width, self.height, cell).expect("cell is outside of the map");
+        self.costs[i] = cost;
+    }
+}
+
+/// The cost of reaching the nearest goal from every cell of a [`CostMap`].
+///
+/// [`CostMap`]: struct.CostMap.html
+#[derive(Clone, Debug, PartialEq)]
+pub struct IntegrationField {
+    width: usize,
+    height: usize,
+    neighbourhood: Neighbourhood,
+    costs: Vec<Option<f64>>,
+}
+
+impl IntegrationField {
+    /// Find the cost of reaching the nearest of `goals` from every cell of
+    /// `map`, moving between the provided neighbours. Moves cost the same as
+    /// with a [`GridPathfinder`], so entering a cell costs its value in the
+    /// map, or √2 times as much diagonally. Goals outside of the map or on
+    /// blocked cells are ignored.
+    ///
+    /// [`GridPathfinder`]: ../pathfinding/struct.GridPathfinder.html
+    pub fn new(map: &CostMap, goals: &[Vector2D<i32>], neighbourhood: Neighbourhood) -> Self {
+        let pathfinder =
+            GridPathfinder::new(|cell| map.cost(cell)).with_neighbourhood(neighbourhood);
+        let mut costs = vec![None; map.costs.len()];
+        let mut open = BinaryHeap::new();
+        for &goal in goals {
+            if let (Some(i), Some(_)) = (index(map.width, map.height, goal), map.cost(goal)) {
+                costs[i] = Some(0.0);
+                open.push(OpenCell {
+                    estimate: 0.0,
+                    cost: 0.0,
+                    cell: goal,
+                });
+            }
+        }
+
+        // Dijkstra's algorithm outwards from the goals. Moves are followed
+        // backwards, so the cost of a step is the cost of entering the cell
+        // being expanded, rather than the neighbour.
+        while let Some(OpenCell { cost, cell, .. }) = open.pop() {
+            let i = index(map.width, map.height, cell).expect("only cells on the map are opened");
+            if costs[i].is_some_and(|c| cost > c) {
+                continue;
+            }
+            let entry = map.cost(cell).expect("only open cells are opened");
+            for (next, _) in pathfinder.neighbours(cell) {
+                let diagonal = next.x != cell.x && next.y != cell.y;
+                let next_cost = cost + if diagonal { entry * DIAGONAL } else { entry };
+                let j = index(map.width, map.height, next).expect("open cells are on the map");
+                if costs[j].is_some_and(|c| c <= next_cost) {
+                    continue;
+                }
+                costs[j] = Some(next_cost);
+                open.push(OpenCell {
+                    estimate: next_cost,
+                    cost: next_cost,
+                    cell: next,
+                });
+            }
+        }
+
+        Self {
+            width: map.width,
+            height: map.height,
+            neighbourhood,
+            costs,
+        }
+    }
+
+    /// Get the cost of reaching the nearest goal from `cell`, or `None` if no
+    /// goal can be reached from it.
+    pub fn cost(&self, cell: Vector2D<i32>) -> Option<f64> {
+        self.costs[index(self.width, self.height, cell)?]
+    }
+}
+
+/// The direction to move in from every cell of a grid. See the [module
+/// documentation] for details.
+///
+/// [module documentation]: index.html
+#[derive(Clone, Debug, PartialEq)]
+pub struct FlowField<T> {
+    width: usize,
+    height: usize,
+    directions: Vec<Option<Vector2D<T>>>,
+}
+
+impl<T: Float> FlowField<T> {
+    /// Point every cell towards its neighbour with the lowest cost in
+    /// `integration`. Directions are unit vectors, except at the goals, where
+    /// they are zero.
+    pub fn new(integration: &IntegrationField) -> Self {
+        let pathfinder = GridPathfinder::new(|cell| integration.cost(cell).map(|_| 1.0))
+            .with_neighbourhood(integration.neighbourhood);
+        let (width, height) = (integration.width, integration.height);
+        let directions = (0..width * height)
+            .map(|i| {
+                let cell = Vector2D::new((i % width) as i32, (i / width) as i32);
+                let mut best = (cell, integration.costs[i]?);
+                for (next, _) in pathfinder.neighbours(cell) {
+                    match integration.cost(next) {
+                        Some(cost) if cost < best.1 => best = (next, cost),
+                        _ => {}
+                    }
+                }
+                let step = best.0 - cell;
+                let direction =
+                    Vector2D::new(T::from_f64(step.x as f64), T::from_f64(step.y as f64));
+                if step.x != 0 && step.y != 0 {
+                    Some(direction / T::from_f64(2.0).sqrt())
+                } else {
+                    Some(direction)
+                }
+            })
+            .collect();
+        Self {
+            width,
+            height,
+            directions,
+        }
+    }
+
+    /// Get the direction to move in from `cell`, which is zero at the goals,
+    /// and for cells that are blocked, outside of the grid, or can't reach a
+    /// goal.
+    pub fn direction(&self, cell: Vector2D<i32>) -> Vector2D<T> {
+        index(self.width, self.height, cell)
+            .and_then(|i| self.directions[i])
+            .unwrap_or_else(|| Vector2D::new(T::ZERO, T::ZERO))
+    }
+
+    /// Sample the field at any position, blending the directions of the four
+    /// surrounding cells with bilinear interpolation. Cell centres are at
+    /// their integer coordinates.
+    ///
+    /// Cells that can't reach a goal are left out of the blend, so agents next
+    /// to walls aren't slowed down by them. The result isn't normalised, so
+    /// it can be shorter than 1 where directions diverge.
+    pub fn sample(&self, position: Vector2D<T>) -> Vector2D<T> {
+        let (x, y) = (position.x.floor(), position.y.floor());
+        let (fx, fy) = (position.x - x, position.y - y);
+        let (x, y) = (x.to_f64() as i32, y.to_f64() as i32);
+        let corners = [
+            (0, 0, (T::ONE - fx) * (T::ONE - fy)),
+            (1, 0, fx * (T::ONE - fy)),
+            (0, 1, (T::ONE - fx) * fy),
+            (1, 1, fx * fy),
+        ];
+
+        let mut sum = Vector2D::new(T::ZERO, T::ZERO);
+        let mut total = T::ZERO;
+        for &(dx, dy, weight) in &corners {
+            let cell = Vector2D::new(x + dx, y + dy);
+            if let Some(direction) =
+                index(self.width, self.height, cell).and_then(|i| self.directions[i])
+            {
+                sum += direction * weight;
+                total += weight;
+            }
+        }
+        if total > T::ZERO {
+            sum / total
+        } else {
+            sum
+        }
+    }
+}
+
+/// Get the index of `cell` in a grid of the provided size, or `None` if it is
+/// outside of the grid.
+fn index(width: usize, height: usize, cell: Vector2D<i32>) -> Option<usize> {
+    if cell.x < 0 || cell.y < 0 || cell.x as usize >= width || cell.y as usize >= height {
+        None
+    } else {
+        Some(cell.y as usize * width + cell.x as usize)
+    }
+}
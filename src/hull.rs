@@ -0,0 +1,228 @@
+//! Convex hulls of sets of points.
+//!
+//! The convex hull is the smallest convex polygon containing every point, as
+//! if a rubber band were stretched around them. Two algorithms are provided,
+//! which work with both integer and floating point components:
+//!   - [`convex_hull()`] uses Andrew's monotone chain algorithm, which sorts
+//!     the points and takes O(n log n) time in every case.
+//!   - [`quickhull()`] divides the points around the furthest point from each
+//!     edge, which is often faster when most points lie well inside the hull.
+//!
+//! Both return the hull's vertices in counter-clockwise order, starting from
+//! the point with the lowest x, and then lowest y, coordinate. Duplicate points
+//! are only included once, and points lying exactly on an edge of the hull are
+//! left out, so that every vertex is a corner. [`convex_hull_with()`] can keep
+//! the points on edges instead, with [`Collinear::Include`].
+//!
+//! When the points don't enclose any area, the hull is degenerate: no points
+//! give an empty hull, identical points give a single point, and points along
+//! a line give its two ends, or every distinct point in order along the line
+//! when including collinear points.
+//!
+//! For integer components, the products of differences between coordinates
+//! must fit within the type, so `i32` coordinates should stay within ±16384.
+//!
+//! # Example
+//! ```
+//! use vector2d::hull::convex_hull;
+//! use vector2d::Vector2D;
+//!
+//! let points = [
+//!     Vector2D::new(0, 0),
+//!     Vector2D::new(2, 1),
+//!     Vector2D::new(4, 0),
+//!     Vector2D::new(2, 0),
+//!     Vector2D::new(4, 4),
+//!     Vector2D::new(0, 4),
+//!     Vector2D::new(0, 0),
+//! ];
+//! let hull = convex_hull(&points);
+//! assert_eq!(
+//!     vec![Vector2D::new(0, 0), Vector2D::new(4, 0), Vector2D::new(4, 4), Vector2D::new(0, 4)],
+//!     hull,
+//! );
+//! ```
+//!
+//! [`convex_hull()`]: fn.convex_hull.html
+//! [`convex_hull_with()`]: fn.convex_hull_with.html
+//! [`quickhull()`]: fn.quickhull.html
+//! [`Collinear::Include`]: enum.Collinear.html#variant.Include
+
+use crate::Vector2D;
+use std::cmp::Ordering;
+use std::ops::{Add, Mul, Sub};
+
+/// What to do with points lying exactly on an edge of a hull.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum Collinear {
+    /// Leave them out, so that the hull only contains its corners.
+    Exclude,
+    /// Include them, in order along the edge.
+    Include,
+}
+
+/// Find the convex hull of a set of points with Andrew's monotone chain
+/// algorithm, leaving out points lying on its edges. See the [module
+/// documentation] for details.
+///
+/// [module documentation]: index.html
+pub fn convex_hull<T>(points: &[Vector2D<T>]) -> Vec<Vector2D<T>>
+where
+    T: Copy + PartialOrd + Default + Sub<Output = T> + Mul<Output = T>,
+{
+    convex_hull_with(points, Collinear::Exclude)
+}
+
+/// Find the convex hull of a set of points with Andrew's monotone chain
+/// algorithm, choosing what to do with points lying on its edges.
+pub fn convex_hull_with<T>(points: &[Vector2D<T>], collinear: Collinear) -> Vec<Vector2D<T>>
+where
+    T: Copy + PartialOrd + Default + Sub<Output = T> + Mul<Output = T>,
+{
+    let mut sorted = points.to_vec();
+    sorted.sort_by(compare);
+    sorted.dedup();
+    if sorted.len() < 3 {
+        return sorted;
+    }
+
+    let (first, last) = (sorted[0], sorted[sorted.len() - 1]);
+    if sorted.iter().all(|&p| turn(first, last, p) == T::default()) {
+        return match collinear {
+            Collinear::Exclude => vec![first, last],
+            Collinear::Include => sorted,
+        };
+    }
+
+    // Build the lower and upper halves of the hull from left to right and back,
+    // removing points which would make the chain turn clockwise
+    let keep = |t: T| match collinear {
+        Collinear::Exclude => t > T::default(),
+        Collinear::Include => t >= T::default(),
+    };
+    let mut hull = Vec::with_capacity(sorted.len() + 1);
+    chain(sorted.iter().copied(), &mut hull, keep);
+    chain(sorted.iter().rev().copied(), &mut hull, keep);
+    hull
+}
+
+/// Add one half of a monotone chain hull to `hull`, leaving off its last point,
+/// where the other half begins.
+fn chain<T, I, K>(points: I, hull: &mut Vec<Vector2D<T>>, keep: K)
+where
+    T: Copy + Sub<Output = T> + Mul<Output = T>,
+    I: Iterator<Item = Vector2D<T>>,
+    K: Fn(T) -> bool,
+{
+    let start = hull.len();
+    for p in points {
+        while hull.len() >= start + 2 && !keep(turn(hull[hull.len() - 2], hull[hull.len() - 1], p))
+        {
+            hull.pop();
+        }
+        hull.push(p);
+    }
+    hull.pop();
+}
+
+/// Find the convex hull of a set of points with the Quickhull algorithm,
+/// leaving out points lying on its edges. The result is the same as
+/// [`convex_hull()`].
+///
+/// [`convex_hull()`]: fn.convex_hull.html
+pub fn quickhull<T>(points: &[Vector2D<T>]) -> Vec<Vector2D<T>>
+where
+    T: Copy + PartialOrd + Default + Add<Output = T> + Sub<Output = T> + Mul<Output = T>,
+{
+    let mut iter = points.iter().copied();
+    let first = match iter.next() {
+        Some(first) => first,
+        None => return Vec::new(),
+    };
+    let (min, max) = iter.fold((first, first), |(min, max), p| {
+        (
+            if compare(&p, &min) == Ordering::Less {
+                p
+            } else {
+                min
+            },
+            if compare(&p, &max) == Ordering::Greater {
+                p
+            } else {
+                max
+            },
+        )
+    });
+    if min == max {
+        return vec![min];
+    }
+
+    // The lower half runs from the leftmost point to the rightmost, and the
+    // upper half back again
+    let mut hull = vec![min];
+    let below: Vec<Vector2D<T>> = points
+        .iter()
+        .copied()
+        .filter(|&p| turn(min, max, p) < T::default())
+        .collect();
+    expand(min, max, &below, &mut hull);
+    hull.push(max);
+    let above: Vec<Vector2D<T>> = points
+        .iter()
+        .copied()
+        .filter(|&p| turn(max, min, p) < T::default())
+        .collect();
+    expand(max, min, &above, &mut hull);
+    hull
+}
+
+/// Add the hull vertices between `a` and `b` to `hull`, given the points lying
+/// to the right of the line from `a` to `b`.
+fn expand<T>(a: Vector2D<T>, b: Vector2D<T>, points: &[Vector2D<T>], hull: &mut Vec<Vector2D<T>>)
+where
+    T: Copy + PartialOrd + Default + Add<Output = T> + Sub<Output = T> + Mul<Output = T>,
+{
+    // The point furthest from the line is a vertex of the hull. Of several
+    // equally far, only those at the ends are vertices, so take the one
+    // furthest along the line
+    let along = |p: Vector2D<T>| (b.x - a.x) * (p.x - a.x) + (b.y - a.y) * (p.y - a.y);
+    let furthest = match points.iter().copied().reduce(|furthest, p| {
+        let (distance, furthest_distance) = (turn(a, b, p), turn(a, b, furthest));
+        if distance < furthest_distance
+            || (distance == furthest_distance && along(p) > along(furthest))
+        {
+            p
+        } else {
+            furthest
+        }
+    }) {
+        Some(furthest) => furthest,
+        None => return,
+    };
+    let outside = |from: Vector2D<T>, to: Vector2D<T>| -> Vec<Vector2D<T>> {
+        points
+            .iter()
+            .copied()
+            .filter(|&p| turn(from, to, p) < T::default())
+            .collect()
+    };
+    expand(a, furthest, &outside(a, furthest), hull);
+    hull.push(furthest);
+    expand(furthest, b, &outside(furthest, b), hull);
+}
+
+/// Get twice the signed area of the triangle `a`, `b`, `c`, which is positive
+/// when it winds counter-clockwise.
+fn turn<T>(a: Vector2D<T>, b: Vector2D<T>, c: Vector2D<T>) -> T
+where
+    T: Copy + Sub<Output = T> + Mul<Output = T>,
+{
+    (b.x - a.x) * (c.y - a.y) - (b.y - a.y) * (c.x - a.x)
+}
+
+/// Order points by their x, and then y, coordinates.
+pub(crate) fn compare<T: PartialOrd>(a: &Vector2D<T>, b: &Vector2D<T>) -> Ordering {
+    a.x.partial_cmp(&b.x)
+        .unwrap_or(Ordering::Equal)
+        .then_with(|| a.y.partial_cmp(&b.y).unwrap_or(Ordering::Equal))
+}
@@ -0,0 +1,155 @@
+//! Numerical integration of particle motion.
+//!
+//! A particle is described by its [`ParticleState`], and is moved forward in
+//! time by an [`Integrator`], given a function returning its acceleration.
+//! For a force `F` acting on a particle of mass `m`, the acceleration is
+//! `F / m`.
+//!
+//! # Example
+//! ```
+//! use vector2d::integrate::{Integrator, ParticleState};
+//! use vector2d::Vector2D;
+//!
+//! let gravity = Vector2D::new(0.0f64, -9.81);
+//! let mut state = ParticleState::new(Vector2D::new(0.0, 0.0), Vector2D::new(3.0, 10.0));
+//! for _ in 0..60 {
+//!     state = Integrator::VelocityVerlet.step(state, 0.0, 1.0 / 60.0, |_, _| gravity);
+//! }
+//!
+//! // Verlet is exact under a constant acceleration
+//! let expected = Vector2D::new(3.0, 10.0 - 9.81 / 2.0);
+//! assert!((state.position - expected).length() < 1e-9);
+//! ```
+//!
+//! [`ParticleState`]: struct.ParticleState.html
+//! [`Integrator`]: enum.Integrator.html
+
+use crate::float::Float;
+use crate::Vector2D;
+
+/// The position and velocity of a particle.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct ParticleState<T> {
+    pub position: Vector2D<T>,
+    pub velocity: Vector2D<T>,
+}
+
+impl<T: Float> ParticleState<T> {
+    /// Create a new `ParticleState` with the provided position and velocity.
+    pub fn new(position: Vector2D<T>, velocity: Vector2D<T>) -> Self {
+        Self { position, velocity }
+    }
+}
+
+/// A method of advancing a particle's state through time.
+///
+/// The methods trade accuracy for speed, evaluating the acceleration a
+/// different number of times per step:
+///
+/// | Method              | Evaluations | Order | Notes                                   |
+/// |---------------------|-------------|-------|-----------------------------------------|
+/// | `ExplicitEuler`     | 1           | 1     | Gains energy; oscillators spiral out    |
+/// | `SemiImplicitEuler` | 1           | 1     | Energy stays bounded; a good default    |
+/// | `VelocityVerlet`    | 2           | 2     | Exact for constant accelerations        |
+/// | `Rk4`               | 4           | 4     | Most accurate, for smooth accelerations |
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum Integrator {
+    /// Moves the position with the old velocity, then updates the velocity.
+    ExplicitEuler,
+    /// Updates the velocity, then moves the position with the new velocity.
+    SemiImplicitEuler,
+    /// Moves the position using the current acceleration, then updates the
+    /// velocity with the average of the accelerations before and after.
+    /// Velocity-dependent accelerations are evaluated with a predicted velocity.
+    VelocityVerlet,
+    /// The classic fourth-order Runge-Kutta method.
+    Rk4,
+}
+
+impl Integrator {
+    /// Advance `state` from `time` by `dt`. The `acceleration` function receives
+    /// a state and the time at which it applies, and returns the particle's
+    /// acceleration.
+    pub fn step<T, F>(
+        self,
+        state: ParticleState<T>,
+        time: T,
+        dt: T,
+        acceleration: F,
+    ) -> ParticleState<T>
+    where
+        T: Float,
+        F: Fn(&ParticleState<T>, T) -> Vector2D<T>,
+    {
+        let half = T::from_f64(0.5);
+        match self {
+            Integrator::ExplicitEuler => {
+                let a = acceleration(&state, time);
+                ParticleState::new(
+                    state.position + state.velocity * dt,
+                    state.velocity + a * dt,
+                )
+            }
+            Integrator::SemiImplicitEuler => {
+                let a = acceleration(&state, time);
+                let velocity = state.velocity + a * dt;
+                ParticleState::new(state.position + velocity * dt, velocity)
+            }
+            Integrator::VelocityVerlet => {
+                let a0 = acceleration(&state, time);
+                let position = state.position + state.velocity * dt + a0 * (half * dt * dt);
+                let predicted = ParticleState::new(position, state.velocity + a0 * dt);
+                let a1 = acceleration(&predicted, time + dt);
+                ParticleState::new(position, state.velocity + (a0 + a1) * (half * dt))
+            }
+            Integrator::Rk4 => {
+                let half_dt = dt * half;
+                let k1v = acceleration(&state, time);
+                let k1x = state.velocity;
+
+                let s2 = ParticleState::new(
+                    state.position + k1x * half_dt,
+                    state.velocity + k1v * half_dt,
+                );
+                let k2v = acceleration(&s2, time + half_dt);
+                let k2x = s2.velocity;
+
+                let s3 = ParticleState::new(
+                    state.position + k2x * half_dt,
+                    state.velocity + k2v * half_dt,
+                );
+                let k3v = acceleration(&s3, time + half_dt);
+                let k3x = s3.velocity;
+
+                let s4 = ParticleState::new(state.position + k3x * dt, state.velocity + k3v * dt);
+                let k4v = acceleration(&s4, time + dt);
+                let k4x = s4.velocity;
+
+                let two = T::from_f64(2.0);
+                let sixth = dt / T::from_f64(6.0);
+                ParticleState::new(
+                    state.position + (k1x + k2x * two + k3x * two + k4x) * sixth,
+                    state.velocity + (k1v + k2v * two + k3v * two + k4v) * sixth,
+                )
+            }
+        }
+    }
+
+    /// Advance `state` from `time` by `count` steps of `dt`.
+    pub fn steps<T, F>(
+        self,
+        state: ParticleState<T>,
+        time: T,
+        dt: T,
+        count: usize,
+        acceleration: F,
+    ) -> ParticleState<T>
+    where
+        T: Float,
+        F: Fn(&ParticleState<T>, T) -> Vector2D<T>,
+    {
+        (0..count).fold(state, |s, i| {
+            self.step(s, time + dt * T::from_f64(i as f64), dt, &acceleration)
+        })
+    }
+}
@@ -5,11 +5,77 @@
 //! into any excursions with the law using this library thanks to the awfully
 //! permissive Unlicense.
 //!
-//! The only type in this crate is [`Vector2D`], which is highly generic;
+//! The core type in this crate is [`Vector2D`], which is highly generic;
 //! shifting functionality depending upon the traits implemented by its internal
 //! components' types.
 //!
+//! For deterministic simulations, the [`Fixed`] fixed-point type can be used as
+//! a component type in place of `f32` or `f64`, and vectors can be tagged with
+//! the coordinate space they belong to using the [`space`] module. Where the
+//! distinction between positions and displacements matters, [`Point2D`] can be
+//! used for positions, and [`Transform2D`] applies affine transforms to both.
+//! Directions can be expressed with the unit-safe [`Angle`] type, which works
+//! with any component type implementing [`Float`], and vectors can be converted
+//! to and from [`Polar`] coordinates.
+//!
+//! Beyond straight lines, curves can be built from `Vector2D` control points
+//! with [`Bezier`], and bounded with [`Box2D`]. Smooth paths through many
+//! waypoints are available in the [`spline`] module, and values can be
+//! animated over time with the [`tween`] module's [`Easing`] curves. For
+//! following a moving target, the [`smoothing`] module provides frame-rate
+//! independent alternatives to calling `lerp()` every frame, and the
+//! [`integrate`] module moves particles under arbitrary forces. Solid objects
+//! can be described with the [`shape`] module, tested for overlaps with the
+//! [`collision`] module, and simulated as rigid bodies in a [`physics`] world.
+//! Ropes, cloth and soft bodies can be built from constrained point masses
+//! with the [`verlet`] module, and projectiles can be aimed at moving targets
+//! with the [`ballistics`] module. Autonomous agents can be moved around with
+//! the [`steering`] module's seek, avoidance and flocking behaviours, and
+//! routes across grids of `Vector2D<i32>` cells found with the [`pathfinding`]
+//! module, or across open areas of convex polygons with a [`navmesh`]. Crowds
+//! heading for the same goals can share a single [`flowfield`], and what they
+//! can see is worked out with the [`visibility`] module. Sets of points can be
+//! wrapped in a convex [`hull`], and geometric decisions made exactly with the
+//! [`predicates`] module. Polygons, including those with holes, can be split
+//! into triangles for rendering with the [`triangulate`] module, and
+//! scattered points joined into a [`delaunay`] triangulation or divided into
+//! Voronoi cells. Polygons can be merged, cut and intersected with the
+//! [`boolean`] operations, and grown, shrunk or drawn around with an
+//! [`offset`]. Dense recorded lines, such as gestures, can be simplified,
+//! resampled and smoothed with the [`polyline`] module.
+//!
 //! [`Vector2D`]: struct.Vector2D.html
+//! [`Fixed`]: fixed/struct.Fixed.html
+//! [`space`]: space/index.html
+//! [`Point2D`]: point/struct.Point2D.html
+//! [`Transform2D`]: transform/struct.Transform2D.html
+//! [`Angle`]: angle/struct.Angle.html
+//! [`Float`]: float/trait.Float.html
+//! [`Polar`]: polar/struct.Polar.html
+//! [`Bezier`]: bezier/struct.Bezier.html
+//! [`Box2D`]: box2d/struct.Box2D.html
+//! [`spline`]: spline/index.html
+//! [`tween`]: tween/index.html
+//! [`Easing`]: easing/enum.Easing.html
+//! [`smoothing`]: smoothing/index.html
+//! [`integrate`]: integrate/index.html
+//! [`shape`]: shape/index.html
+//! [`collision`]: collision/index.html
+//! [`physics`]: physics/index.html
+//! [`verlet`]: verlet/index.html
+//! [`ballistics`]: ballistics/index.html
+//! [`steering`]: steering/index.html
+//! [`pathfinding`]: pathfinding/index.html
+//! [`navmesh`]: navmesh/index.html
+//! [`flowfield`]: flowfield/index.html
+//! [`visibility`]: visibility/index.html
+//! [`hull`]: hull/index.html
+//! [`predicates`]: predicates/index.html
+//! [`triangulate`]: triangulate/index.html
+//! [`delaunay`]: delaunay/index.html
+//! [`boolean`]: boolean/index.html
+//! [`offset`]: offset/index.html
+//! [`polyline`]: polyline/index.html
 //!
 //! # Example
 //! ```
@@ -93,6 +159,40 @@
 #[cfg(test)]
 mod test;
 
+pub mod angle;
+pub mod ballistics;
+pub mod bezier;
+pub mod boolean;
+pub mod box2d;
+pub mod collision;
+pub mod delaunay;
+pub mod easing;
+pub mod fixed;
+pub mod float;
+pub mod flowfield;
+pub mod hull;
+pub mod integrate;
+pub mod navmesh;
+pub mod offset;
+pub mod pathfinding;
+pub mod physics;
+pub mod point;
+pub mod polar;
+pub mod polyline;
+pub mod predicates;
+pub mod shape;
+pub mod smoothing;
+pub mod space;
+pub mod spline;
+pub mod steering;
+pub mod transform;
+pub mod triangulate;
+pub mod tween;
+pub mod verlet;
+pub mod visibility;
+
+pub use fixed::Fixed;
+
 use proc_vector2d::{fn_lower_bounded_as, fn_simple_as};
 use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign};
 
@@ -145,7 +245,7 @@ use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssi
 /// [`into_vec2d()`]: struct.Vector2D.html#method.into_vec2d
 /// [`from_vec2d(..)`]: struct.Vector2D.html#method.from_vec2d
 /// [`as_u32s()`]: struct.Vector2D.html#method.as_u32s-1
-#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
 pub struct Vector2D<T> {
     pub x: T,
     pub y: T,
@@ -331,12 +431,12 @@ where
 
 // From/Into Implementations
 
-impl<T, U> Into<(U, U)> for Vector2D<T>
+impl<T, U> From<Vector2D<T>> for (U, U)
 where
     T: Into<U> + Copy + Clone,
 {
-    fn into(self) -> (U, U) {
-        (self.x.into(), self.y.into())
+    fn from(src: Vector2D<T>) -> (U, U) {
+        (src.x.into(), src.y.into())
     }
 }
 
@@ -0,0 +1,391 @@
+//! Navigation meshes, for smooth paths across open areas.
+//!
+//! A [`NavMesh`] covers the walkable area with convex polygons. Polygons that
+//! share an edge are connected through it, so a path between two points is
+//! found by locating the polygons containing them, searching for a corridor
+//! of polygons between them with A*, and then pulling the path tight through
+//! the corridor with the simple stupid funnel algorithm. The result only turns
+//! at the corners it has to go around, rather than following a grid.
+//!
+//! Agents with a radius can keep clear of walls by shrinking each shared edge
+//! by the radius at the ends which touch a wall, which also stops them
+//! squeezing through gaps narrower than they are. Ends in the middle of the
+//! mesh are left alone, so finely divided open areas don't block them.
+//!
+//! # Example
+//! ```
+//! use vector2d::navmesh::NavMesh;
+//! use vector2d::Vector2D;
+//!
+//! // An L-shaped corridor, turning at (10, 10)
+//! let square = |x: f32, y: f32| {
+//!     vec![
+//!         Vector2D::new(x, y),
+//!         Vector2D::new(x + 10.0, y),
+//!         Vector2D::new(x + 10.0, y + 10.0),
+//!         Vector2D::new(x, y + 10.0),
+//!     ]
+//! };
+//! let mesh = NavMesh::new(vec![square(0.0, 0.0), square(10.0, 0.0), square(10.0, 10.0)]);
+//!
+//! let path = mesh.find_path(Vector2D::new(5.0, 5.0), Vector2D::new(12.0, 18.0)).unwrap();
+//! assert_eq!(
+//!     vec![Vector2D::new(5.0, 5.0), Vector2D::new(10.0, 10.0), Vector2D::new(12.0, 18.0)],
+//!     path,
+//! );
+//! ```
+//!
+//! [`NavMesh`]: struct.NavMesh.html
+
+use crate::float::Float;
+use crate::shape::cross;
+use crate::Vector2D;
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap, HashSet};
+
+/// A vertex position that can be hashed, treating 0 and -0 as the same.
+type Key = (u64, u64);
+
+/// An edge shared between two polygons, which paths can pass through.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct Portal<T> {
+    /// The index of the polygon on the other side of the edge.
+    pub neighbour: usize,
+    /// The end of the edge on the left, looking into the neighbour.
+    pub left: Vector2D<T>,
+    /// The end of the edge on the right, looking into the neighbour.
+    pub right: Vector2D<T>,
+}
+
+/// A walkable area made of convex polygons. See the [module documentation] for
+/// details.
+///
+/// [module documentation]: index.html
+#[derive(Clone, Debug, PartialEq)]
+pub struct NavMesh<T> {
+    polygons: Vec<Vec<Vector2D<T>>>,
+    portals: Vec<Vec<Portal<T>>>,
+    centroids: Vec<Vector2D<T>>,
+    /// The vertices touching an edge which isn't shared, and so is a wall.
+    wall_vertices: HashSet<Key>,
+}
+
+impl<T: Float> NavMesh<T> {
+    /// Build a mesh from convex polygons. Clockwise polygons are reversed to
+    /// wind counter-clockwise. Polygons are connected where one has an edge
+    /// with exactly the same end points as an edge of another.
+    pub fn new(polygons: Vec<Vec<Vector2D<T>>>) -> Self {
+        let polygons: Vec<Vec<Vector2D<T>>> = polygons
+            .into_iter()
+            .map(|mut polygon| {
+                let mut area = T::ZERO;
+                for (i, &a) in polygon.iter().enumerate() {
+                    area += cross(a, polygon[(i + 1) % polygon.len()]);
+                }
+                if area < T::ZERO {
+                    polygon.reverse();
+                }
+                polygon
+            })
+            .collect();
+
+        // The polygons with each edge, running from its first key to its second
+        let mut owners: HashMap<(Key, Key), Vec<usize>> = HashMap::new();
+        for (i, polygon) in polygons.iter().enumerate() {
+            for (a, b) in edges(polygon) {
+                owners.entry((key(a), key(b))).or_default().push(i);
+            }
+        }
+
+        let mut portals = vec![Vec::new(); polygons.len()];
+        let mut wall_vertices = HashSet::new();
+        for (i, polygon) in polygons.iter().enumerate() {
+            // Shared edges run in opposite directions around each polygon
+            let mut shared = Vec::new();
+            for (a, b) in edges(polygon) {
+                match owners.get(&(key(b), key(a))) {
+                    Some(others) => {
+                        shared.extend(others.iter().filter(|&&j| j > i).map(|&j| (j, a, b)))
+                    }
+                    None => {
+                        wall_vertices.insert(key(a));
+                        wall_vertices.insert(key(b));
+                    }
+                }
+            }
+            shared.sort_by_key(|&(j, _, _)| j);
+            for (j, a, b) in shared {
+                // Leaving a counter-clockwise polygon, its edge's start is on
+                // the right and its end is on the left
+                portals[i].push(Portal {
+                    neighbour: j,
+                    left: b,
+                    right: a,
+                });
+                portals[j].push(Portal {
+                    neighbour: i,
+                    left: a,
+                    right: b,
+                });
+            }
+        }
+
+        let centroids = polygons
+            .iter()
+            .map(|p| {
+                let sum = p
+                    .iter()
+                    .fold(Vector2D::new(T::ZERO, T::ZERO), |sum, &v| sum + v);
+                sum / T::from_f64(p.len() as f64)
+            })
+            .collect();
+
+        Self {
+            polygons,
+            portals,
+            centroids,
+            wall_vertices,
+        }
+    }
+
+    /// Get the polygons making up the mesh.
+    pub fn polygons(&self) -> &[Vec<Vector2D<T>>] {
+        &self.polygons
+    }
+
+    /// Get the edges connecting a polygon to its neighbours.
+    pub fn portals(&self, polygon: usize) -> &[Portal<T>] {
+        &self.portals[polygon]
+    }
+
+    /// Find the index of the polygon containing `point`, or `None` if it is
+    /// outside of the mesh. Points on a shared edge belong to the first of the
+    /// polygons.
+    pub fn locate(&self, point: Vector2D<T>) -> Option<usize> {
+        self.polygons
+            .iter()
+            .position(|polygon| edges(polygon).all(|(a, b)| cross(b - a, point - a) >= T::ZERO))
+    }
+
+    /// Find the shortest path from `start` to `goal`, including both, or `None`
+    /// if either is outside the mesh or there is no route between them.
+    pub fn find_path(&self, start: Vector2D<T>, goal: Vector2D<T>) -> Option<Vec<Vector2D<T>>> {
+        self.find_path_with_radius(start, goal, T::ZERO)
+    }
+
+    /// Find the shortest path from `start` to `goal` for an agent of the
+    /// provided radius, keeping it at least that far from the corners it turns
+    /// around, and avoiding gaps between walls narrower than its diameter.
+    pub fn find_path_with_radius(
+        &self,
+        start: Vector2D<T>,
+        goal: Vector2D<T>,
+        radius: T,
+    ) -> Option<Vec<Vector2D<T>>> {
+        let corridor = self.find_corridor(start, goal, radius)?;
+        let mut portals = vec![(start, start)];
+        for pair in corridor.windows(2) {
+            let portal = self.portals[pair[0]]
+                .iter()
+                .find(|p| p.neighbour == pair[1])
+                .expect("corridors only cross portals");
+            portals.push(self.shrink(portal, radius)?);
+        }
+        portals.push((goal, goal));
+        Some(string_pull(&portals))
+    }
+
+    /// Find the sequence of polygons leading from the polygon containing
+    /// `start` to the polygon containing `goal`, crossing only edges wide
+    /// enough for an agent of the provided radius to keep clear of the walls
+    /// at their ends.
+    pub fn find_corridor(
+        &self,
+        start: Vector2D<T>,
+        goal: Vector2D<T>,
+        radius: T,
+    ) -> Option<Vec<usize>> {
+        let from = self.locate(start)?;
+        let to = self.locate(goal)?;
+
+        // A* over the polygons, measuring distances between their centroids
+        let position = |polygon: usize| {
+            if polygon == from {
+                start
+            } else {
+                self.centroids[polygon]
+            }
+        };
+        let distance = |a: Vector2D<T>, b: Vector2D<T>| (b - a).length_squared().sqrt();
+        let mut best = vec![None; self.polygons.len()];
+        let mut came_from = vec![None; self.polygons.len()];
+        let mut open = BinaryHeap::new();
+        best[from] = Some(T::ZERO);
+        open.push(OpenPolygon {
+            estimate: distance(start, goal),
+            cost: T::ZERO,
+            polygon: from,
+        });
+
+        while let Some(OpenPolygon { cost, polygon, .. }) = open.pop() {
+            if polygon == to {
+                let mut corridor = vec![to];
+                let mut current = to;
+                while let Some(previous) = came_from[current] {
+                    corridor.push(previous);
+                    current = previous;
+                }
+                corridor.reverse();
+                return Some(corridor);
+            }
+            if best[polygon].is_some_and(|b| cost > b) {
+                continue;
+            }
+            for portal in &self.portals[polygon] {
+                if self.shrink(portal, radius).is_none() {
+                    continue;
+                }
+                let next = portal.neighbour;
+                let next_cost = cost + distance(position(polygon), position(next));
+                if best[next].is_some_and(|b| b <= next_cost) {
+                    continue;
+                }
+                best[next] = Some(next_cost);
+                came_from[next] = Some(polygon);
+                open.push(OpenPolygon {
+                    estimate: next_cost + distance(position(next), goal),
+                    cost: next_cost,
+                    polygon: next,
+                });
+            }
+        }
+        None
+    }
+
+    /// Move the ends of a portal which touch a wall towards each other by
+    /// `radius`, returning its new left and right ends, or `None` if it is too
+    /// narrow.
+    fn shrink(&self, portal: &Portal<T>, radius: T) -> Option<(Vector2D<T>, Vector2D<T>)> {
+        if radius <= T::ZERO {
+            return Some((portal.left, portal.right));
+        }
+        let inset = |end: Vector2D<T>| {
+            if self.wall_vertices.contains(&key(end)) {
+                radius
+            } else {
+                T::ZERO
+            }
+        };
+        let (left_inset, right_inset) = (inset(portal.left), inset(portal.right));
+        let across = portal.right - portal.left;
+        let width = across.length_squared().sqrt();
+        if width < left_inset + right_inset {
+            return None;
+        }
+        let direction = across / width;
+        Some((
+            portal.left + direction * left_inset,
+            portal.right - direction * right_inset,
+        ))
+    }
+}
+
+/// Get the hashable key of a vertex position.
+fn key<T: Float>(point: Vector2D<T>) -> Key {
+    (
+        (point.x.to_f64() + 0.0).to_bits(),
+        (point.y.to_f64() + 0.0).to_bits(),
+    )
+}
+
+/// Iterate over the edges of a polygon, as pairs of consecutive vertices.
+fn edges<T: Float>(
+    polygon: &[Vector2D<T>],
+) -> impl Iterator<Item = (Vector2D<T>, Vector2D<T>)> + '_ {
+    polygon
+        .iter()
+        .enumerate()
+        .map(move |(i, &a)| (a, polygon[(i + 1) % polygon.len()]))
+}
+
+/// Pull a path tight through a sequence of portals, given as their left and
+/// right ends, using the simple stupid funnel algorithm. The first and last
+/// portals should be the start and goal points.
+fn string_pull<T: Float>(portals: &[(Vector2D<T>, Vector2D<T>)]) -> Vec<Vector2D<T>> {
+    let (start, _) = portals[0];
+    let mut path = vec![start];
+    let mut apex = start;
+    let (mut left, mut right) = (start, start);
+    let (mut left_index, mut right_index) = (0, 0);
+
+    let mut i = 1;
+    while i < portals.len() {
+        let (new_left, new_right) = portals[i];
+
+        // Narrow the funnel from the right, unless that crosses the left side,
+        // in which case the path turns around the left corner
+        if cross(right - apex, new_right - apex) >= T::ZERO {
+            if apex == right || cross(left - apex, new_right - apex) < T::ZERO {
+                right = new_right;
+                right_index = i;
+            } else {
+                apex = left;
+                let apex_index = left_index;
+                path.push(apex);
+                right = apex;
+                right_index = apex_index;
+                i = apex_index + 1;
+                continue;
+            }
+        }
+
+        // And likewise from the left
+        if cross(left - apex, new_left - apex) <= T::ZERO {
+            if apex == left || cross(right - apex, new_left - apex) > T::ZERO {
+                left = new_left;
+                left_index = i;
+            } else {
+                apex = right;
+                let apex_index = right_index;
+                path.push(apex);
+                left = apex;
+                left_index = apex_index;
+                i = apex_index + 1;
+                continue;
+            }
+        }
+        i += 1;
+    }
+
+    let (goal, _) = portals[portals.len() - 1];
+    if path.last() != Some(&goal) {
+        path.push(goal);
+    }
+    path
+}
+
+/// A polygon waiting to be explored, ordered so that the polygon with the
+/// lowest estimated total cost is popped from a `BinaryHeap` first.
+#[derive(Copy, Clone, Debug, PartialEq)]
+struct OpenPolygon<T> {
+    estimate: T,
+    cost: T,
+    polygon: usize,
+}
+
+impl<T: PartialOrd> Eq for OpenPolygon<T> {}
+
+impl<T: PartialOrd> Ord for OpenPolygon<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other
+            .estimate
+            .partial_cmp(&self.estimate)
+            .unwrap_or(Ordering::Equal)
+    }
+}
+
+impl<T: PartialOrd> PartialOrd for OpenPolygon<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
@@ -0,0 +1,328 @@
+//! Offsetting polygons and thickening polylines.
+//!
+//! An [`Offset`] grows a polygon outwards, or shrinks it inwards, by a distance,
+//! such as to draw an outline or to erode a navigation mesh by an agent's
+//! radius. It can also thicken a polyline into a polygon, for drawing thick
+//! lines. Corners are filled in with a [`Join`], which may be mitred, round
+//! or bevelled, and the ends of polylines are finished with a [`Cap`].
+//! Polygons with holes, such as a floor around obstacles, are offset with
+//! [`polygon_with_holes()`].
+//!
+//! The result is a set of [`Polygon`]s, since shrinking can split a shape into
+//! pieces or remove it entirely, and growing can close off holes. Overlaps
+//! where the outline crosses itself, such as around tight bends, are merged
+//! with the same machinery as the [`boolean`] operations, which compares every
+//! edge of the outline with every other, so this takes O(n²) time in the
+//! number of vertices.
+//!
+//! # Example
+//! ```
+//! use vector2d::offset::{Cap, Join, Offset};
+//! use vector2d::Vector2D;
+//!
+//! let square = [
+//!     Vector2D::new(0.0f32, 0.0),
+//!     Vector2D::new(4.0, 0.0),
+//!     Vector2D::new(4.0, 4.0),
+//!     Vector2D::new(0.0, 4.0),
+//! ];
+//! // Growing with mitred corners keeps them square
+//! let grown = Offset::new(1.0).with_join(Join::Miter(2.0)).polygon(&square);
+//! assert_eq!(1, grown.len());
+//! assert_eq!(36.0, grown[0].area());
+//!
+//! // Shrinking by more than half the width leaves nothing
+//! assert!(Offset::new(-2.5).polygon(&square).is_empty());
+//!
+//! // A line 2 units wide and 4 long, with square caps adding 1 at each end
+//! let line = [Vector2D::new(0.0f32, 0.0), Vector2D::new(4.0, 0.0)];
+//! let thick = Offset::new(1.0).with_cap(Cap::Square).polyline(&line);
+//! assert_eq!(12.0, thick[0].area());
+//! ```
+//!
+//! [`Offset`]: struct.Offset.html
+//! [`Join`]: enum.Join.html
+//! [`Cap`]: enum.Cap.html
+//! [`polygon_with_holes()`]: struct.Offset.html#method.polygon_with_holes
+//! [`Polygon`]: ../boolean/struct.Polygon.html
+//! [`boolean`]: ../boolean/index.html
+
+use crate::boolean::{fill_positive, from_f64, to_f64, Polygon};
+use crate::float::Float;
+use crate::shape::{cross, signed_area};
+use crate::Vector2D;
+
+/// How to fill in the corners between offset edges.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Join<T> {
+    /// Extend the edges until they meet in a point, unless it would lie
+    /// further than the given multiple of the distance from the corner, in
+    /// which case the corner is bevelled instead.
+    Miter(T),
+    /// Round the corner off with an arc.
+    Round,
+    /// Cut the corner off with a straight line.
+    Bevel,
+}
+
+/// How to finish the ends of a thickened polyline.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum Cap {
+    /// Stop flat at the end.
+    Butt,
+    /// Round the end off with a semicircle.
+    Round,
+    /// Extend the end by the distance, then stop flat.
+    Square,
+}
+
+/// Offsets polygons and polylines by a distance. See the [module
+/// documentation] for details.
+///
+/// [module documentation]: index.html
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Offset<T> {
+    distance: T,
+    join: Join<T>,
+    cap: Cap,
+    tolerance: T,
+}
+
+impl<T: Float> Offset<T> {
+    /// Create a new `Offset` by `distance`, which grows polygons when positive
+    /// and shrinks them when negative. It defaults to mitred corners, limited
+    /// to four times the distance, and butt caps. Arcs stray from a true
+    /// circle by at most 1% of the distance.
+    pub fn new(distance: T) -> Self {
+        Self {
+            distance,
+            join: Join::Miter(T::from_f64(4.0)),
+            cap: Cap::Butt,
+            tolerance: distance.abs() / T::from_f64(100.0),
+        }
+    }
+
+    /// Set how to fill in corners.
+    pub fn with_join(mut self, join: Join<T>) -> Self {
+        self.join = join;
+        self
+    }
+
+    /// Set how to finish the ends of polylines.
+    pub fn with_cap(mut self, cap: Cap) -> Self {
+        self.cap = cap;
+        self
+    }
+
+    /// Set how far the straight segments approximating round joins and caps
+    /// may stray from a true circle.
+    ///
+    /// # Panics
+    /// Panics if `tolerance` isn't positive.
+    pub fn with_tolerance(mut self, tolerance: T) -> Self {
+        assert!(tolerance > T::ZERO, "tolerance must be positive");
+        self.tolerance = tolerance;
+        self
+    }
+
+    /// Offset a polygon, whose vertices may wind either way.
+    pub fn polygon(&self, polygon: &[Vector2D<T>]) -> Vec<Polygon<T>> {
+        fill(self.ring(polygon, true).into_iter().collect())
+    }
+
+    /// Offset a polygon with holes, moving the holes' edges the opposite way
+    /// to its exterior's, so that growing the polygon shrinks its holes.
+    pub fn polygon_with_holes(&self, polygon: &Polygon<T>) -> Vec<Polygon<T>> {
+        let exterior = self.ring(&polygon.exterior, true);
+        let holes = polygon.holes.iter().map(|hole| self.ring(hole, false));
+        fill(exterior.into_iter().chain(holes.flatten()).collect())
+    }
+
+    /// Offset one ring of a polygon, wound counter-clockwise if it is the
+    /// exterior or clockwise if it is a hole, or get `None` if it has no area.
+    fn ring(&self, ring: &[Vector2D<T>], exterior: bool) -> Option<Vec<Vector2D<T>>> {
+        let mut ring = ring.to_vec();
+        ring.dedup();
+        while ring.len() > 1 && ring.first() == ring.last() {
+            ring.pop();
+        }
+        if ring.len() < 3 {
+            return None;
+        }
+        if (signed_area(&ring) < T::ZERO) == exterior {
+            ring.reverse();
+        }
+        if self.distance == T::ZERO {
+            return Some(ring);
+        }
+
+        // Going around the material counter-clockwise, the outside lies to the
+        // right, both of the exterior and of holes wound clockwise
+        let n = ring.len();
+        let mut outline = Vec::with_capacity(n * 2);
+        for i in 0..n {
+            let (prev, here, next) = (ring[(i + n - 1) % n], ring[i], ring[(i + 1) % n]);
+            self.corner(prev, here, next, self.distance, &mut outline);
+        }
+        Some(outline)
+    }
+
+    /// Thicken a polyline into a polygon, with the distance as half of its
+    /// width. A single point becomes a circle or square with round or square
+    /// caps.
+    pub fn polyline(&self, polyline: &[Vector2D<T>]) -> Vec<Polygon<T>> {
+        let mut points = polyline.to_vec();
+        points.dedup();
+        let distance = self.distance.abs();
+        if points.is_empty() || distance == T::ZERO {
+            return Vec::new();
+        }
+        if points.len() == 1 {
+            // Cap both sides of a line of no length, facing along the x axis
+            let mut outline = Vec::new();
+            let across = Vector2D::new(T::ZERO, -distance);
+            for side in [across, -across] {
+                self.cap(points[0], side, &mut outline);
+            }
+            return fill(vec![outline]);
+        }
+
+        // Run along the right side of the line, around the end, then back
+        // along the other side
+        let mut outline = Vec::with_capacity(points.len() * 4);
+        for _ in 0..2 {
+            let (first, second) = (points[0], points[1]);
+            outline.push(first + right(second - first) * distance);
+            for i in 1..points.len() - 1 {
+                self.corner(
+                    points[i - 1],
+                    points[i],
+                    points[i + 1],
+                    distance,
+                    &mut outline,
+                );
+            }
+            let (before, end) = (points[points.len() - 2], points[points.len() - 1]);
+            let side = right(end - before) * distance;
+            outline.push(end + side);
+            self.cap(end, side, &mut outline);
+            points.reverse();
+        }
+        fill(vec![outline])
+    }
+
+    /// Add the offset points around the corner at `here`, pushing out to the
+    /// right of the edges by `distance`.
+    fn corner(
+        &self,
+        prev: Vector2D<T>,
+        here: Vector2D<T>,
+        next: Vector2D<T>,
+        distance: T,
+        outline: &mut Vec<Vector2D<T>>,
+    ) {
+        let (incoming, outgoing) = (here - prev, next - here);
+        let (from, to) = (right(incoming) * distance, right(outgoing) * distance);
+        let turn = cross(incoming, outgoing);
+        if turn == T::ZERO && Vector2D::dot(incoming, outgoing) > T::ZERO {
+            // Straight on
+            outline.push(here + from);
+            return;
+        }
+        if turn * distance < T::ZERO {
+            // The offset edges overlap on this side of the corner, so join them
+            // through it, leaving the overlap to be merged
+            outline.push(here + from);
+            outline.push(here);
+            outline.push(here + to);
+            return;
+        }
+        match self.join {
+            Join::Miter(limit) => {
+                // The miter reaches 1 / cos(θ / 2) times the distance, for a
+                // turn of θ
+                let cos = Vector2D::dot(from, to) / (distance * distance);
+                let reach_squared = T::from_f64(2.0) / (T::ONE + cos);
+                if cos > -T::ONE && reach_squared <= limit * limit {
+                    outline.push(here + (from + to) / (T::ONE + cos));
+                } else {
+                    outline.push(here + from);
+                    outline.push(here + to);
+                }
+            }
+            Join::Round => {
+                let angle = cross(from, to).atan2(Vector2D::dot(from, to));
+                self.arc(here, from, angle, outline);
+            }
+            Join::Bevel => {
+                outline.push(here + from);
+                outline.push(here + to);
+            }
+        }
+    }
+
+    /// Add the points around the cap at `end`, turning counter-clockwise from
+    /// the `side` offset to the opposite side.
+    fn cap(&self, end: Vector2D<T>, side: Vector2D<T>, outline: &mut Vec<Vector2D<T>>) {
+        match self.cap {
+            Cap::Butt => {}
+            Cap::Round => self.arc(end, side, T::PI, outline),
+            Cap::Square => {
+                let forward = Vector2D::new(-side.y, side.x);
+                outline.push(end + side + forward);
+                outline.push(end - side + forward);
+            }
+        }
+        outline.push(end - side);
+    }
+
+    /// Add the points along an arc around `centre`, starting at the offset
+    /// `from` and turning counter-clockwise through `angle`.
+    fn arc(
+        &self,
+        centre: Vector2D<T>,
+        from: Vector2D<T>,
+        angle: T,
+        outline: &mut Vec<Vector2D<T>>,
+    ) {
+        // Each segment of a step of θ strays r(1 - cos(θ / 2)) from the circle
+        let radius = from.length_squared().sqrt();
+        let mut cos = T::ONE - self.tolerance / radius;
+        if cos < T::ZERO {
+            cos = T::ZERO;
+        }
+        let mut half_step = (T::ONE - cos * cos).sqrt().atan2(cos);
+        // A tolerance lost to rounding against the radius would otherwise give
+        // a single step, so limit the segments to 1024 per full turn
+        let min_half_step = T::TAU / T::from_f64(2048.0);
+        if half_step < min_half_step {
+            half_step = min_half_step;
+        }
+        let steps = (angle.abs() / (half_step * T::from_f64(2.0)))
+            .to_f64()
+            .ceil()
+            .max(1.0) as usize;
+        for i in 0..=steps {
+            let theta = angle * T::from_f64(i as f64 / steps as f64);
+            let (sin, cos) = (theta.sin(), theta.cos());
+            outline.push(
+                centre + Vector2D::new(from.x * cos - from.y * sin, from.x * sin + from.y * cos),
+            );
+        }
+    }
+}
+
+/// Get the unit vector pointing to the right of a direction.
+fn right<T: Float>(direction: Vector2D<T>) -> Vector2D<T> {
+    let length = direction.length_squared().sqrt();
+    Vector2D::new(direction.y / length, -direction.x / length)
+}
+
+/// Merge the overlaps of a set of outlines into polygons.
+fn fill<T: Float>(outlines: Vec<Vec<Vector2D<T>>>) -> Vec<Polygon<T>> {
+    let rings: Vec<Vec<Vector2D<f64>>> = outlines
+        .into_iter()
+        .map(|outline| outline.into_iter().map(to_f64).collect())
+        .collect();
+    fill_positive(&rings).into_iter().map(from_f64).collect()
+}
@@ -0,0 +1,421 @@
+//! Pathfinding over grids of `Vector2D<i32>` cells.
+//!
+//! A [`GridPathfinder`] finds the cheapest path between two cells with A*,
+//! given a callback returning the cost of entering each cell, or `None` if the
+//! cell can't be entered. Cells can connect to their four orthogonal
+//! neighbours, or to all eight neighbours with a choice of rules for cutting
+//! past the corners of blocked cells. Using [`Heuristic::Zero`] turns the
+//! search into Dijkstra's algorithm, and Jump Point Search can speed up
+//! searches over grids where every open cell costs the same.
+//!
+//! The search explores every reachable cell before giving up on an
+//! unreachable goal, so the cost callback should return `None` outside of a
+//! bounded area.
+//!
+//! # Example
+//! ```
+//! use vector2d::pathfinding::{GridPathfinder, Neighbourhood};
+//! use vector2d::Vector2D;
+//!
+//! let map = [
+//!     "....#...",
+//!     "..#.#.#.",
+//!     "..#...#.",
+//! ];
+//! // Negative coordinates wrap around to huge indices, which are out of bounds
+//! let cost = |cell: Vector2D<i32>| {
+//!     let row = map.get(cell.y as usize)?.as_bytes();
+//!     match row.get(cell.x as usize)? {
+//!         b'.' => Some(1.0),
+//!         _ => None,
+//!     }
+//! };
+//!
+//! let pathfinder = GridPathfinder::new(cost).with_neighbourhood(Neighbourhood::Four);
+//! let path = pathfinder.find_path(Vector2D::new(0, 0), Vector2D::new(7, 0)).unwrap();
+//! assert_eq!(12, path.len());
+//! assert_eq!(Some(11.0), pathfinder.path_cost(&path));
+//! ```
+//!
+//! [`GridPathfinder`]: struct.GridPathfinder.html
+//! [`Heuristic::Zero`]: enum.Heuristic.html#variant.Zero
+
+use crate::Vector2D;
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap};
+
+/// The cost of a diagonal move, relative to an orthogonal one.
+pub(crate) const DIAGONAL: f64 = std::f64::consts::SQRT_2;
+
+/// Which neighbouring cells each cell connects to.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum Neighbourhood {
+    /// The four orthogonal neighbours.
+    Four,
+    /// The four orthogonal and four diagonal neighbours, with diagonal moves
+    /// allowed past blocked cells according to the provided rule.
+    Eight(CornerCutting),
+}
+
+/// When a diagonal move may pass the corners of the two orthogonal cells it
+/// squeezes between.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum CornerCutting {
+    /// Diagonal moves are always allowed, even between two blocked cells.
+    Always,
+    /// Diagonal moves are allowed if at least one of the two cells is open.
+    IfOneOpen,
+    /// Diagonal moves are only allowed if both cells are open, so paths never
+    /// touch the corner of a blocked cell.
+    Never,
+}
+
+/// An estimate of the cost between two cells, which guides A* towards the
+/// goal.
+///
+/// Heuristics assume that every open cell costs at least 1 to enter. Cheaper
+/// cells can lead to paths that are not the cheapest.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum Heuristic {
+    /// The sum of the horizontal and vertical distances, which is exact for
+    /// four-way movement.
+    Manhattan,
+    /// The distance with diagonal moves costing √2, which is exact for
+    /// eight-way movement.
+    Octile,
+    /// The straight-line distance.
+    Euclidean,
+    /// No estimate at all, which turns A* into Dijkstra's algorithm.
+    Zero,
+}
+
+impl Heuristic {
+    /// Estimate the cost of travelling from `a` to `b`.
+    pub fn distance(self, a: Vector2D<i32>, b: Vector2D<i32>) -> f64 {
+        let dx = (a.x - b.x).abs() as f64;
+        let dy = (a.y - b.y).abs() as f64;
+        match self {
+            Heuristic::Manhattan => dx + dy,
+            Heuristic::Octile => {
+                let (short, long) = if dx < dy { (dx, dy) } else { (dy, dx) };
+                long - short + short * DIAGONAL
+            }
+            Heuristic::Euclidean => (dx * dx + dy * dy).sqrt(),
+            Heuristic::Zero => 0.0,
+        }
+    }
+}
+
+/// Finds paths over a grid. See the [module documentation] for details.
+///
+/// [module documentation]: index.html
+#[derive(Copy, Clone, Debug)]
+pub struct GridPathfinder<F> {
+    cost: F,
+    pub neighbourhood: Neighbourhood,
+    pub heuristic: Heuristic,
+    /// Whether to use Jump Point Search. See
+    /// [`with_jump_point_search()`](#method.with_jump_point_search).
+    pub jump_point_search: bool,
+}
+
+impl<F: Fn(Vector2D<i32>) -> Option<f64>> GridPathfinder<F> {
+    /// Create a pathfinder over the grid described by `cost`, which returns the
+    /// cost of entering a cell, or `None` if it can't be entered. Diagonal
+    /// moves cost √2 times as much.
+    ///
+    /// By default, cells connect to all eight neighbours without cutting
+    /// corners, and the octile heuristic is used.
+    pub fn new(cost: F) -> Self {
+        Self {
+            cost,
+            neighbourhood: Neighbourhood::Eight(CornerCutting::Never),
+            heuristic: Heuristic::Octile,
+            jump_point_search: false,
+        }
+    }
+
+    /// Connect cells to the provided neighbours.
+    pub fn with_neighbourhood(mut self, neighbourhood: Neighbourhood) -> Self {
+        self.neighbourhood = neighbourhood;
+        self
+    }
+
+    /// Guide the search with the provided heuristic.
+    pub fn with_heuristic(mut self, heuristic: Heuristic) -> Self {
+        self.heuristic = heuristic;
+        self
+    }
+
+    /// Use Jump Point Search, which skips over runs of open cells rather than
+    /// exploring each of them. It treats every open cell as costing 1,
+    /// regardless of the cost callback, and is only used with an eight-way
+    /// neighbourhood that never cuts corners. Otherwise, plain A* is used.
+    pub fn with_jump_point_search(mut self, enabled: bool) -> Self {
+        self.jump_point_search = enabled;
+        self
+    }
+
+    /// Find the cheapest path from `start` to `goal`, including both, or `None`
+    /// if the goal can't be reached. The start cell itself doesn't need to be
+    /// open.
+    pub fn find_path(
+        &self,
+        start: Vector2D<i32>,
+        goal: Vector2D<i32>,
+    ) -> Option<Vec<Vector2D<i32>>> {
+        if start == goal {
+            return Some(vec![start]);
+        }
+        if !self.is_open(goal) {
+            return None;
+        }
+        let jump = self.jump_point_search
+            && self.neighbourhood == Neighbourhood::Eight(CornerCutting::Never);
+
+        let mut open = BinaryHeap::new();
+        let mut best: HashMap<Vector2D<i32>, f64> = HashMap::new();
+        let mut came_from: HashMap<Vector2D<i32>, Vector2D<i32>> = HashMap::new();
+        best.insert(start, 0.0);
+        open.push(OpenCell {
+            estimate: self.heuristic.distance(start, goal),
+            cost: 0.0,
+            cell: start,
+        });
+
+        while let Some(OpenCell { cost, cell, .. }) = open.pop() {
+            if cell == goal {
+                let mut path = vec![goal];
+                let mut current = goal;
+                while let Some(&previous) = came_from.get(&current) {
+                    if jump {
+                        fill_line(&mut path, current, previous);
+                    } else {
+                        path.push(previous);
+                    }
+                    current = previous;
+                }
+                path.reverse();
+                return Some(path);
+            }
+            if best.get(&cell).is_some_and(|&b| cost > b) {
+                // A cheaper route to this cell has already been explored
+                continue;
+            }
+
+            let successors = if jump {
+                self.jump_successors(cell, came_from.get(&cell).copied(), goal)
+            } else {
+                self.neighbours(cell)
+            };
+            for (next, step) in successors {
+                let next_cost = cost + step;
+                if best.get(&next).is_some_and(|&b| b <= next_cost) {
+                    continue;
+                }
+                best.insert(next, next_cost);
+                came_from.insert(next, cell);
+                open.push(OpenCell {
+                    estimate: next_cost + self.heuristic.distance(next, goal),
+                    cost: next_cost,
+                    cell: next,
+                });
+            }
+        }
+        None
+    }
+
+    /// Get the cells reachable from `cell` in a single move, with the cost of
+    /// moving to each.
+    pub fn neighbours(&self, cell: Vector2D<i32>) -> Vec<(Vector2D<i32>, f64)> {
+        let mut result = Vec::with_capacity(8);
+        for &(dx, dy) in &[(1, 0), (0, 1), (-1, 0), (0, -1)] {
+            let next = Vector2D::new(cell.x + dx, cell.y + dy);
+            if let Some(cost) = (self.cost)(next) {
+                result.push((next, cost));
+            }
+        }
+        let rule = match self.neighbourhood {
+            Neighbourhood::Four => return result,
+            Neighbourhood::Eight(rule) => rule,
+        };
+        for &(dx, dy) in &[(1, 1), (-1, 1), (-1, -1), (1, -1)] {
+            let next = Vector2D::new(cell.x + dx, cell.y + dy);
+            let cost = match (self.cost)(next) {
+                Some(cost) => cost,
+                None => continue,
+            };
+            let open_x = self.is_open(Vector2D::new(cell.x + dx, cell.y));
+            let open_y = self.is_open(Vector2D::new(cell.x, cell.y + dy));
+            let allowed = match rule {
+                CornerCutting::Always => true,
+                CornerCutting::IfOneOpen => open_x || open_y,
+                CornerCutting::Never => open_x && open_y,
+            };
+            if allowed {
+                result.push((next, cost * DIAGONAL));
+            }
+        }
+        result
+    }
+
+    /// Get the total cost of following a path, or `None` if any step isn't a
+    /// valid move. The cost of the first cell isn't included.
+    pub fn path_cost(&self, path: &[Vector2D<i32>]) -> Option<f64> {
+        path.windows(2).try_fold(0.0, |total, pair| {
+            let (_, cost) = self
+                .neighbours(pair[0])
+                .into_iter()
+                .find(|&(cell, _)| cell == pair[1])?;
+            Some(total + cost)
+        })
+    }
+
+    fn is_open(&self, cell: Vector2D<i32>) -> bool {
+        (self.cost)(cell).is_some()
+    }
+
+    /// Get the jump points reachable from `cell`, pruning the directions that
+    /// a path arriving from `parent` would never need to explore.
+    fn jump_successors(
+        &self,
+        cell: Vector2D<i32>,
+        parent: Option<Vector2D<i32>>,
+        goal: Vector2D<i32>,
+    ) -> Vec<(Vector2D<i32>, f64)> {
+        let open = |dx: i32, dy: i32| self.is_open(Vector2D::new(cell.x + dx, cell.y + dy));
+        let mut directions = Vec::with_capacity(8);
+        match parent {
+            None => {
+                directions.extend(
+                    self.neighbours(cell)
+                        .into_iter()
+                        .map(|(next, _)| (next.x - cell.x, next.y - cell.y)),
+                );
+            }
+            Some(parent) => {
+                let dx = (cell.x - parent.x).signum();
+                let dy = (cell.y - parent.y).signum();
+                if dx != 0 && dy != 0 {
+                    directions.extend_from_slice(&[(dx, 0), (0, dy)]);
+                    if open(dx, 0) && open(0, dy) {
+                        directions.push((dx, dy));
+                    }
+                } else {
+                    // Moving straight, side cells may have been reachable only
+                    // through this cell, because the path can't cut the corner
+                    // of the blocked cell behind them
+                    let (sx, sy) = (dy, dx);
+                    let ahead = open(dx, dy);
+                    if ahead {
+                        directions.push((dx, dy));
+                    }
+                    for &side in &[1, -1] {
+                        if open(sx * side, sy * side) {
+                            directions.push((sx * side, sy * side));
+                            if ahead {
+                                directions.push((dx + sx * side, dy + sy * side));
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        directions
+            .into_iter()
+            .filter_map(|(dx, dy)| {
+                let point = self.jump(cell, dx, dy, goal)?;
+                Some((point, Heuristic::Octile.distance(cell, point)))
+            })
+            .collect()
+    }
+
+    /// Move from `cell` in a direction until reaching a jump point: the goal,
+    /// or a cell where the path might need to turn.
+    fn jump(
+        &self,
+        cell: Vector2D<i32>,
+        dx: i32,
+        dy: i32,
+        goal: Vector2D<i32>,
+    ) -> Option<Vector2D<i32>> {
+        let mut current = cell;
+        loop {
+            let (x, y) = (current.x + dx, current.y + dy);
+            let next = Vector2D::new(x, y);
+            let open = |cx: i32, cy: i32| self.is_open(Vector2D::new(cx, cy));
+            if !open(x, y) {
+                return None;
+            }
+            if next == goal {
+                return Some(next);
+            }
+            if dx != 0 && dy != 0 {
+                if self.jump(next, dx, 0, goal).is_some() || self.jump(next, 0, dy, goal).is_some()
+                {
+                    return Some(next);
+                }
+                if !(open(x + dx, y) && open(x, y + dy)) {
+                    return None;
+                }
+            } else if dx != 0 {
+                if (open(x, y - 1) && !open(x - dx, y - 1))
+                    || (open(x, y + 1) && !open(x - dx, y + 1))
+                {
+                    return Some(next);
+                }
+            } else if (open(x - 1, y) && !open(x - 1, y - dy))
+                || (open(x + 1, y) && !open(x + 1, y - dy))
+            {
+                return Some(next);
+            }
+            current = next;
+        }
+    }
+}
+
+/// Add the cells on the straight or diagonal line from `from`, exclusive, to
+/// `to`, inclusive, to `path`.
+fn fill_line(path: &mut Vec<Vector2D<i32>>, from: Vector2D<i32>, to: Vector2D<i32>) {
+    let dx = (to.x - from.x).signum();
+    let dy = (to.y - from.y).signum();
+    let mut current = from;
+    while current != to {
+        current = Vector2D::new(current.x + dx, current.y + dy);
+        path.push(current);
+    }
+}
+
+/// A cell waiting to be explored, ordered so that the cell with the lowest
+/// estimated total cost is popped from a `BinaryHeap` first.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub(crate) struct OpenCell {
+    pub(crate) estimate: f64,
+    pub(crate) cost: f64,
+    pub(crate) cell: Vector2D<i32>,
+}
+
+impl Eq for OpenCell {}
+
+impl Ord for OpenCell {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // Break ties in favour of cells further from the start, which are
+        // likely closer to the goal
+        other
+            .estimate
+            .partial_cmp(&self.estimate)
+            .unwrap_or(Ordering::Equal)
+            .then_with(|| {
+                self.cost
+                    .partial_cmp(&other.cost)
+                    .unwrap_or(Ordering::Equal)
+            })
+    }
+}
+
+impl PartialOrd for OpenCell {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
@@ -0,0 +1,590 @@
+//! A lightweight rigid body physics world.
+//!
+//! A [`World`] holds [`RigidBody`]s, each with a [`Shape`], mass, moment of
+//! inertia, and linear and angular velocity. Every fixed time step, gravity is
+//! applied, overlapping bodies are found with the [`collision`] module, and
+//! their velocities are corrected with impulses that account for restitution
+//! (bounciness) and friction. Bodies that have come to rest fall asleep, and
+//! are left alone until something hits them.
+//!
+//! The solver is tuned for bodies roughly between 0.1 and 10 units across,
+//! such as metres, and does not handle fast-moving bodies passing through each
+//! other in a single step.
+//!
+//! # Example
+//! ```
+//! use vector2d::physics::{RigidBody, World};
+//! use vector2d::shape::Shape;
+//! use vector2d::Vector2D;
+//!
+//! let mut world = World::new(Vector2D::new(0.0f64, -9.81), 1.0 / 60.0);
+//! world.add_body(RigidBody::fixed(Shape::rectangle(Vector2D::new(10.0, 0.5))));
+//! let ball = world.add_body(
+//!     RigidBody::new(Shape::circle(0.5), 1.0).with_position(Vector2D::new(0.0, 3.0)),
+//! );
+//!
+//! // Let the ball drop onto the ground and settle
+//! world.update(5.0);
+//! let body = world.body(ball);
+//! assert!((body.position.y - 1.0).abs() < 0.02);
+//! assert!(body.is_sleeping());
+//! ```
+//!
+//! [`World`]: struct.World.html
+//! [`RigidBody`]: struct.RigidBody.html
+//! [`Shape`]: ../shape/enum.Shape.html
+//! [`collision`]: ../collision/index.html
+
+use crate::angle::Angle;
+use crate::collision::collide;
+use crate::float::Float;
+use crate::shape::{cross, Pose, Shape};
+use crate::Vector2D;
+
+/// How far bodies may overlap before their positions are corrected, which
+/// keeps resting contacts from jittering.
+const LINEAR_SLOP: f64 = 0.005;
+
+/// The fraction of the remaining overlap corrected each step.
+const CORRECTION_FRACTION: f64 = 0.4;
+
+/// A rigid body, which moves and rotates without deforming.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RigidBody<T> {
+    pub shape: Shape<T>,
+    pub position: Vector2D<T>,
+    pub rotation: Angle<T>,
+    pub velocity: Vector2D<T>,
+    /// The angular velocity, in radians per second counter-clockwise.
+    pub angular_velocity: T,
+    /// How much of the body's speed is kept when it bounces, from 0 to 1.
+    pub restitution: T,
+    /// The friction coefficient of the body's surface.
+    pub friction: T,
+    inv_mass: T,
+    inv_inertia: T,
+    sleep_time: T,
+    sleeping: bool,
+}
+
+impl<T: Float> RigidBody<T> {
+    /// Create a dynamic body of the provided mass at the origin, at rest. Its
+    /// moment of inertia is calculated from its shape, assuming a uniform
+    /// density.
+    ///
+    /// # Panics
+    /// Panics if `mass` is not positive.
+    pub fn new(shape: Shape<T>, mass: T) -> Self {
+        assert!(mass > T::ZERO, "a dynamic body's mass must be positive");
+        let inertia = shape.moment_of_inertia(mass);
+        let inv_inertia = if inertia > T::ZERO {
+            T::ONE / inertia
+        } else {
+            T::ZERO
+        };
+        Self {
+            inv_mass: T::ONE / mass,
+            inv_inertia,
+            ..Self::fixed(shape)
+        }
+    }
+
+    /// Create a static body at the origin, which is never moved by collisions
+    /// or gravity.
+    pub fn fixed(shape: Shape<T>) -> Self {
+        Self {
+            shape,
+            position: Vector2D::new(T::ZERO, T::ZERO),
+            rotation: Angle::zero(),
+            velocity: Vector2D::new(T::ZERO, T::ZERO),
+            angular_velocity: T::ZERO,
+            restitution: T::ZERO,
+            friction: T::from_f64(0.5),
+            inv_mass: T::ZERO,
+            inv_inertia: T::ZERO,
+            sleep_time: T::ZERO,
+            sleeping: false,
+        }
+    }
+
+    /// Move the body to the provided position.
+    pub fn with_position(mut self, position: Vector2D<T>) -> Self {
+        self.position = position;
+        self
+    }
+
+    /// Rotate the body to the provided angle.
+    pub fn with_rotation(mut self, rotation: Angle<T>) -> Self {
+        self.rotation = rotation;
+        self
+    }
+
+    /// Set the body moving with the provided velocity.
+    pub fn with_velocity(mut self, velocity: Vector2D<T>) -> Self {
+        self.velocity = velocity;
+        self
+    }
+
+    /// Set the body spinning with the provided angular velocity, in radians
+    /// per second.
+    pub fn with_angular_velocity(mut self, angular_velocity: T) -> Self {
+        self.angular_velocity = angular_velocity;
+        self
+    }
+
+    /// Use the provided restitution, from 0 (no bounce) to 1 (perfectly
+    /// elastic). The default is 0.
+    pub fn with_restitution(mut self, restitution: T) -> Self {
+        self.restitution = restitution;
+        self
+    }
+
+    /// Use the provided friction coefficient. The default is 0.5.
+    pub fn with_friction(mut self, friction: T) -> Self {
+        self.friction = friction;
+        self
+    }
+
+    /// Get the mass of the body, or zero for static bodies.
+    pub fn mass(&self) -> T {
+        reciprocal(self.inv_mass)
+    }
+
+    /// Get the moment of inertia of the body, or zero for static bodies.
+    pub fn inertia(&self) -> T {
+        reciprocal(self.inv_inertia)
+    }
+
+    /// Check whether the body is static.
+    pub fn is_static(&self) -> bool {
+        self.inv_mass == T::ZERO
+    }
+
+    /// Check whether the body has come to rest and stopped being simulated.
+    pub fn is_sleeping(&self) -> bool {
+        self.sleeping
+    }
+
+    /// Wake the body up, so that it is simulated again. This should be called
+    /// after moving a body or changing its velocity directly.
+    pub fn wake(&mut self) {
+        self.sleeping = false;
+        self.sleep_time = T::ZERO;
+    }
+
+    /// Get the pose placing the body's shape in the world.
+    pub fn pose(&self) -> Pose<T> {
+        Pose::new(self.position, self.rotation)
+    }
+
+    /// Apply an impulse at the provided point in world coordinates, instantly
+    /// changing the body's velocity and angular velocity, and waking it up.
+    pub fn apply_impulse(&mut self, impulse: Vector2D<T>, point: Vector2D<T>) {
+        if self.is_static() {
+            return;
+        }
+        self.wake();
+        self.velocity += impulse * self.inv_mass;
+        self.angular_velocity += cross(point - self.position, impulse) * self.inv_inertia;
+    }
+
+    /// Check whether the body is moved by the simulation.
+    fn is_active(&self) -> bool {
+        !self.is_static() && !self.sleeping
+    }
+
+    /// Get the inverse mass and inverse inertia the solver should use, treating
+    /// sleeping bodies as static.
+    fn effective_inverse_mass(&self) -> (T, T) {
+        if self.sleeping {
+            (T::ZERO, T::ZERO)
+        } else {
+            (self.inv_mass, self.inv_inertia)
+        }
+    }
+}
+
+/// Identifies a body within a [`World`].
+///
+/// [`World`]: struct.World.html
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct BodyHandle(usize);
+
+/// A collection of rigid bodies, simulated with a fixed time step. See the
+/// [module documentation] for an example.
+///
+/// [module documentation]: index.html
+#[derive(Clone, Debug, PartialEq)]
+pub struct World<T> {
+    pub gravity: Vector2D<T>,
+    /// The length of each simulation step, in seconds.
+    pub timestep: T,
+    /// The number of times contacts are solved each step. More iterations make
+    /// stacks of bodies stiffer.
+    pub iterations: usize,
+    /// The speed below which bodies are considered to be at rest, applied to
+    /// both linear speed and angular speed in radians per second.
+    pub sleep_velocity: T,
+    /// How long a body must be at rest before it falls asleep, in seconds.
+    pub sleep_time: T,
+    bodies: Vec<RigidBody<T>>,
+    accumulator: T,
+}
+
+impl<T: Float> World<T> {
+    /// Create an empty world with the provided gravity and time step.
+    ///
+    /// # Panics
+    /// Panics if `timestep` isn't positive.
+    pub fn new(gravity: Vector2D<T>, timestep: T) -> Self {
+        assert!(timestep > T::ZERO, "timestep must be positive");
+        Self {
+            gravity,
+            timestep,
+            iterations: 10,
+            sleep_velocity: T::from_f64(0.05),
+            sleep_time: T::from_f64(0.5),
+            bodies: Vec::new(),
+            accumulator: T::ZERO,
+        }
+    }
+
+    /// Solve contacts the provided number of times each step.
+    pub fn with_iterations(mut self, iterations: usize) -> Self {
+        self.iterations = iterations;
+        self
+    }
+
+    /// Add a body to the world, returning a handle to it.
+    pub fn add_body(&mut self, body: RigidBody<T>) -> BodyHandle {
+        self.bodies.push(body);
+        BodyHandle(self.bodies.len() - 1)
+    }
+
+    /// Get the body with the provided handle.
+    pub fn body(&self, handle: BodyHandle) -> &RigidBody<T> {
+        &self.bodies[handle.0]
+    }
+
+    /// Get the body with the provided handle, mutably. Sleeping bodies should
+    /// be woken with [`RigidBody::wake()`] after being changed.
+    ///
+    /// [`RigidBody::wake()`]: struct.RigidBody.html#method.wake
+    pub fn body_mut(&mut self, handle: BodyHandle) -> &mut RigidBody<T> {
+        &mut self.bodies[handle.0]
+    }
+
+    /// Get every body in the world, in the order they were added.
+    pub fn bodies(&self) -> &[RigidBody<T>] {
+        &self.bodies
+    }
+
+    /// Advance the world by `dt`, running as many fixed steps as fit into the
+    /// time passed so far. Time left over is carried into the next call. Returns
+    /// the number of steps run.
+    ///
+    /// # Panics
+    /// Panics if [`timestep`] has been set to a value that isn't positive.
+    ///
+    /// [`timestep`]: #structfield.timestep
+    pub fn update(&mut self, dt: T) -> usize {
+        assert!(self.timestep > T::ZERO, "timestep must be positive");
+        self.accumulator += dt;
+        let mut steps = 0;
+        while self.accumulator >= self.timestep {
+            self.step();
+            self.accumulator -= self.timestep;
+            steps += 1;
+        }
+        steps
+    }
+
+    /// Get how far the time carried over by [`update()`] is through the next
+    /// step, from 0 to 1. This can be used to interpolate between the previous
+    /// and current positions of bodies when rendering.
+    ///
+    /// [`update()`]: #method.update
+    pub fn interpolation_alpha(&self) -> T {
+        self.accumulator / self.timestep
+    }
+
+    /// Advance the world by a single time step.
+    ///
+    /// # Panics
+    /// Panics if [`timestep`] has been set to a value that isn't positive.
+    ///
+    /// [`timestep`]: #structfield.timestep
+    pub fn step(&mut self) {
+        assert!(self.timestep > T::ZERO, "timestep must be positive");
+        let dt = self.timestep;
+        let mut contacts = self.find_contacts();
+
+        for body in self.bodies.iter_mut().filter(|b| b.is_active()) {
+            body.velocity += self.gravity * dt;
+        }
+
+        let restitution_threshold = self.gravity.length_squared().sqrt() * dt * T::from_f64(2.0);
+        for contact in &mut contacts {
+            contact.prepare(&self.bodies, restitution_threshold);
+        }
+        for _ in 0..self.iterations {
+            for contact in &mut contacts {
+                contact.solve(&mut self.bodies);
+            }
+        }
+
+        for body in self.bodies.iter_mut().filter(|b| b.is_active()) {
+            body.position += body.velocity * dt;
+            body.rotation += Angle::radians(body.angular_velocity * dt);
+        }
+
+        for contact in &contacts {
+            contact.correct_positions(&mut self.bodies);
+        }
+
+        self.update_sleep(dt);
+    }
+
+    /// Find every pair of overlapping bodies where at least one is moving,
+    /// waking sleeping bodies that are hit.
+    fn find_contacts(&mut self) -> Vec<ContactConstraint<T>> {
+        let mut contacts = Vec::new();
+        let wake_speed_squared = self.sleep_velocity * self.sleep_velocity;
+        for i in 0..self.bodies.len() {
+            for j in i + 1..self.bodies.len() {
+                let (a, b) = (&self.bodies[i], &self.bodies[j]);
+                if !a.is_active() && !b.is_active() {
+                    continue;
+                }
+                let (pose_a, pose_b) = (a.pose(), b.pose());
+                if !a
+                    .shape
+                    .bounding_box(&pose_a)
+                    .intersects(&b.shape.bounding_box(&pose_b))
+                {
+                    continue;
+                }
+                let contact = match collide(&a.shape, &pose_a, &b.shape, &pose_b) {
+                    Some(contact) => contact,
+                    None => continue,
+                };
+
+                // A sleeping body is woken by a moving body hitting it, but not
+                // by one resting on it
+                for (sleeper, other) in [(i, j), (j, i)] {
+                    if self.bodies[sleeper].sleeping
+                        && self.bodies[other].velocity.length_squared() > wake_speed_squared
+                    {
+                        self.bodies[sleeper].wake();
+                    }
+                }
+
+                let (a, b) = (&self.bodies[i], &self.bodies[j]);
+                contacts.push(ContactConstraint {
+                    a: i,
+                    b: j,
+                    normal: contact.normal,
+                    friction: (a.friction * b.friction).sqrt(),
+                    restitution: if a.restitution > b.restitution {
+                        a.restitution
+                    } else {
+                        b.restitution
+                    },
+                    points: contact
+                        .points
+                        .iter()
+                        .map(|p| PointConstraint {
+                            r_a: p.position - a.position,
+                            r_b: p.position - b.position,
+                            depth: p.depth,
+                            normal_mass: T::ZERO,
+                            tangent_mass: T::ZERO,
+                            bias: T::ZERO,
+                            normal_impulse: T::ZERO,
+                            tangent_impulse: T::ZERO,
+                        })
+                        .collect(),
+                });
+            }
+        }
+        contacts
+    }
+
+    /// Put bodies that have been at rest for long enough to sleep.
+    fn update_sleep(&mut self, dt: T) {
+        let speed_squared = self.sleep_velocity * self.sleep_velocity;
+        for body in self.bodies.iter_mut().filter(|b| b.is_active()) {
+            let resting = body.velocity.length_squared() <= speed_squared
+                && body.angular_velocity * body.angular_velocity <= speed_squared;
+            if resting {
+                body.sleep_time += dt;
+                if body.sleep_time >= self.sleep_time {
+                    body.sleeping = true;
+                    body.velocity = Vector2D::new(T::ZERO, T::ZERO);
+                    body.angular_velocity = T::ZERO;
+                }
+            } else {
+                body.sleep_time = T::ZERO;
+            }
+        }
+    }
+}
+
+/// A contact between two bodies, with the state the solver needs to resolve
+/// it.
+#[derive(Clone, Debug)]
+struct ContactConstraint<T> {
+    a: usize,
+    b: usize,
+    normal: Vector2D<T>,
+    friction: T,
+    restitution: T,
+    points: Vec<PointConstraint<T>>,
+}
+
+#[derive(Clone, Debug)]
+struct PointConstraint<T> {
+    /// The offset of the point from the centre of each body.
+    r_a: Vector2D<T>,
+    r_b: Vector2D<T>,
+    depth: T,
+    normal_mass: T,
+    tangent_mass: T,
+    /// The separating velocity the contact should end up with.
+    bias: T,
+    /// The impulses applied so far this step.
+    normal_impulse: T,
+    tangent_impulse: T,
+}
+
+impl<T: Float> ContactConstraint<T> {
+    fn tangent(&self) -> Vector2D<T> {
+        Vector2D::new(self.normal.y, -self.normal.x)
+    }
+
+    /// Calculate the effective masses at each point, and the bounce velocity
+    /// from the velocity the bodies approach each other with.
+    fn prepare(&mut self, bodies: &[RigidBody<T>], restitution_threshold: T) {
+        let (a, b) = (&bodies[self.a], &bodies[self.b]);
+        let (ima, iia) = a.effective_inverse_mass();
+        let (imb, iib) = b.effective_inverse_mass();
+        let normal = self.normal;
+        let tangent = self.tangent();
+        for point in &mut self.points {
+            let (r_a, r_b) = (point.r_a, point.r_b);
+            let effective_mass = |axis: Vector2D<T>| {
+                let rn_a = cross(r_a, axis);
+                let rn_b = cross(r_b, axis);
+                reciprocal(ima + imb + iia * rn_a * rn_a + iib * rn_b * rn_b)
+            };
+            point.normal_mass = effective_mass(normal);
+            point.tangent_mass = effective_mass(tangent);
+
+            let approach = Vector2D::dot(relative_velocity(a, b, point), normal);
+            point.bias = if approach < -restitution_threshold {
+                -self.restitution * approach
+            } else {
+                T::ZERO
+            };
+        }
+    }
+
+    /// Apply impulses to stop the bodies moving into each other, and to resist
+    /// them sliding against each other.
+    fn solve(&mut self, bodies: &mut [RigidBody<T>]) {
+        let normal = self.normal;
+        let tangent = self.tangent();
+        for i in 0..self.points.len() {
+            // Accumulate the impulses, so that later iterations can take back
+            // some of what earlier ones applied without ever pulling the
+            // bodies together
+            let point = &self.points[i];
+            let approach = Vector2D::dot(
+                relative_velocity(&bodies[self.a], &bodies[self.b], point),
+                normal,
+            );
+            let previous = point.normal_impulse;
+            let total = previous + point.normal_mass * (point.bias - approach);
+            let total = if total > T::ZERO { total } else { T::ZERO };
+            self.points[i].normal_impulse = total;
+            self.apply(bodies, i, normal * (total - previous));
+
+            let point = &self.points[i];
+            let slide = Vector2D::dot(
+                relative_velocity(&bodies[self.a], &bodies[self.b], point),
+                tangent,
+            );
+            let limit = self.friction * point.normal_impulse;
+            let previous = point.tangent_impulse;
+            let total = previous - point.tangent_mass * slide;
+            let total = if total > limit {
+                limit
+            } else if total < -limit {
+                -limit
+            } else {
+                total
+            };
+            self.points[i].tangent_impulse = total;
+            self.apply(bodies, i, tangent * (total - previous));
+        }
+    }
+
+    /// Apply an impulse at a contact point, pushing the second body along it
+    /// and the first body against it.
+    fn apply(&self, bodies: &mut [RigidBody<T>], point: usize, impulse: Vector2D<T>) {
+        let point = &self.points[point];
+        let (ima, iia) = bodies[self.a].effective_inverse_mass();
+        let (imb, iib) = bodies[self.b].effective_inverse_mass();
+        let a = &mut bodies[self.a];
+        a.velocity -= impulse * ima;
+        a.angular_velocity -= cross(point.r_a, impulse) * iia;
+        let b = &mut bodies[self.b];
+        b.velocity += impulse * imb;
+        b.angular_velocity += cross(point.r_b, impulse) * iib;
+    }
+
+    /// Push overlapping bodies apart, to stop errors accumulating into bodies
+    /// sinking into each other.
+    fn correct_positions(&self, bodies: &mut [RigidBody<T>]) {
+        let (ima, _) = bodies[self.a].effective_inverse_mass();
+        let (imb, _) = bodies[self.b].effective_inverse_mass();
+        let total = ima + imb;
+        if total == T::ZERO {
+            return;
+        }
+        let depth =
+            self.points.iter().fold(
+                T::ZERO,
+                |deepest, p| if p.depth > deepest { p.depth } else { deepest },
+            );
+        let excess = depth - T::from_f64(LINEAR_SLOP);
+        if excess <= T::ZERO {
+            return;
+        }
+        let correction = self.normal * (excess * T::from_f64(CORRECTION_FRACTION) / total);
+        bodies[self.a].position -= correction * ima;
+        bodies[self.b].position += correction * imb;
+    }
+}
+
+/// Get the velocity of the second body relative to the first at a contact
+/// point.
+fn relative_velocity<T: Float>(
+    a: &RigidBody<T>,
+    b: &RigidBody<T>,
+    point: &PointConstraint<T>,
+) -> Vector2D<T> {
+    let spin = |w: T, r: Vector2D<T>| Vector2D::new(-w * r.y, w * r.x);
+    b.velocity + spin(b.angular_velocity, point.r_b)
+        - a.velocity
+        - spin(a.angular_velocity, point.r_a)
+}
+
+/// Get `1 / x`, or zero if `x` is zero.
+fn reciprocal<T: Float>(x: T) -> T {
+    if x == T::ZERO {
+        T::ZERO
+    } else {
+        T::ONE / x
+    }
+}
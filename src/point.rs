@@ -0,0 +1,225 @@
+//! Positions, as distinct from the displacements represented by [`Vector2D`].
+//!
+//! A [`Point2D`] is a location, while a `Vector2D` is the offset between two
+//! locations. Only the operations that make sense for each are implemented:
+//!   - point - point = vector
+//!   - point + vector = point
+//!   - point - vector = point
+//!   - vector + vector = vector
+//!
+//! Adding two points together does not compile. When an affine
+//! [`Transform2D`] is applied, points are translated while vectors are not.
+//!
+//! # Example
+//! ```
+//! use vector2d::point::Point2D;
+//! use vector2d::Vector2D;
+//!
+//! let start = Point2D::new(10.0, 5.0);
+//! let end = Point2D::new(13.0, 9.0);
+//!
+//! let offset: Vector2D<f64> = end - start;
+//! assert_eq!(5.0, offset.length());
+//! assert_eq!(end, start + offset);
+//!
+//! // Conversions to and from Vector2D are free
+//! assert_eq!(Vector2D::new(10.0, 5.0), start.to_vector());
+//! assert_eq!(start, Point2D::from_vector(Vector2D::new(10.0, 5.0)));
+//! ```
+//!
+//! [`Vector2D`]: ../struct.Vector2D.html
+//! [`Point2D`]: struct.Point2D.html
+//! [`Transform2D`]: ../transform/struct.Transform2D.html
+
+use crate::Vector2D;
+use std::ops::{Add, AddAssign, Mul, Sub, SubAssign};
+
+/// A position in 2D space, containing an `x` and a `y` component. See the
+/// [module documentation] for how points and vectors interact.
+///
+/// [module documentation]: index.html
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct Point2D<T> {
+    pub x: T,
+    pub y: T,
+}
+
+impl<T: Copy + Clone> Point2D<T> {
+    /// Create a new `Point2D` with the provided components.
+    pub fn new(x: T, y: T) -> Self {
+        Self { x, y }
+    }
+
+    /// Create a point at the position reached by displacing the origin by `v`.
+    pub fn from_vector(v: Vector2D<T>) -> Self {
+        Self { x: v.x, y: v.y }
+    }
+
+    /// Get the displacement of this point from the origin.
+    pub fn to_vector(self) -> Vector2D<T> {
+        Vector2D {
+            x: self.x,
+            y: self.y,
+        }
+    }
+
+    /// Convert a `Point2D` of type `T` to one of type `U`. Available only when
+    /// type T has implemented `Into<U>`.
+    pub fn into_point2d<U: From<T>>(self) -> Point2D<U> {
+        Point2D {
+            x: self.x.into(),
+            y: self.y.into(),
+        }
+    }
+}
+
+impl<T: Default> Point2D<T> {
+    /// Get the point at the origin.
+    pub fn origin() -> Self {
+        Self {
+            x: Default::default(),
+            y: Default::default(),
+        }
+    }
+}
+
+impl<T, U, V> Point2D<T>
+where
+    T: Sub<T, Output = U> + Copy + Clone,
+    U: Mul<U, Output = V> + Copy + Clone,
+    V: Add<V, Output = V>,
+{
+    /// Get the squared distance between two points.
+    pub fn distance_squared(self, other: Self) -> V {
+        let dx = other.x - self.x;
+        let dy = other.y - self.y;
+        dx * dx + dy * dy
+    }
+}
+
+impl<T> Point2D<T>
+where
+    T: Sub<T, Output = T> + Mul<T, Output = T> + Add<T, Output = T> + Copy + Clone,
+{
+    /// Linearly interpolates between two points
+    pub fn lerp(start: Self, end: Self, progress: T) -> Self {
+        start + (end - start) * progress
+    }
+}
+
+impl Point2D<f32> {
+    /// Get the distance between two points. If possible, favour
+    /// `distance_squared()` over this function, as it is more performant.
+    pub fn distance(self, other: Self) -> f32 {
+        (other - self).length()
+    }
+}
+
+impl Point2D<f64> {
+    /// Get the distance between two points. If possible, favour
+    /// `distance_squared()` over this function, as it is more performant.
+    pub fn distance(self, other: Self) -> f64 {
+        (other - self).length()
+    }
+}
+
+// From/Into Implementations
+
+impl<T> From<Vector2D<T>> for Point2D<T> {
+    fn from(src: Vector2D<T>) -> Self {
+        Point2D { x: src.x, y: src.y }
+    }
+}
+
+impl<T> From<Point2D<T>> for Vector2D<T> {
+    fn from(src: Point2D<T>) -> Self {
+        Vector2D { x: src.x, y: src.y }
+    }
+}
+
+impl<T, U> From<(U, U)> for Point2D<T>
+where
+    T: From<U>,
+    U: Copy + Clone,
+{
+    fn from(src: (U, U)) -> Point2D<T> {
+        Point2D {
+            x: src.0.into(),
+            y: src.1.into(),
+        }
+    }
+}
+
+impl<T, U> From<[U; 2]> for Point2D<T>
+where
+    T: From<U>,
+    U: Copy + Clone,
+{
+    fn from(src: [U; 2]) -> Point2D<T> {
+        Point2D {
+            x: src[0].into(),
+            y: src[1].into(),
+        }
+    }
+}
+
+// Ops Implementations
+
+impl<T, O> Sub<Point2D<T>> for Point2D<T>
+where
+    T: Sub<T, Output = O> + Copy + Clone,
+{
+    type Output = Vector2D<O>;
+    fn sub(self, rhs: Point2D<T>) -> Self::Output {
+        Vector2D {
+            x: self.x - rhs.x,
+            y: self.y - rhs.y,
+        }
+    }
+}
+
+impl<T, O> Add<Vector2D<T>> for Point2D<T>
+where
+    T: Add<T, Output = O> + Copy + Clone,
+{
+    type Output = Point2D<O>;
+    fn add(self, rhs: Vector2D<T>) -> Self::Output {
+        Point2D {
+            x: self.x + rhs.x,
+            y: self.y + rhs.y,
+        }
+    }
+}
+
+impl<T, O> Sub<Vector2D<T>> for Point2D<T>
+where
+    T: Sub<T, Output = O> + Copy + Clone,
+{
+    type Output = Point2D<O>;
+    fn sub(self, rhs: Vector2D<T>) -> Self::Output {
+        Point2D {
+            x: self.x - rhs.x,
+            y: self.y - rhs.y,
+        }
+    }
+}
+
+impl<T> AddAssign<Vector2D<T>> for Point2D<T>
+where
+    T: Add<T, Output = T> + Copy + Clone,
+{
+    fn add_assign(&mut self, rhs: Vector2D<T>) {
+        self.x = self.x + rhs.x;
+        self.y = self.y + rhs.y;
+    }
+}
+
+impl<T> SubAssign<Vector2D<T>> for Point2D<T>
+where
+    T: Sub<T, Output = T> + Copy + Clone,
+{
+    fn sub_assign(&mut self, rhs: Vector2D<T>) {
+        self.x = self.x - rhs.x;
+        self.y = self.y - rhs.y;
+    }
+}
@@ -0,0 +1,165 @@
+//! Polar coordinates, for code that naturally thinks in terms of a distance
+//! and a direction, such as orbits and radial menus.
+//!
+//! # Example
+//! ```
+//! use vector2d::angle::Angle;
+//! use vector2d::polar::Polar;
+//! use vector2d::Vector2D;
+//!
+//! let p = Polar::new(2.0f64, Angle::degrees(0.0));
+//!
+//! // Rotation and scaling stay in polar form
+//! let q = p.rotate(Angle::degrees(90.0)) * 1.5;
+//! assert_eq!(3.0, q.radius);
+//!
+//! let v: Vector2D<f64> = q.to_vector();
+//! assert!((v - Vector2D::new(0.0, 3.0)).length() < 1e-9);
+//! ```
+
+use crate::angle::Angle;
+use crate::float::Float;
+use crate::Vector2D;
+use std::ops::{Div, DivAssign, Mul, MulAssign, Neg};
+
+/// A position or displacement expressed as a distance from the origin and a
+/// direction.
+///
+/// The radius is not required to be positive; a negative radius points in the
+/// opposite direction to the angle. Call [`normalised()`] to get the canonical
+/// form.
+///
+/// [`normalised()`]: struct.Polar.html#method.normalised
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct Polar<T> {
+    pub radius: T,
+    pub angle: Angle<T>,
+}
+
+impl<T: Float> Polar<T> {
+    /// Create a new `Polar` with the provided radius and angle.
+    pub fn new(radius: T, angle: Angle<T>) -> Self {
+        Self { radius, angle }
+    }
+
+    /// Convert a `Vector2D` to polar form. The zero vector has an angle of zero.
+    pub fn from_vector(v: Vector2D<T>) -> Self {
+        Self {
+            radius: v.length_squared().sqrt(),
+            angle: v.direction(),
+        }
+    }
+
+    /// Convert to a `Vector2D`.
+    pub fn to_vector(self) -> Vector2D<T> {
+        Vector2D::from_angle(self.angle) * self.radius
+    }
+
+    /// Get the equivalent coordinates with a non-negative radius and an angle
+    /// within `(-π, π]`.
+    pub fn normalised(self) -> Self {
+        if self.radius < T::ZERO {
+            Self {
+                radius: -self.radius,
+                angle: (self.angle + Angle::radians(T::PI)).normalised_signed(),
+            }
+        } else {
+            Self {
+                radius: self.radius,
+                angle: self.angle.normalised_signed(),
+            }
+        }
+    }
+
+    /// Get these coordinates rotated counter-clockwise about the origin by the
+    /// provided angle.
+    pub fn rotate(self, angle: Angle<T>) -> Self {
+        Self {
+            radius: self.radius,
+            angle: self.angle + angle,
+        }
+    }
+
+    /// Interpolates between two coordinates along an arc about the origin. The
+    /// radius is interpolated linearly, and the angle along the shortest arc.
+    pub fn lerp(start: Self, end: Self, progress: T) -> Self {
+        let start = start.normalised();
+        let end = end.normalised();
+        Self {
+            radius: start.radius + (end.radius - start.radius) * progress,
+            angle: Angle::lerp(start.angle, end.angle, progress),
+        }
+    }
+}
+
+impl<T: Float> Vector2D<T> {
+    /// Convert this vector to [`Polar`] coordinates.
+    ///
+    /// [`Polar`]: polar/struct.Polar.html
+    pub fn to_polar(self) -> Polar<T> {
+        Polar::from_vector(self)
+    }
+
+    /// Create a vector from its length and direction.
+    pub fn from_polar(radius: T, angle: Angle<T>) -> Self {
+        Polar::new(radius, angle).to_vector()
+    }
+}
+
+// From/Into Implementations
+
+impl<T: Float> From<Vector2D<T>> for Polar<T> {
+    fn from(src: Vector2D<T>) -> Self {
+        Self::from_vector(src)
+    }
+}
+
+impl<T: Float> From<Polar<T>> for Vector2D<T> {
+    fn from(src: Polar<T>) -> Self {
+        src.to_vector()
+    }
+}
+
+// Ops Implementations
+
+impl<T: Float> Mul<T> for Polar<T> {
+    type Output = Polar<T>;
+    fn mul(self, rhs: T) -> Self::Output {
+        Polar {
+            radius: self.radius * rhs,
+            angle: self.angle,
+        }
+    }
+}
+
+impl<T: Float> MulAssign<T> for Polar<T> {
+    fn mul_assign(&mut self, rhs: T) {
+        self.radius *= rhs;
+    }
+}
+
+impl<T: Float> Div<T> for Polar<T> {
+    type Output = Polar<T>;
+    fn div(self, rhs: T) -> Self::Output {
+        Polar {
+            radius: self.radius / rhs,
+            angle: self.angle,
+        }
+    }
+}
+
+impl<T: Float> DivAssign<T> for Polar<T> {
+    fn div_assign(&mut self, rhs: T) {
+        self.radius /= rhs;
+    }
+}
+
+impl<T: Float> Neg for Polar<T> {
+    type Output = Polar<T>;
+    fn neg(self) -> Self::Output {
+        Polar {
+            radius: -self.radius,
+            angle: self.angle,
+        }
+    }
+}
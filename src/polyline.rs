@@ -0,0 +1,319 @@
+//! Simplifying, resampling and smoothing polylines.
+//!
+//! Recorded input, such as mouse gestures or GPS traces, tends to have far more
+//! points than needed, unevenly spaced and full of jitter. The functions here
+//! clean it up:
+//!   - [`ramer_douglas_peucker()`] keeps enough points to stay within a
+//!     distance of the original line.
+//!   - [`visvalingam_whyatt()`] repeatedly removes the point which makes the
+//!     smallest triangle with its neighbours, which tends to keep the overall
+//!     shape better than a distance bound.
+//!   - [`resample()`] and [`resample_by_length()`] place points evenly along
+//!     the line, as gesture recognisers usually expect.
+//!   - [`chaikin()`] rounds off corners by repeatedly cutting them.
+//!
+//! # Example
+//! ```
+//! use vector2d::polyline::{ramer_douglas_peucker, resample};
+//! use vector2d::Vector2D;
+//!
+//! // A wobbly line along the x axis
+//! let trace: Vec<Vector2D<f64>> = (0..=100)
+//!     .map(|i| Vector2D::new(i as f64, if i % 2 == 0 { 0.1 } else { -0.1 }))
+//!     .collect();
+//! let simplified = ramer_douglas_peucker(&trace, 0.5);
+//! assert_eq!(vec![trace[0], trace[100]], simplified);
+//!
+//! let even = resample(&simplified, 5);
+//! assert_eq!(Vector2D::new(25.0, 0.1), even[1]);
+//! ```
+//!
+//! [`ramer_douglas_peucker()`]: fn.ramer_douglas_peucker.html
+//! [`visvalingam_whyatt()`]: fn.visvalingam_whyatt.html
+//! [`resample()`]: fn.resample.html
+//! [`resample_by_length()`]: fn.resample_by_length.html
+//! [`chaikin()`]: fn.chaikin.html
+
+use crate::float::Float;
+use crate::shape::cross;
+use crate::Vector2D;
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
+
+/// Simplify a polyline with the Ramer–Douglas–Peucker algorithm, which
+/// greedily keeps the point furthest from the simplified line until every
+/// removed point lies within `epsilon` of it. This keeps few points, though not
+/// necessarily the fewest possible. The ends are always kept.
+pub fn ramer_douglas_peucker<T: Float>(points: &[Vector2D<T>], epsilon: T) -> Vec<Vector2D<T>> {
+    if points.len() < 3 {
+        return points.to_vec();
+    }
+    let mut keep = vec![false; points.len()];
+    keep[0] = true;
+    keep[points.len() - 1] = true;
+    // Split each span at its furthest point from the line between its ends,
+    // until no point is further than epsilon
+    let mut spans = vec![(0, points.len() - 1)];
+    while let Some((start, end)) = spans.pop() {
+        let mut furthest = None;
+        let mut furthest_distance = epsilon;
+        for (i, &p) in points.iter().enumerate().take(end).skip(start + 1) {
+            let distance = segment_distance(p, points[start], points[end]);
+            if distance > furthest_distance {
+                furthest = Some(i);
+                furthest_distance = distance;
+            }
+        }
+        if let Some(i) = furthest {
+            keep[i] = true;
+            spans.push((start, i));
+            spans.push((i, end));
+        }
+    }
+    points
+        .iter()
+        .zip(keep)
+        .filter_map(|(&p, keep)| if keep { Some(p) } else { None })
+        .collect()
+}
+
+/// Simplify a polyline with the Visvalingam–Whyatt algorithm, repeatedly
+/// removing the point which makes the smallest triangle with its neighbours,
+/// until every remaining triangle has an area of at least `min_area`. The ends
+/// are always kept.
+///
+/// A point's area never counts as smaller than that of a point removed before
+/// it, so that removing a point can't make its neighbours suddenly
+/// insignificant.
+pub fn visvalingam_whyatt<T: Float>(points: &[Vector2D<T>], min_area: T) -> Vec<Vector2D<T>> {
+    let n = points.len();
+    if n < 3 {
+        return points.to_vec();
+    }
+    let mut prev: Vec<usize> = (0..n).map(|i| i.saturating_sub(1)).collect();
+    let mut next: Vec<usize> = (1..=n).collect();
+    let triangle = |a: usize, b: usize, c: usize| {
+        cross(points[b] - points[a], points[c] - points[a])
+            .abs()
+            .to_f64()
+            / 2.0
+    };
+    let mut areas: Vec<f64> = (0..n)
+        .map(|i| match i {
+            0 => f64::INFINITY,
+            _ if i == n - 1 => f64::INFINITY,
+            _ => triangle(i - 1, i, i + 1),
+        })
+        .collect();
+    let mut heap: BinaryHeap<Candidate> = (1..n - 1)
+        .map(|index| Candidate {
+            area: areas[index],
+            index,
+        })
+        .collect();
+
+    let mut removed = vec![false; n];
+    let min_area = min_area.to_f64();
+    while let Some(Candidate { area, index }) = heap.pop() {
+        // Skip points already removed, or whose area has changed since
+        if removed[index] || area != areas[index] {
+            continue;
+        }
+        if area >= min_area {
+            break;
+        }
+        removed[index] = true;
+        let (before, after) = (prev[index], next[index]);
+        next[before] = after;
+        prev[after] = before;
+        for neighbour in [before, after] {
+            if neighbour != 0 && neighbour != n - 1 {
+                let new_area = triangle(prev[neighbour], neighbour, next[neighbour]).max(area);
+                areas[neighbour] = new_area;
+                heap.push(Candidate {
+                    area: new_area,
+                    index: neighbour,
+                });
+            }
+        }
+    }
+    points
+        .iter()
+        .zip(removed)
+        .filter_map(|(&p, removed)| if removed { None } else { Some(p) })
+        .collect()
+}
+
+/// A point which could be removed, ordered so that the point with the
+/// smallest area is popped from a `BinaryHeap` first.
+#[derive(Copy, Clone, Debug, PartialEq)]
+struct Candidate {
+    area: f64,
+    index: usize,
+}
+
+impl Eq for Candidate {}
+
+impl Ord for Candidate {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other
+            .area
+            .partial_cmp(&self.area)
+            .unwrap_or(Ordering::Equal)
+            .then_with(|| other.index.cmp(&self.index))
+    }
+}
+
+impl PartialOrd for Candidate {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Get `count` points spaced evenly along a polyline, by distance along it,
+/// including both ends.
+pub fn resample<T: Float>(points: &[Vector2D<T>], count: usize) -> Vec<Vector2D<T>> {
+    if points.is_empty() {
+        return Vec::new();
+    }
+    match count {
+        0 => Vec::new(),
+        1 => vec![points[0]],
+        _ => {
+            let step = length(points) / T::from_f64((count - 1) as f64);
+            let mut resampled =
+                points_at_lengths(points, (0..count - 1).map(|i| step * T::from_f64(i as f64)));
+            // Finish exactly on the end, whatever the rounding
+            resampled.push(points[points.len() - 1]);
+            resampled
+        }
+    }
+}
+
+/// Get points spaced `spacing` apart along a polyline, by distance along it,
+/// starting from its first point. The last point is also included, so the
+/// final gap may be shorter.
+///
+/// # Panics
+/// Panics if `spacing` isn't positive.
+pub fn resample_by_length<T: Float>(points: &[Vector2D<T>], spacing: T) -> Vec<Vector2D<T>> {
+    assert!(spacing > T::ZERO, "spacing must be positive");
+    if points.is_empty() {
+        return Vec::new();
+    }
+    let total = length(points);
+    let count = (total / spacing).floor().to_f64() as usize;
+    let mut resampled = points_at_lengths(
+        points,
+        (0..=count)
+            .map(|i| spacing * T::from_f64(i as f64))
+            .filter(|&distance| distance < total),
+    );
+    resampled.push(points[points.len() - 1]);
+    resampled
+}
+
+/// Get the total length of a polyline.
+fn length<T: Float>(points: &[Vector2D<T>]) -> T {
+    points.windows(2).fold(T::ZERO, |total, pair| {
+        total + (pair[1] - pair[0]).length_squared().sqrt()
+    })
+}
+
+/// Get the points at increasing distances along a polyline.
+fn points_at_lengths<T: Float, I: Iterator<Item = T>>(
+    points: &[Vector2D<T>],
+    lengths: I,
+) -> Vec<Vector2D<T>> {
+    let mut result = Vec::new();
+    let mut segment = 0;
+    let mut start = T::ZERO;
+    for target in lengths {
+        // Move on to the segment containing the target distance
+        while segment + 2 < points.len() {
+            let segment_length = (points[segment + 1] - points[segment])
+                .length_squared()
+                .sqrt();
+            if start + segment_length > target {
+                break;
+            }
+            start += segment_length;
+            segment += 1;
+        }
+        if points.len() == 1 {
+            result.push(points[0]);
+            continue;
+        }
+        let (a, b) = (points[segment], points[segment + 1]);
+        let segment_length = (b - a).length_squared().sqrt();
+        let t = if segment_length > T::ZERO && target - start < segment_length {
+            (target - start) / segment_length
+        } else if segment_length > T::ZERO {
+            T::ONE
+        } else {
+            T::ZERO
+        };
+        result.push(a + (b - a) * t);
+    }
+    result
+}
+
+/// Smooth a polyline with Chaikin's corner cutting algorithm, replacing each
+/// segment with points a quarter and three quarters of the way along it, and
+/// repeating `iterations` times. Each iteration almost doubles the number of
+/// points, and the result approaches a quadratic B-spline.
+///
+/// Open polylines keep their ends, while closed polylines, whose last point
+/// joins back to the first, have every corner cut.
+pub fn chaikin<T: Float>(
+    points: &[Vector2D<T>],
+    iterations: usize,
+    closed: bool,
+) -> Vec<Vector2D<T>> {
+    let mut points = points.to_vec();
+    if points.len() < 3 {
+        return points;
+    }
+    let (quarter, three_quarters) = (T::from_f64(0.25), T::from_f64(0.75));
+    for _ in 0..iterations {
+        let n = points.len();
+        let segments = if closed { n } else { n - 1 };
+        let mut smoothed = Vec::with_capacity(segments * 2 + 2);
+        if !closed {
+            smoothed.push(points[0]);
+        }
+        for i in 0..segments {
+            let (a, b) = (points[i], points[(i + 1) % n]);
+            smoothed.push(a * three_quarters + b * quarter);
+            smoothed.push(a * quarter + b * three_quarters);
+        }
+        if !closed {
+            // The cuts next to the ends lie on the straight line from them,
+            // so add nothing
+            smoothed.remove(1);
+            smoothed.remove(smoothed.len() - 1);
+            smoothed.push(points[n - 1]);
+        }
+        points = smoothed;
+    }
+    points
+}
+
+/// Get the distance from a point to the line segment from `a` to `b`.
+fn segment_distance<T: Float>(p: Vector2D<T>, a: Vector2D<T>, b: Vector2D<T>) -> T {
+    let ab = b - a;
+    let length_squared = ab.length_squared();
+    let t = if length_squared > T::ZERO {
+        let t = Vector2D::dot(p - a, ab) / length_squared;
+        if t < T::ZERO {
+            T::ZERO
+        } else if t > T::ONE {
+            T::ONE
+        } else {
+            t
+        }
+    } else {
+        T::ZERO
+    };
+    (p - (a + ab * t)).length_squared().sqrt()
+}
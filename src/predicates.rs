@@ -0,0 +1,342 @@
+//! Exact geometric predicates.
+//!
+//! Geometric algorithms make decisions based on which side of a line a point
+//! is on, or whether a point is inside a circle. With floating point
+//! arithmetic, rounding errors can give the wrong answer for points that are
+//! nearly collinear or nearly cocircular, and algorithms that make
+//! inconsistent decisions can loop forever or produce broken output.
+//!
+//! [`orient2d()`] and [`incircle()`] always give the exact answer. Following
+//! Shewchuk's adaptive precision predicates, they first make a quick
+//! approximation, and only when it is too close to call do they fall back to
+//! exact arithmetic, by representing values as sums of non-overlapping `f64`s.
+//! This works for any coordinate type implementing [`Exact`], which includes
+//! `f64`, `f32`, `i32` and `i64`, where the predicates can't overflow.
+//!
+//! Floating point coordinates are assumed to be finite, and far enough from
+//! the limits of `f64` that products of their differences don't overflow or
+//! underflow.
+//!
+//! # Example
+//! ```
+//! use vector2d::predicates::{orient2d, Orientation};
+//! use vector2d::Vector2D;
+//!
+//! // Points that are very nearly collinear
+//! let a = Vector2D::new(0.5f64, 0.5);
+//! let b = Vector2D::new(12.0, 12.0);
+//! let c = Vector2D::new(24.0, 24.0);
+//! let nudged = Vector2D::new(0.5 + f64::EPSILON, 0.5);
+//! assert_eq!(Orientation::Collinear, orient2d(a, b, c));
+//! assert_eq!(Orientation::Clockwise, orient2d(nudged, b, c));
+//!
+//! // Integer predicates don't overflow
+//! let big = Vector2D::new(i64::MAX, i64::MAX);
+//! let origin = Vector2D::new(0, 0);
+//! assert_eq!(Orientation::Collinear, orient2d(origin, big, Vector2D::new(1, 1)));
+//! assert_eq!(Orientation::CounterClockwise, orient2d(origin, big, Vector2D::new(1, 2)));
+//! ```
+//!
+//! [`orient2d()`]: fn.orient2d.html
+//! [`incircle()`]: fn.incircle.html
+//! [`Exact`]: trait.Exact.html
+
+use crate::Vector2D;
+
+/// Half of the difference between 1 and the next `f64`, which bounds the
+/// relative error of a single rounded operation.
+const EPSILON: f64 = f64::EPSILON / 2.0;
+/// The relative error bound of the approximate orientation test.
+const ORIENT_BOUND: f64 = (3.0 + 16.0 * EPSILON) * EPSILON;
+/// The relative error bound of the approximate in-circle test.
+const INCIRCLE_BOUND: f64 = (10.0 + 96.0 * EPSILON) * EPSILON;
+
+/// The direction in which three points turn.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum Orientation {
+    Clockwise,
+    Collinear,
+    CounterClockwise,
+}
+
+/// Where a point lies relative to a circle.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum CirclePosition {
+    Inside,
+    On,
+    Outside,
+}
+
+/// Coordinate types which can be represented exactly with `f64`s, and so can
+/// be used with the exact predicates.
+pub trait Exact: Copy {
+    /// Convert to the nearest `f64`.
+    fn to_f64(self) -> f64;
+    /// Convert to an `f64`, or `None` if that would round the value.
+    fn to_exact_f64(self) -> Option<f64>;
+    /// Get the value as a sum of non-overlapping `f64`s, in order of increasing
+    /// magnitude, leaving out zeroes.
+    fn to_expansion(self) -> Vec<f64>;
+}
+
+impl Exact for f64 {
+    fn to_f64(self) -> f64 {
+        self
+    }
+
+    fn to_exact_f64(self) -> Option<f64> {
+        Some(self)
+    }
+
+    fn to_expansion(self) -> Vec<f64> {
+        if self == 0.0 {
+            Vec::new()
+        } else {
+            vec![self]
+        }
+    }
+}
+
+impl Exact for f32 {
+    fn to_f64(self) -> f64 {
+        self as f64
+    }
+
+    fn to_exact_f64(self) -> Option<f64> {
+        Some(self as f64)
+    }
+
+    fn to_expansion(self) -> Vec<f64> {
+        (self as f64).to_expansion()
+    }
+}
+
+impl Exact for i32 {
+    fn to_f64(self) -> f64 {
+        self as f64
+    }
+
+    fn to_exact_f64(self) -> Option<f64> {
+        Some(self as f64)
+    }
+
+    fn to_expansion(self) -> Vec<f64> {
+        (self as f64).to_expansion()
+    }
+}
+
+impl Exact for i64 {
+    fn to_f64(self) -> f64 {
+        self as f64
+    }
+
+    fn to_exact_f64(self) -> Option<f64> {
+        let rounded = self as f64;
+        // Rounding can only carry a value up to 2^63, which doesn't fit in an
+        // i64, so compare in a wider type
+        if rounded as i128 == self as i128 {
+            Some(rounded)
+        } else {
+            None
+        }
+    }
+
+    fn to_expansion(self) -> Vec<f64> {
+        // The rounding error of the conversion is less than 2^11, so it fits
+        // in an f64 exactly
+        let high = self as f64;
+        let low = (self as i128 - high as i128) as f64;
+        let mut expansion = low.to_expansion();
+        expansion.extend(high.to_expansion());
+        expansion
+    }
+}
+
+/// Find whether `a`, `b` and `c` turn counter-clockwise, clockwise, or lie on a
+/// line, assuming the y axis points up.
+pub fn orient2d<T: Exact>(a: Vector2D<T>, b: Vector2D<T>, c: Vector2D<T>) -> Orientation {
+    if let Some([a, b, c]) = exact_f64([a, b, c]) {
+        let left = (a.x - c.x) * (b.y - c.y);
+        let right = (a.y - c.y) * (b.x - c.x);
+        let det = left - right;
+        // When the two products differ in sign, their difference can't be
+        // rounded to the wrong sign
+        let sum = if (left > 0.0 && right > 0.0) || (left < 0.0 && right < 0.0) {
+            left.abs() + right.abs()
+        } else {
+            0.0
+        };
+        if det.abs() > ORIENT_BOUND * sum || sum == 0.0 {
+            return orientation(det);
+        }
+    }
+
+    let [ax, ay, bx, by, cx, cy] = [a.x, a.y, b.x, b.y, c.x, c.y].map(T::to_expansion);
+    let acx = difference(&ax, &cx);
+    let acy = difference(&ay, &cy);
+    let bcx = difference(&bx, &cx);
+    let bcy = difference(&by, &cy);
+    let det = difference(&product(&acx, &bcy), &product(&acy, &bcx));
+    orientation(det.last().copied().unwrap_or(0.0))
+}
+
+/// Find where `d` lies relative to the circle through `a`, `b` and `c`, which
+/// must turn counter-clockwise, as found by [`orient2d()`]. If they turn
+/// clockwise, the inside and outside of the circle are swapped, and if they
+/// are collinear, the circle is a line, and points are never inside it.
+///
+/// [`orient2d()`]: fn.orient2d.html
+pub fn incircle<T: Exact>(
+    a: Vector2D<T>,
+    b: Vector2D<T>,
+    c: Vector2D<T>,
+    d: Vector2D<T>,
+) -> CirclePosition {
+    if let Some([a, b, c, d]) = exact_f64([a, b, c, d]) {
+        let (adx, ady) = (a.x - d.x, a.y - d.y);
+        let (bdx, bdy) = (b.x - d.x, b.y - d.y);
+        let (cdx, cdy) = (c.x - d.x, c.y - d.y);
+        let (bdxcdy, cdxbdy) = (bdx * cdy, cdx * bdy);
+        let (cdxady, adxcdy) = (cdx * ady, adx * cdy);
+        let (adxbdy, bdxady) = (adx * bdy, bdx * ady);
+        let alift = adx * adx + ady * ady;
+        let blift = bdx * bdx + bdy * bdy;
+        let clift = cdx * cdx + cdy * cdy;
+        let det = alift * (bdxcdy - cdxbdy) + blift * (cdxady - adxcdy) + clift * (adxbdy - bdxady);
+        let permanent = (bdxcdy.abs() + cdxbdy.abs()) * alift
+            + (cdxady.abs() + adxcdy.abs()) * blift
+            + (adxbdy.abs() + bdxady.abs()) * clift;
+        if det.abs() > INCIRCLE_BOUND * permanent {
+            return circle_position(det);
+        }
+    }
+
+    let [ax, ay, bx, by, cx, cy, dx, dy] =
+        [a.x, a.y, b.x, b.y, c.x, c.y, d.x, d.y].map(T::to_expansion);
+    let (adx, ady) = (difference(&ax, &dx), difference(&ay, &dy));
+    let (bdx, bdy) = (difference(&bx, &dx), difference(&by, &dy));
+    let (cdx, cdy) = (difference(&cx, &dx), difference(&cy, &dy));
+    let lift = |x: &[f64], y: &[f64]| sum(&product(x, x), &product(y, y));
+    let cross = |x1: &[f64], y1: &[f64], x2: &[f64], y2: &[f64]| {
+        difference(&product(x1, y2), &product(y1, x2))
+    };
+    let det = sum(
+        &sum(
+            &product(&lift(&adx, &ady), &cross(&bdx, &bdy, &cdx, &cdy)),
+            &product(&lift(&bdx, &bdy), &cross(&cdx, &cdy, &adx, &ady)),
+        ),
+        &product(&lift(&cdx, &cdy), &cross(&adx, &ady, &bdx, &bdy)),
+    );
+    circle_position(det.last().copied().unwrap_or(0.0))
+}
+
+/// Convert points to `f64`s, or `None` if any coordinate would be rounded.
+fn exact_f64<T: Exact, const N: usize>(points: [Vector2D<T>; N]) -> Option<[Vector2D<f64>; N]> {
+    let mut result = [Vector2D::new(0.0, 0.0); N];
+    for (exact, p) in result.iter_mut().zip(points.iter()) {
+        *exact = Vector2D::new(p.x.to_exact_f64()?, p.y.to_exact_f64()?);
+    }
+    Some(result)
+}
+
+fn orientation(det: f64) -> Orientation {
+    if det > 0.0 {
+        Orientation::CounterClockwise
+    } else if det < 0.0 {
+        Orientation::Clockwise
+    } else {
+        Orientation::Collinear
+    }
+}
+
+fn circle_position(det: f64) -> CirclePosition {
+    if det > 0.0 {
+        CirclePosition::Inside
+    } else if det < 0.0 {
+        CirclePosition::Outside
+    } else {
+        CirclePosition::On
+    }
+}
+
+/// Add two `f64`s, returning the rounded sum and its rounding error.
+fn two_sum(a: f64, b: f64) -> (f64, f64) {
+    let x = a + b;
+    let b_virtual = x - a;
+    let a_virtual = x - b_virtual;
+    (x, (a - a_virtual) + (b - b_virtual))
+}
+
+/// Multiply two `f64`s, returning the rounded product and its rounding error.
+fn two_product(a: f64, b: f64) -> (f64, f64) {
+    let x = a * b;
+    (x, a.mul_add(b, -x))
+}
+
+/// Add an `f64` to an expansion, leaving out zeroes.
+fn grow(e: &[f64], b: f64) -> Vec<f64> {
+    let mut result = Vec::with_capacity(e.len() + 1);
+    let mut q = b;
+    for &component in e {
+        let (sum, error) = two_sum(q, component);
+        q = sum;
+        if error != 0.0 {
+            result.push(error);
+        }
+    }
+    if q != 0.0 {
+        result.push(q);
+    }
+    result
+}
+
+/// Add two expansions.
+fn sum(e: &[f64], f: &[f64]) -> Vec<f64> {
+    f.iter()
+        .fold(e.to_vec(), |total, &component| grow(&total, component))
+}
+
+/// Subtract one expansion from another.
+fn difference(e: &[f64], f: &[f64]) -> Vec<f64> {
+    f.iter()
+        .fold(e.to_vec(), |total, &component| grow(&total, -component))
+}
+
+/// Multiply an expansion by an `f64`, leaving out zeroes.
+fn scale(e: &[f64], b: f64) -> Vec<f64> {
+    let mut result = Vec::with_capacity(e.len() * 2);
+    let mut iter = e.iter();
+    let mut q = match iter.next() {
+        Some(&first) => {
+            let (product, error) = two_product(first, b);
+            if error != 0.0 {
+                result.push(error);
+            }
+            product
+        }
+        None => return result,
+    };
+    for &component in iter {
+        let (product, product_error) = two_product(component, b);
+        let (sum, sum_error) = two_sum(q, product_error);
+        if sum_error != 0.0 {
+            result.push(sum_error);
+        }
+        let (total, total_error) = two_sum(product, sum);
+        if total_error != 0.0 {
+            result.push(total_error);
+        }
+        q = total;
+    }
+    if q != 0.0 {
+        result.push(q);
+    }
+    result
+}
+
+/// Multiply two expansions.
+fn product(e: &[f64], f: &[f64]) -> Vec<f64> {
+    f.iter().fold(Vec::new(), |total, &component| {
+        sum(&total, &scale(e, component))
+    })
+}
@@ -0,0 +1,197 @@
+//! Collision shapes, and the poses that place them in the world.
+//!
+//! Shapes are described in local coordinates around an origin, which should be
+//! their centre of mass when used with the [`physics`] module. A [`Pose`]
+//! rotates a shape about its origin and then moves it into position.
+//!
+//! # Example
+//! ```
+//! use vector2d::angle::Angle;
+//! use vector2d::shape::{Pose, Shape};
+//! use vector2d::Vector2D;
+//!
+//! let crate_shape = Shape::rectangle(Vector2D::new(1.0f64, 0.5));
+//! let pose = Pose::new(Vector2D::new(10.0, 0.0), Angle::degrees(90.0));
+//!
+//! let bounds = crate_shape.bounding_box(&pose);
+//! assert!((bounds.min - Vector2D::new(9.5, -1.0)).length() < 1e-9);
+//! assert!((bounds.max - Vector2D::new(10.5, 1.0)).length() < 1e-9);
+//! ```
+//!
+//! [`physics`]: ../physics/index.html
+//! [`Pose`]: struct.Pose.html
+
+use crate::angle::Angle;
+use crate::box2d::Box2D;
+use crate::float::Float;
+use crate::Vector2D;
+
+/// A position and rotation, placing a shape in the world.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct Pose<T> {
+    pub position: Vector2D<T>,
+    pub rotation: Angle<T>,
+}
+
+impl<T: Float> Pose<T> {
+    /// Create a new `Pose` with the provided position and rotation.
+    pub fn new(position: Vector2D<T>, rotation: Angle<T>) -> Self {
+        Self { position, rotation }
+    }
+
+    /// Get the pose at the origin, without any rotation.
+    pub fn identity() -> Self {
+        Self::new(Vector2D::new(T::ZERO, T::ZERO), Angle::zero())
+    }
+
+    /// Convert a point from local coordinates to world coordinates.
+    pub fn transform_point(&self, local: Vector2D<T>) -> Vector2D<T> {
+        local.rotate(self.rotation) + self.position
+    }
+
+    /// Convert a point from world coordinates to local coordinates.
+    pub fn inverse_transform_point(&self, world: Vector2D<T>) -> Vector2D<T> {
+        (world - self.position).rotate(-self.rotation)
+    }
+}
+
+/// A collision shape.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Shape<T> {
+    /// A circle of the provided radius, centred on the origin.
+    Circle(T),
+    /// A convex polygon of at least three vertices, wound counter-clockwise.
+    ///
+    /// Collision detection relies on these properties without checking them,
+    /// so build polygons with [`Shape::polygon()`] unless they are already
+    /// known to hold.
+    ///
+    /// [`Shape::polygon()`]: #method.polygon
+    Polygon(Vec<Vector2D<T>>),
+}
+
+impl<T: Float> Shape<T> {
+    /// Create a circle of the provided radius, centred on the origin.
+    pub fn circle(radius: T) -> Self {
+        Shape::Circle(radius)
+    }
+
+    /// Create a rectangle centred on the origin, extending `half_extents` along
+    /// each axis.
+    pub fn rectangle(half_extents: Vector2D<T>) -> Self {
+        let (hx, hy) = (half_extents.x, half_extents.y);
+        Shape::Polygon(vec![
+            Vector2D::new(-hx, -hy),
+            Vector2D::new(hx, -hy),
+            Vector2D::new(hx, hy),
+            Vector2D::new(-hx, hy),
+        ])
+    }
+
+    /// Create a convex polygon from its vertices. Clockwise vertices are
+    /// reversed so that the polygon always winds counter-clockwise.
+    ///
+    /// # Panics
+    /// Panics if there are fewer than three vertices, or they don't form a
+    /// convex polygon.
+    pub fn polygon(mut vertices: Vec<Vector2D<T>>) -> Self {
+        assert!(
+            vertices.len() >= 3,
+            "a polygon needs at least three vertices"
+        );
+        if signed_area(&vertices) < T::ZERO {
+            vertices.reverse();
+        }
+        assert!(is_convex(&vertices), "a polygon must be convex");
+        Shape::Polygon(vertices)
+    }
+
+    /// Get the area of the shape.
+    pub fn area(&self) -> T {
+        match self {
+            Shape::Circle(r) => T::PI * *r * *r,
+            Shape::Polygon(vertices) => signed_area(vertices).abs(),
+        }
+    }
+
+    /// Get the moment of inertia of the shape about its origin, given its mass.
+    pub fn moment_of_inertia(&self, mass: T) -> T {
+        match self {
+            Shape::Circle(r) => mass * *r * *r / T::from_f64(2.0),
+            Shape::Polygon(vertices) => {
+                let mut numerator = T::ZERO;
+                let mut denominator = T::ZERO;
+                for (i, &a) in vertices.iter().enumerate() {
+                    let b = vertices[(i + 1) % vertices.len()];
+                    let cross = cross(a, b).abs();
+                    numerator +=
+                        cross * (Vector2D::dot(a, a) + Vector2D::dot(a, b) + Vector2D::dot(b, b));
+                    denominator += cross;
+                }
+                if denominator == T::ZERO {
+                    T::ZERO
+                } else {
+                    mass * numerator / (T::from_f64(6.0) * denominator)
+                }
+            }
+        }
+    }
+
+    /// Get the smallest axis-aligned box containing the shape at the provided
+    /// pose.
+    pub fn bounding_box(&self, pose: &Pose<T>) -> Box2D<T> {
+        match self {
+            Shape::Circle(r) => {
+                let extent = Vector2D::new(*r, *r);
+                Box2D::new(pose.position - extent, pose.position + extent)
+            }
+            Shape::Polygon(vertices) => {
+                Box2D::from_points(vertices.iter().map(|&v| pose.transform_point(v)))
+                    .expect("polygons have at least three vertices")
+            }
+        }
+    }
+
+    /// Check whether the point, in world coordinates, lies within the shape at
+    /// the provided pose.
+    pub fn contains(&self, pose: &Pose<T>, point: Vector2D<T>) -> bool {
+        let local = pose.inverse_transform_point(point);
+        match self {
+            Shape::Circle(r) => local.length_squared() <= *r * *r,
+            Shape::Polygon(vertices) => vertices.iter().enumerate().all(|(i, &a)| {
+                let b = vertices[(i + 1) % vertices.len()];
+                cross(b - a, local - a) >= T::ZERO
+            }),
+        }
+    }
+}
+
+/// Get the z component of the cross product of two vectors, treating them as
+/// 3D vectors lying in the xy plane.
+pub(crate) fn cross<T: Float>(a: Vector2D<T>, b: Vector2D<T>) -> T {
+    a.x * b.y - a.y * b.x
+}
+
+/// Check that every vertex of a counter-clockwise polygon lies on or to the
+/// left of every edge, allowing for rounding in nearly straight corners.
+fn is_convex<T: Float>(vertices: &[Vector2D<T>]) -> bool {
+    let tolerance = T::from_f64(1e-9);
+    vertices.iter().enumerate().all(|(i, &a)| {
+        let edge = vertices[(i + 1) % vertices.len()] - a;
+        let edge_length = edge.length_squared().sqrt();
+        vertices.iter().all(|&p| {
+            let offset = p - a;
+            cross(edge, offset) >= -tolerance * edge_length * offset.length_squared().sqrt()
+        })
+    })
+}
+
+/// Get the signed area of a polygon, which is positive when its vertices wind
+/// counter-clockwise.
+pub(crate) fn signed_area<T: Float>(vertices: &[Vector2D<T>]) -> T {
+    let mut sum = T::ZERO;
+    for (i, &a) in vertices.iter().enumerate() {
+        sum += cross(a, vertices[(i + 1) % vertices.len()]);
+    }
+    sum / T::from_f64(2.0)
+}
@@ -0,0 +1,221 @@
+//! Frame-rate independent smoothing of `Vector2D` values towards a target.
+//!
+//! Calling `lerp(current, target, 0.1)` every frame moves further per second
+//! at high frame rates than at low ones. The functions here take the time step
+//! into account, so that they behave the same regardless of frame rate:
+//!   - [`damp()`] moves exponentially towards the target.
+//!   - [`smooth_damp()`] accelerates towards the target and eases in without
+//!     overshooting, tracking a velocity between calls.
+//!   - [`SpringDamper`] simulates a spring, which can be tuned to bounce or to
+//!     settle as quickly as possible.
+//!
+//! # Example
+//! ```
+//! use vector2d::Vector2D;
+//!
+//! let target = Vector2D::new(100.0, 0.0);
+//!
+//! // Two 30fps frames end up in the same place as one 15fps frame
+//! let mut a = Vector2D::new(0.0f64, 0.0);
+//! a = Vector2D::damp(a, target, 5.0, 1.0 / 30.0);
+//! a = Vector2D::damp(a, target, 5.0, 1.0 / 30.0);
+//! let b = Vector2D::damp(Vector2D::new(0.0, 0.0), target, 5.0, 1.0 / 15.0);
+//! assert!((a - b).length() < 1e-9);
+//! ```
+//!
+//! [`damp()`]: ../struct.Vector2D.html#method.damp
+//! [`smooth_damp()`]: ../struct.Vector2D.html#method.smooth_damp
+//! [`SpringDamper`]: struct.SpringDamper.html
+
+use crate::float::Float;
+use crate::Vector2D;
+
+impl<T: Float> Vector2D<T> {
+    /// Move `current` towards `target`, covering the fraction
+    /// `1 - e^(-rate * dt)` of the remaining distance. Higher rates converge
+    /// more quickly, and the remaining distance halves every `ln(2) / rate`
+    /// seconds.
+    ///
+    /// A per-frame `lerp(current, target, f)` at a fixed `fps` is equivalent to
+    /// a rate of `-ln(1 - f) * fps`.
+    pub fn damp(current: Self, target: Self, rate: T, dt: T) -> Self {
+        Self::lerp(current, target, T::ONE - (-rate * dt).exp())
+    }
+
+    /// Move `current` towards `target` as if connected by a critically damped
+    /// spring, reaching it in roughly `smooth_time` seconds without
+    /// overshooting. This matches the behaviour of Unity's `SmoothDamp`.
+    ///
+    /// `velocity` carries the motion between calls, and should start at zero.
+    /// If `max_speed` is provided, the speed of the movement is limited to it.
+    pub fn smooth_damp(
+        current: Self,
+        target: Self,
+        velocity: &mut Self,
+        smooth_time: T,
+        max_speed: Option<T>,
+        dt: T,
+    ) -> Self {
+        let min_time = T::from_f64(1e-4);
+        let smooth_time = if smooth_time < min_time {
+            min_time
+        } else {
+            smooth_time
+        };
+        let omega = T::from_f64(2.0) / smooth_time;
+
+        // A cheap approximation of e^(-omega * dt)
+        let x = omega * dt;
+        let decay =
+            T::ONE / (T::ONE + x + T::from_f64(0.48) * x * x + T::from_f64(0.235) * x * x * x);
+
+        let mut change = current - target;
+        if let Some(max_speed) = max_speed {
+            let max_change = max_speed * smooth_time;
+            let len_sq = change.length_squared();
+            if len_sq > max_change * max_change {
+                change = change / len_sq.sqrt() * max_change;
+            }
+        }
+        let clamped_target = current - change;
+
+        let temp = (*velocity + change * omega) * dt;
+        *velocity = (*velocity - temp * omega) * decay;
+        let mut output = clamped_target + (change + temp) * decay;
+
+        // Stop exactly on the target rather than overshooting it
+        if Self::dot(target - current, output - target) > T::ZERO {
+            output = target;
+            *velocity = Self::new(T::ZERO, T::ZERO);
+        }
+        output
+    }
+}
+
+/// A damped spring pulling a value towards a target.
+///
+/// The spring is simulated with the exact solution of its motion over each
+/// time step, so it is stable for any step size and gives the same results
+/// regardless of how time is divided into steps.
+///
+/// # Example
+/// ```
+/// use vector2d::smoothing::SpringDamper;
+/// use vector2d::Vector2D;
+///
+/// let spring = SpringDamper::critically_damped(2.0f64);
+/// let target = Vector2D::new(10.0, 0.0);
+/// let mut position = Vector2D::new(0.0, 0.0);
+/// let mut velocity = Vector2D::new(0.0, 0.0);
+/// for _ in 0..120 {
+///     position = spring.update(position, &mut velocity, target, 1.0 / 60.0);
+/// }
+/// assert!((position - target).length() < 1e-3);
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct SpringDamper<T> {
+    /// The natural frequency of the spring, in oscillations per second.
+    pub frequency: T,
+    /// How quickly oscillations die out. At 0 the spring bounces forever, below
+    /// 1 it overshoots the target, at 1 (critical damping) it settles as
+    /// quickly as possible without overshooting, and above 1 it settles slowly.
+    pub damping_ratio: T,
+}
+
+impl<T: Float> SpringDamper<T> {
+    /// Create a new `SpringDamper` with the provided frequency, in oscillations
+    /// per second, and damping ratio.
+    pub fn new(frequency: T, damping_ratio: T) -> Self {
+        Self {
+            frequency,
+            damping_ratio,
+        }
+    }
+
+    /// Create a spring that settles as quickly as possible without
+    /// overshooting.
+    pub fn critically_damped(frequency: T) -> Self {
+        Self::new(frequency, T::ONE)
+    }
+
+    /// Advance the spring by `dt`, returning the new position and updating
+    /// `velocity`.
+    pub fn update(
+        &self,
+        position: Vector2D<T>,
+        velocity: &mut Vector2D<T>,
+        target: Vector2D<T>,
+        dt: T,
+    ) -> Vector2D<T> {
+        let [pos_pos, pos_vel, vel_pos, vel_vel] = self.coefficients(dt);
+        let offset = position - target;
+        let new_position = offset * pos_pos + *velocity * pos_vel + target;
+        *velocity = offset * vel_pos + *velocity * vel_vel;
+        new_position
+    }
+
+    /// Get the coefficients mapping the current offset from the target and
+    /// velocity to their values after `dt`, from the closed form solution of a
+    /// damped harmonic oscillator.
+    fn coefficients(&self, dt: T) -> [T; 4] {
+        let epsilon = T::from_f64(1e-4);
+        let omega = T::TAU * self.frequency;
+        let zeta = if self.damping_ratio < T::ZERO {
+            T::ZERO
+        } else {
+            self.damping_ratio
+        };
+
+        if omega < epsilon {
+            return [T::ONE, T::ZERO, T::ZERO, T::ONE];
+        }
+
+        if zeta > T::ONE + epsilon {
+            // Over-damped
+            let za = -omega * zeta;
+            let zb = omega * (zeta * zeta - T::ONE).sqrt();
+            let z1 = za - zb;
+            let z2 = za + zb;
+            let e1 = (z1 * dt).exp();
+            let e2 = (z2 * dt).exp();
+            let inv_two_zb = T::ONE / (zb + zb);
+            let e1_over_two_zb = e1 * inv_two_zb;
+            let e2_over_two_zb = e2 * inv_two_zb;
+            let z1e1_over_two_zb = z1 * e1_over_two_zb;
+            let z2e2_over_two_zb = z2 * e2_over_two_zb;
+            [
+                e1_over_two_zb * z2 - z2e2_over_two_zb + e2,
+                -e1_over_two_zb + e2_over_two_zb,
+                (z1e1_over_two_zb - z2e2_over_two_zb + e2) * z2,
+                -z1e1_over_two_zb + z2e2_over_two_zb,
+            ]
+        } else if zeta < T::ONE - epsilon {
+            // Under-damped
+            let omega_zeta = omega * zeta;
+            let alpha = omega * (T::ONE - zeta * zeta).sqrt();
+            let exp_term = (-omega_zeta * dt).exp();
+            let cos_term = (alpha * dt).cos();
+            let sin_term = (alpha * dt).sin();
+            let exp_sin = exp_term * sin_term;
+            let exp_cos = exp_term * cos_term;
+            let exp_omega_zeta_sin_over_alpha = exp_term * omega_zeta * sin_term / alpha;
+            [
+                exp_cos + exp_omega_zeta_sin_over_alpha,
+                exp_sin / alpha,
+                -exp_sin * alpha - omega_zeta * exp_omega_zeta_sin_over_alpha,
+                exp_cos - exp_omega_zeta_sin_over_alpha,
+            ]
+        } else {
+            // Critically damped
+            let exp_term = (-omega * dt).exp();
+            let time_exp = dt * exp_term;
+            let time_exp_freq = time_exp * omega;
+            [
+                time_exp_freq + exp_term,
+                time_exp,
+                -omega * time_exp_freq,
+                -time_exp_freq + exp_term,
+            ]
+        }
+    }
+}
@@ -0,0 +1,394 @@
+//! Vectors tagged with the coordinate space they belong to.
+//!
+//! A [`TypedVector2D<T, S>`] behaves like a [`Vector2D<T>`], but carries a
+//! zero-sized marker type `S` naming its coordinate space. Vectors from
+//! different spaces cannot be added or subtracted from one-another, and moving
+//! between spaces is done explicitly with a [`SpaceTransform`].
+//!
+//! # Example
+//! ```
+//! use vector2d::space::{SpaceTransform, TypedVector2D};
+//! use vector2d::Vector2D;
+//!
+//! // Spaces are usually empty enums, as they are never instantiated
+//! enum Screen {}
+//! enum World {}
+//!
+//! let cursor: TypedVector2D<f64, Screen> = TypedVector2D::new(400.0, 300.0);
+//!
+//! // A camera at (100, 50), zoomed in by a factor of 2
+//! let screen_to_world: SpaceTransform<f64, Screen, World> =
+//!     SpaceTransform::new(Vector2D::new(0.5, 0.5), Vector2D::new(100.0, 50.0));
+//!
+//! let world_pos = screen_to_world.transform(cursor);
+//! assert_eq!(TypedVector2D::new(300.0, 200.0), world_pos);
+//! assert_eq!(cursor, screen_to_world.inverse().transform(world_pos));
+//!
+//! // Plain vectors can be tagged and untagged freely
+//! let untyped: Vector2D<f64> = world_pos.to_untyped();
+//! assert_eq!(world_pos, TypedVector2D::from_untyped(untyped));
+//! ```
+//!
+//! Mixing spaces is a compile-time error:
+//! ```compile_fail
+//! use vector2d::space::TypedVector2D;
+//!
+//! enum Screen {}
+//! enum World {}
+//!
+//! let a: TypedVector2D<f64, Screen> = TypedVector2D::new(1.0, 2.0);
+//! let b: TypedVector2D<f64, World> = TypedVector2D::new(3.0, 4.0);
+//! let _ = a + b;
+//! ```
+//!
+//! [`TypedVector2D<T, S>`]: struct.TypedVector2D.html
+//! [`Vector2D<T>`]: ../struct.Vector2D.html
+//! [`SpaceTransform`]: struct.SpaceTransform.html
+
+use crate::Vector2D;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::marker::PhantomData;
+use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+
+/// A 2D vector in the coordinate space `S`. See the [module documentation] for
+/// details.
+///
+/// The space marker is never instantiated, so any type can be used, including
+/// empty enums.
+///
+/// [module documentation]: index.html
+pub struct TypedVector2D<T, S> {
+    pub x: T,
+    pub y: T,
+    space: PhantomData<fn() -> S>,
+}
+
+impl<T, S> TypedVector2D<T, S> {
+    /// Create a new `TypedVector2D` with the provided components.
+    pub fn new(x: T, y: T) -> Self {
+        Self {
+            x,
+            y,
+            space: PhantomData,
+        }
+    }
+
+    /// Tag a plain `Vector2D` with the space `S`.
+    pub fn from_untyped(src: Vector2D<T>) -> Self {
+        Self::new(src.x, src.y)
+    }
+
+    /// Strip the space tag, returning a plain `Vector2D`.
+    pub fn to_untyped(self) -> Vector2D<T> {
+        Vector2D {
+            x: self.x,
+            y: self.y,
+        }
+    }
+
+    /// Reinterpret this vector as belonging to the space `S2` without changing
+    /// its components. Prefer a [`SpaceTransform`] where one applies.
+    ///
+    /// [`SpaceTransform`]: struct.SpaceTransform.html
+    pub fn cast_space<S2>(self) -> TypedVector2D<T, S2> {
+        TypedVector2D::new(self.x, self.y)
+    }
+}
+
+impl<T, U, V, S> TypedVector2D<T, S>
+where
+    T: Mul<T, Output = U> + Copy + Clone,
+    U: Add<U, Output = V> + Copy + Clone,
+{
+    /// Get the scalar/dot product of the two `TypedVector2D`.
+    pub fn dot(v1: Self, v2: Self) -> V {
+        v1.x * v2.x + v1.y * v2.y
+    }
+
+    /// Get the squared length of a `TypedVector2D`.
+    pub fn length_squared(self) -> V {
+        self.x * self.x + self.y * self.y
+    }
+}
+
+impl<S> TypedVector2D<f32, S> {
+    /// Get the length of the vector.
+    pub fn length(self) -> f32 {
+        self.to_untyped().length()
+    }
+
+    /// Get a new vector with the same direction as this vector, but with a length
+    /// of 1.0. If the the length of the vector is 0, then the original vector is
+    /// returned.
+    pub fn normalise(self) -> Self {
+        Self::from_untyped(self.to_untyped().normalise())
+    }
+}
+
+impl<S> TypedVector2D<f64, S> {
+    /// Get the length of the vector.
+    pub fn length(self) -> f64 {
+        self.to_untyped().length()
+    }
+
+    /// Get a new vector with the same direction as this vector, but with a length
+    /// of 1.0. If the the length of the vector is 0, then the original vector is
+    /// returned.
+    pub fn normalise(self) -> Self {
+        Self::from_untyped(self.to_untyped().normalise())
+    }
+}
+
+/// A scale followed by a translation, mapping vectors from the space `Src` to
+/// the space `Dst`. This covers the usual screen, world, and tile conversions
+/// of a panning and zooming camera or a tile grid.
+///
+/// Positions are transformed with [`transform()`], which applies both the scale
+/// and the offset, while displacements are transformed with
+/// [`transform_vector()`], which only applies the scale.
+///
+/// [`transform()`]: struct.SpaceTransform.html#method.transform
+/// [`transform_vector()`]: struct.SpaceTransform.html#method.transform_vector
+pub struct SpaceTransform<T, Src, Dst> {
+    pub scale: Vector2D<T>,
+    pub offset: Vector2D<T>,
+    spaces: PhantomData<fn(Src) -> Dst>,
+}
+
+impl<T, Src, Dst> SpaceTransform<T, Src, Dst> {
+    /// Create a transform that multiplies by `scale` component-wise, then adds
+    /// `offset`.
+    pub fn new(scale: Vector2D<T>, offset: Vector2D<T>) -> Self {
+        Self {
+            scale,
+            offset,
+            spaces: PhantomData,
+        }
+    }
+}
+
+impl<T, Src, Dst> SpaceTransform<T, Src, Dst>
+where
+    T: Add<T, Output = T> + Mul<T, Output = T> + Copy + Clone,
+{
+    /// Transform a position from `Src` to `Dst`.
+    pub fn transform(&self, v: TypedVector2D<T, Src>) -> TypedVector2D<T, Dst> {
+        TypedVector2D::new(
+            v.x * self.scale.x + self.offset.x,
+            v.y * self.scale.y + self.offset.y,
+        )
+    }
+
+    /// Transform a displacement from `Src` to `Dst`, ignoring the offset.
+    pub fn transform_vector(&self, v: TypedVector2D<T, Src>) -> TypedVector2D<T, Dst> {
+        TypedVector2D::new(v.x * self.scale.x, v.y * self.scale.y)
+    }
+
+    /// Combine this transform with one that follows it, producing a single
+    /// transform from `Src` to `Dst2`.
+    pub fn then<Dst2>(&self, next: &SpaceTransform<T, Dst, Dst2>) -> SpaceTransform<T, Src, Dst2> {
+        SpaceTransform::new(
+            self.scale.mul_components(next.scale),
+            self.offset.mul_components(next.scale) + next.offset,
+        )
+    }
+}
+
+impl<T, Src, Dst> SpaceTransform<T, Src, Dst>
+where
+    T: Default + PartialEq + Copy + Clone,
+{
+    /// Check that neither scale component is zero, so that the transform can be
+    /// inverted.
+    pub fn is_invertible(&self) -> bool {
+        self.scale.x != T::default() && self.scale.y != T::default()
+    }
+}
+
+impl<T, Src, Dst> SpaceTransform<T, Src, Dst>
+where
+    T: Div<T, Output = T> + Neg<Output = T> + Copy + Clone + From<u8>,
+{
+    /// Get the transform mapping from `Dst` back to `Src`. The result is
+    /// meaningless if either scale component is zero.
+    pub fn inverse(&self) -> SpaceTransform<T, Dst, Src> {
+        let one = T::from(1u8);
+        SpaceTransform::new(
+            Vector2D::new(one / self.scale.x, one / self.scale.y),
+            Vector2D::new(-self.offset.x / self.scale.x, -self.offset.y / self.scale.y),
+        )
+    }
+}
+
+impl<T: Copy, Src, Dst> Clone for SpaceTransform<T, Src, Dst> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T: Copy, Src, Dst> Copy for SpaceTransform<T, Src, Dst> {}
+
+impl<T: fmt::Debug, Src, Dst> fmt::Debug for SpaceTransform<T, Src, Dst> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("SpaceTransform")
+            .field("scale", &self.scale)
+            .field("offset", &self.offset)
+            .finish()
+    }
+}
+
+impl<T: PartialEq, Src, Dst> PartialEq for SpaceTransform<T, Src, Dst> {
+    fn eq(&self, other: &Self) -> bool {
+        self.scale == other.scale && self.offset == other.offset
+    }
+}
+
+// Derivable Trait Implementations
+//
+// These are implemented by hand so that they don't require the same traits of
+// the space marker.
+
+impl<T: Copy, S> Clone for TypedVector2D<T, S> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T: Copy, S> Copy for TypedVector2D<T, S> {}
+
+impl<T: fmt::Debug, S> fmt::Debug for TypedVector2D<T, S> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("TypedVector2D")
+            .field("x", &self.x)
+            .field("y", &self.y)
+            .finish()
+    }
+}
+
+impl<T: Default, S> Default for TypedVector2D<T, S> {
+    fn default() -> Self {
+        Self::new(T::default(), T::default())
+    }
+}
+
+impl<T: PartialEq, S> PartialEq for TypedVector2D<T, S> {
+    fn eq(&self, other: &Self) -> bool {
+        self.x == other.x && self.y == other.y
+    }
+}
+
+impl<T: Eq, S> Eq for TypedVector2D<T, S> {}
+
+impl<T: Hash, S> Hash for TypedVector2D<T, S> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.x.hash(state);
+        self.y.hash(state);
+    }
+}
+
+// From/Into Implementations
+
+impl<T, S> From<Vector2D<T>> for TypedVector2D<T, S> {
+    fn from(src: Vector2D<T>) -> Self {
+        Self::from_untyped(src)
+    }
+}
+
+impl<T, S> From<TypedVector2D<T, S>> for Vector2D<T> {
+    fn from(src: TypedVector2D<T, S>) -> Self {
+        src.to_untyped()
+    }
+}
+
+// Ops Implementations
+
+impl<T, O, S> Add<TypedVector2D<T, S>> for TypedVector2D<T, S>
+where
+    T: Add<T, Output = O>,
+{
+    type Output = TypedVector2D<O, S>;
+    fn add(self, rhs: TypedVector2D<T, S>) -> Self::Output {
+        TypedVector2D::new(self.x + rhs.x, self.y + rhs.y)
+    }
+}
+
+impl<T, S> AddAssign<TypedVector2D<T, S>> for TypedVector2D<T, S>
+where
+    T: Add<T, Output = T> + Copy + Clone,
+{
+    fn add_assign(&mut self, rhs: TypedVector2D<T, S>) {
+        self.x = self.x + rhs.x;
+        self.y = self.y + rhs.y;
+    }
+}
+
+impl<T, O, S> Sub<TypedVector2D<T, S>> for TypedVector2D<T, S>
+where
+    T: Sub<T, Output = O>,
+{
+    type Output = TypedVector2D<O, S>;
+    fn sub(self, rhs: TypedVector2D<T, S>) -> Self::Output {
+        TypedVector2D::new(self.x - rhs.x, self.y - rhs.y)
+    }
+}
+
+impl<T, S> SubAssign<TypedVector2D<T, S>> for TypedVector2D<T, S>
+where
+    T: Sub<T, Output = T> + Copy + Clone,
+{
+    fn sub_assign(&mut self, rhs: TypedVector2D<T, S>) {
+        self.x = self.x - rhs.x;
+        self.y = self.y - rhs.y;
+    }
+}
+
+impl<T, O, S> Mul<T> for TypedVector2D<T, S>
+where
+    T: Mul<T, Output = O> + Copy + Clone,
+{
+    type Output = TypedVector2D<O, S>;
+    fn mul(self, rhs: T) -> Self::Output {
+        TypedVector2D::new(self.x * rhs, self.y * rhs)
+    }
+}
+
+impl<T, S> MulAssign<T> for TypedVector2D<T, S>
+where
+    T: Mul<T, Output = T> + Copy + Clone,
+{
+    fn mul_assign(&mut self, rhs: T) {
+        self.x = self.x * rhs;
+        self.y = self.y * rhs;
+    }
+}
+
+impl<T, O, S> Div<T> for TypedVector2D<T, S>
+where
+    T: Div<T, Output = O> + Copy + Clone,
+{
+    type Output = TypedVector2D<O, S>;
+    fn div(self, rhs: T) -> Self::Output {
+        TypedVector2D::new(self.x / rhs, self.y / rhs)
+    }
+}
+
+impl<T, S> DivAssign<T> for TypedVector2D<T, S>
+where
+    T: Div<T, Output = T> + Copy + Clone,
+{
+    fn div_assign(&mut self, rhs: T) {
+        self.x = self.x / rhs;
+        self.y = self.y / rhs;
+    }
+}
+
+impl<T, U, S> Neg for TypedVector2D<T, S>
+where
+    T: Neg<Output = U>,
+{
+    type Output = TypedVector2D<U, S>;
+    fn neg(self) -> Self::Output {
+        TypedVector2D::new(-self.x, -self.y)
+    }
+}
@@ -0,0 +1,397 @@
+//! Smooth paths through sequences of `Vector2D` waypoints.
+//!
+//! Each spline type describes a path as a sequence of cubic segments, and can
+//! be converted into [`Bezier`] curves with `to_beziers()`. For sampling, a
+//! spline is converted into a [`SplinePath`] with `to_path()`, which caches the
+//! data needed to travel along the path at a constant speed.
+//!
+//! # Example
+//! ```
+//! use vector2d::spline::{CatmullRom, Parameterisation};
+//! use vector2d::Vector2D;
+//!
+//! let waypoints = vec![
+//!     Vector2D::new(0.0f64, 0.0),
+//!     Vector2D::new(10.0, 0.0),
+//!     Vector2D::new(10.0, 10.0),
+//!     Vector2D::new(0.0, 10.0),
+//! ];
+//! let rail = CatmullRom::new(waypoints, Parameterisation::Centripetal, true)
+//!     .to_path()
+//!     .unwrap();
+//!
+//! // Closed paths return to where they started
+//! assert_eq!(rail.point_at(0.0), rail.point_at(1.0));
+//!
+//! // Evenly spaced samples, for a camera moving at a constant speed
+//! let samples = rail.sample_evenly(100);
+//! assert_eq!(100, samples.len());
+//! ```
+//!
+//! [`Bezier`]: ../bezier/struct.Bezier.html
+//! [`SplinePath`]: struct.SplinePath.html
+
+use crate::bezier::{ArcLengthTable, Bezier};
+use crate::collision::unit;
+use crate::float::Float;
+use crate::Vector2D;
+
+/// The number of arc length samples taken per segment of a `SplinePath`.
+const SAMPLES_PER_SEGMENT: usize = 16;
+
+/// How a [`CatmullRom`] spline spaces its knots.
+///
+/// [`CatmullRom`]: struct.CatmullRom.html
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Parameterisation {
+    /// Knots are evenly spaced. This is the classic Catmull-Rom spline, but it
+    /// can overshoot and form loops when waypoints are unevenly spaced.
+    Uniform,
+    /// Knots are spaced by the square root of the distance between waypoints.
+    /// This never forms cusps or self-intersections within a segment, so is
+    /// usually the best choice.
+    Centripetal,
+    /// Knots are spaced by the distance between waypoints.
+    Chordal,
+}
+
+/// A Catmull-Rom spline, which passes through every one of its waypoints.
+///
+/// Open splines extrapolate an extra waypoint beyond each end, so that the
+/// path covers every waypoint from the first to the last.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CatmullRom<T> {
+    pub points: Vec<Vector2D<T>>,
+    pub parameterisation: Parameterisation,
+    pub closed: bool,
+}
+
+impl<T: Float> CatmullRom<T> {
+    /// Create a new `CatmullRom` spline through the provided waypoints. If
+    /// `closed` is true, the path loops back from the last waypoint to the
+    /// first.
+    pub fn new(points: Vec<Vector2D<T>>, parameterisation: Parameterisation, closed: bool) -> Self {
+        Self {
+            points,
+            parameterisation,
+            closed,
+        }
+    }
+
+    /// Get the cubic Bezier segments making up the spline. There is one fewer
+    /// segment than there are waypoints, or the same number if the spline is
+    /// closed. Splines with fewer than two waypoints have no segments.
+    pub fn to_beziers(&self) -> Vec<Bezier<T>> {
+        let n = self.points.len();
+        if n < 2 {
+            return Vec::new();
+        }
+        let two = T::from_f64(2.0);
+        let point = |i: isize| -> Vector2D<T> {
+            if self.closed {
+                self.points[i.rem_euclid(n as isize) as usize]
+            } else if i < 0 {
+                self.points[0] * two - self.points[1]
+            } else if i as usize >= n {
+                self.points[n - 1] * two - self.points[n - 2]
+            } else {
+                self.points[i as usize]
+            }
+        };
+
+        let segments = if self.closed { n } else { n - 1 };
+        (0..segments as isize)
+            .map(|i| {
+                let (p0, p1, p2, p3) = (point(i - 1), point(i), point(i + 1), point(i + 2));
+                let t01 = self.knot_interval(p0, p1);
+                let t12 = self.knot_interval(p1, p2);
+                let t23 = self.knot_interval(p2, p3);
+                let m1 = (p2 - p1) + ((p1 - p0) / t01 - (p2 - p0) / (t01 + t12)) * t12;
+                let m2 = (p2 - p1) + ((p3 - p2) / t23 - (p3 - p1) / (t12 + t23)) * t12;
+                hermite_segment(p1, m1, p2, m2)
+            })
+            .collect()
+    }
+
+    /// Build a [`SplinePath`] for sampling the spline, or `None` if the spline
+    /// is too short to have any segments.
+    ///
+    /// [`SplinePath`]: struct.SplinePath.html
+    pub fn to_path(&self) -> Option<SplinePath<T>> {
+        SplinePath::from_beziers(self.to_beziers())
+    }
+
+    fn knot_interval(&self, a: Vector2D<T>, b: Vector2D<T>) -> T {
+        let interval = match self.parameterisation {
+            Parameterisation::Uniform => T::ONE,
+            Parameterisation::Centripetal => (b - a).length_squared().sqrt().sqrt(),
+            Parameterisation::Chordal => (b - a).length_squared().sqrt(),
+        };
+        // Repeated waypoints would otherwise cause a division by zero
+        if interval == T::ZERO {
+            T::ONE
+        } else {
+            interval
+        }
+    }
+}
+
+/// A cubic Hermite spline, which passes through every one of its waypoints
+/// with the tangent provided for each.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Hermite<T> {
+    pub points: Vec<Vector2D<T>>,
+    pub tangents: Vec<Vector2D<T>>,
+    pub closed: bool,
+}
+
+impl<T: Float> Hermite<T> {
+    /// Create a new `Hermite` spline through the provided waypoints, leaving
+    /// each with the corresponding tangent. If `closed` is true, the path loops
+    /// back from the last waypoint to the first.
+    ///
+    /// # Panics
+    /// Panics if there is not exactly one tangent per waypoint.
+    pub fn new(points: Vec<Vector2D<T>>, tangents: Vec<Vector2D<T>>, closed: bool) -> Self {
+        assert_eq!(
+            points.len(),
+            tangents.len(),
+            "a Hermite spline needs one tangent per waypoint"
+        );
+        Self {
+            points,
+            tangents,
+            closed,
+        }
+    }
+
+    /// Get the cubic Bezier segments making up the spline. There is one fewer
+    /// segment than there are waypoints, or the same number if the spline is
+    /// closed. Splines with fewer than two waypoints have no segments.
+    pub fn to_beziers(&self) -> Vec<Bezier<T>> {
+        let n = self.points.len();
+        if n < 2 {
+            return Vec::new();
+        }
+        let segments = if self.closed { n } else { n - 1 };
+        (0..segments)
+            .map(|i| {
+                let j = (i + 1) % n;
+                hermite_segment(
+                    self.points[i],
+                    self.tangents[i],
+                    self.points[j],
+                    self.tangents[j],
+                )
+            })
+            .collect()
+    }
+
+    /// Build a [`SplinePath`] for sampling the spline, or `None` if the spline
+    /// is too short to have any segments.
+    ///
+    /// [`SplinePath`]: struct.SplinePath.html
+    pub fn to_path(&self) -> Option<SplinePath<T>> {
+        SplinePath::from_beziers(self.to_beziers())
+    }
+}
+
+/// A uniform cubic B-spline. The path is pulled towards its control points
+/// rather than passing through them, giving second-order continuity.
+///
+/// Open B-splines begin near the second control point and end near the second
+/// last. Repeating the first and last control points three times makes the
+/// path begin and end exactly on them.
+#[derive(Clone, Debug, PartialEq)]
+pub struct BSpline<T> {
+    pub points: Vec<Vector2D<T>>,
+    pub closed: bool,
+}
+
+impl<T: Float> BSpline<T> {
+    /// Create a new `BSpline` from the provided control points. If `closed` is
+    /// true, the path loops back around to the beginning.
+    pub fn new(points: Vec<Vector2D<T>>, closed: bool) -> Self {
+        Self { points, closed }
+    }
+
+    /// Get the cubic Bezier segments making up the spline. Open splines have
+    /// three fewer segments than control points, and closed splines have one
+    /// per control point. Splines that are too short have no segments.
+    pub fn to_beziers(&self) -> Vec<Bezier<T>> {
+        let n = self.points.len();
+        let segments = if self.closed {
+            if n < 3 {
+                return Vec::new();
+            }
+            n
+        } else {
+            if n < 4 {
+                return Vec::new();
+            }
+            n - 3
+        };
+        let (two, four, six) = (T::from_f64(2.0), T::from_f64(4.0), T::from_f64(6.0));
+        let three = T::from_f64(3.0);
+        (0..segments)
+            .map(|i| {
+                let p0 = self.points[i];
+                let p1 = self.points[(i + 1) % n];
+                let p2 = self.points[(i + 2) % n];
+                let p3 = self.points[(i + 3) % n];
+                Bezier::cubic(
+                    (p0 + p1 * four + p2) / six,
+                    (p1 * two + p2) / three,
+                    (p1 + p2 * two) / three,
+                    (p1 + p2 * four + p3) / six,
+                )
+            })
+            .collect()
+    }
+
+    /// Build a [`SplinePath`] for sampling the spline, or `None` if the spline
+    /// is too short to have any segments.
+    ///
+    /// [`SplinePath`]: struct.SplinePath.html
+    pub fn to_path(&self) -> Option<SplinePath<T>> {
+        SplinePath::from_beziers(self.to_beziers())
+    }
+}
+
+/// A path made of consecutive Bezier segments, prepared for sampling.
+///
+/// Positions along the path can be given either as a parameter `u` from 0 at
+/// the start to 1 at the end, with each segment taking an equal share, or as a
+/// distance travelled along the path. Sampling by distance moves at a constant
+/// speed regardless of how the segments are shaped.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SplinePath<T> {
+    segments: Vec<Bezier<T>>,
+    tables: Vec<ArcLengthTable<T>>,
+    offsets: Vec<T>,
+}
+
+impl<T: Float> SplinePath<T> {
+    /// Create a path from consecutive segments, each of which should begin
+    /// where the previous one ends.
+    ///
+    /// # Panics
+    /// Panics if `segments` is empty.
+    pub fn new(segments: Vec<Bezier<T>>) -> Self {
+        assert!(!segments.is_empty(), "a path needs at least one segment");
+        let tables: Vec<ArcLengthTable<T>> = segments
+            .iter()
+            .map(|s| s.arc_length_table(SAMPLES_PER_SEGMENT))
+            .collect();
+        let mut offsets = Vec::with_capacity(tables.len() + 1);
+        let mut total = T::ZERO;
+        offsets.push(total);
+        for table in &tables {
+            total += table.length();
+            offsets.push(total);
+        }
+        Self {
+            segments,
+            tables,
+            offsets,
+        }
+    }
+
+    /// Create a path from consecutive segments, or `None` if there are none.
+    pub(crate) fn from_beziers(segments: Vec<Bezier<T>>) -> Option<Self> {
+        if segments.is_empty() {
+            None
+        } else {
+            Some(Self::new(segments))
+        }
+    }
+
+    /// Get the segments making up the path.
+    pub fn segments(&self) -> &[Bezier<T>] {
+        &self.segments
+    }
+
+    /// Get the total length of the path.
+    pub fn length(&self) -> T {
+        self.offsets[self.offsets.len() - 1]
+    }
+
+    /// Get the point on the path at the parameter `u`, clamped to `[0, 1]`.
+    pub fn point_at(&self, u: T) -> Vector2D<T> {
+        let (segment, t) = self.locate(u);
+        self.segments[segment].point_at(t)
+    }
+
+    /// Get the direction of travel at the parameter `u`, clamped to `[0, 1]`,
+    /// as a vector of length 1.0. This is zero where the path has stalled.
+    pub fn tangent_at(&self, u: T) -> Vector2D<T> {
+        let (segment, t) = self.locate(u);
+        unit(self.segments[segment].velocity_at(t))
+    }
+
+    /// Get the point reached after travelling the provided distance along the
+    /// path. Distances are clamped to the length of the path.
+    pub fn point_at_length(&self, distance: T) -> Vector2D<T> {
+        let (segment, t) = self.locate_length(distance);
+        self.segments[segment].point_at(t)
+    }
+
+    /// Get the direction of travel after travelling the provided distance along
+    /// the path, as a vector of length 1.0.
+    pub fn tangent_at_length(&self, distance: T) -> Vector2D<T> {
+        let (segment, t) = self.locate_length(distance);
+        unit(self.segments[segment].velocity_at(t))
+    }
+
+    /// Get `count` points spaced evenly along the path, including both ends.
+    pub fn sample_evenly(&self, count: usize) -> Vec<Vector2D<T>> {
+        match count {
+            0 => Vec::new(),
+            1 => vec![self.point_at(T::ZERO)],
+            _ => {
+                let step = self.length() / T::from_f64((count - 1) as f64);
+                (0..count)
+                    .map(|i| self.point_at_length(step * T::from_f64(i as f64)))
+                    .collect()
+            }
+        }
+    }
+
+    /// Find the segment and its local parameter for the path parameter `u`.
+    fn locate(&self, u: T) -> (usize, T) {
+        let n = self.segments.len();
+        let scaled = u * T::from_f64(n as f64);
+        if scaled <= T::ZERO {
+            return (0, T::ZERO);
+        }
+        let index = scaled.floor().to_f64() as usize;
+        if index >= n {
+            (n - 1, T::ONE)
+        } else {
+            (index, scaled - T::from_f64(index as f64))
+        }
+    }
+
+    /// Find the segment and its local parameter for a distance along the path.
+    fn locate_length(&self, distance: T) -> (usize, T) {
+        let i = self.offsets.partition_point(|&o| o <= distance);
+        if i == 0 {
+            return (0, T::ZERO);
+        }
+        let segment = (i - 1).min(self.segments.len() - 1);
+        let t = self.tables[segment].t_at_length(distance - self.offsets[segment]);
+        (segment, t)
+    }
+}
+
+/// Get the cubic Bezier equivalent to a Hermite segment from `p0` to `p1` with
+/// the tangents `m0` and `m1`.
+fn hermite_segment<T: Float>(
+    p0: Vector2D<T>,
+    m0: Vector2D<T>,
+    p1: Vector2D<T>,
+    m1: Vector2D<T>,
+) -> Bezier<T> {
+    let three = T::from_f64(3.0);
+    Bezier::cubic(p0, p0 + m0 / three, p1 - m1 / three, p1)
+}
@@ -0,0 +1,396 @@
+//! Steering behaviours for autonomous agents.
+//!
+//! An [`Agent`] moves with a velocity limited to its maximum speed, steered by
+//! forces limited to its maximum force. Each behaviour, following Craig
+//! Reynolds' definitions, returns the steering force that moves the agent
+//! towards some goal, such as seeking a point, avoiding obstacles or keeping
+//! together with a flock. Several behaviours can be combined with
+//! [`Steering`], either by weighting them or by giving some priority over
+//! others.
+//!
+//! # Example
+//! ```
+//! use vector2d::steering::{Agent, Obstacle, Steering};
+//! use vector2d::Vector2D;
+//!
+//! let mut agent = Agent::new(Vector2D::new(0.0f64, 0.0), 5.0, 10.0).with_radius(0.5);
+//! let home = Vector2D::new(20.0, 0.0);
+//! let rocks = [Obstacle::new(Vector2D::new(10.0, 0.5), 2.0)];
+//!
+//! for _ in 0..1200 {
+//!     // Avoiding the rock takes priority over getting home
+//!     let force = Steering::new()
+//!         .with(agent.avoid_obstacles(&rocks, 4.0), 1.0)
+//!         .with(agent.arrive(home, 5.0), 1.0)
+//!         .prioritised(agent.max_force);
+//!     agent.update(force, 1.0 / 60.0);
+//!     assert!((agent.position - rocks[0].centre).length() > 2.5);
+//! }
+//! assert!((agent.position - home).length() < 0.1);
+//! ```
+//!
+//! [`Agent`]: struct.Agent.html
+//! [`Steering`]: struct.Steering.html
+
+use crate::angle::Angle;
+use crate::float::Float;
+use crate::Vector2D;
+
+/// A moving agent, with unit mass.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct Agent<T> {
+    pub position: Vector2D<T>,
+    pub velocity: Vector2D<T>,
+    pub max_speed: T,
+    pub max_force: T,
+    /// The radius of the agent, used to avoid obstacles.
+    pub radius: T,
+}
+
+impl<T: Float> Agent<T> {
+    /// Create an agent at rest, with no radius.
+    pub fn new(position: Vector2D<T>, max_speed: T, max_force: T) -> Self {
+        Self {
+            position,
+            velocity: Vector2D::new(T::ZERO, T::ZERO),
+            max_speed,
+            max_force,
+            radius: T::ZERO,
+        }
+    }
+
+    /// Use the provided radius when avoiding obstacles.
+    pub fn with_radius(mut self, radius: T) -> Self {
+        self.radius = radius;
+        self
+    }
+
+    /// Set the agent moving with the provided velocity.
+    pub fn with_velocity(mut self, velocity: Vector2D<T>) -> Self {
+        self.velocity = velocity;
+        self
+    }
+
+    /// Get the unit direction the agent is moving in, or the positive x axis
+    /// if it is at rest.
+    pub fn heading(&self) -> Vector2D<T> {
+        let speed = self.velocity.length_squared().sqrt();
+        if speed > T::ZERO {
+            self.velocity / speed
+        } else {
+            Vector2D::new(T::ONE, T::ZERO)
+        }
+    }
+
+    /// Apply a steering force for `dt`, limited to the maximum force, then move
+    /// the agent with its new velocity, limited to the maximum speed.
+    pub fn update(&mut self, force: Vector2D<T>, dt: T) {
+        let force = truncate(force, self.max_force);
+        self.velocity = truncate(self.velocity + force * dt, self.max_speed);
+        self.position += self.velocity * dt;
+    }
+
+    /// Steer towards `target` at full speed.
+    pub fn seek(&self, target: Vector2D<T>) -> Vector2D<T> {
+        self.steer_towards(target - self.position, self.max_speed)
+    }
+
+    /// Steer away from `target` at full speed.
+    pub fn flee(&self, target: Vector2D<T>) -> Vector2D<T> {
+        self.steer_towards(self.position - target, self.max_speed)
+    }
+
+    /// Steer towards `target`, slowing down within `slowing_radius` of it to
+    /// stop on it.
+    pub fn arrive(&self, target: Vector2D<T>, slowing_radius: T) -> Vector2D<T> {
+        let offset = target - self.position;
+        let distance = offset.length_squared().sqrt();
+        let speed = if distance < slowing_radius {
+            self.max_speed * distance / slowing_radius
+        } else {
+            self.max_speed
+        };
+        self.steer_towards(offset, speed)
+    }
+
+    /// Steer towards where a target moving with `target_velocity` will be when
+    /// the agent reaches it.
+    pub fn pursue(&self, target: Vector2D<T>, target_velocity: Vector2D<T>) -> Vector2D<T> {
+        self.seek(self.predict(target, target_velocity))
+    }
+
+    /// Steer away from where a target moving with `target_velocity` will be
+    /// when it reaches the agent.
+    pub fn evade(&self, target: Vector2D<T>, target_velocity: Vector2D<T>) -> Vector2D<T> {
+        self.flee(self.predict(target, target_velocity))
+    }
+
+    /// Steer around the nearest obstacle in the agent's way, up to
+    /// `look_ahead` in front of it. Returns a zero force if the way is clear.
+    pub fn avoid_obstacles(&self, obstacles: &[Obstacle<T>], look_ahead: T) -> Vector2D<T> {
+        let heading = self.heading();
+        let side = Vector2D::new(-heading.y, heading.x);
+
+        // Find the nearest obstacle overlapping the agent's path, measuring
+        // how far ahead and to the left of the agent it is
+        let mut nearest: Option<(T, T, T)> = None;
+        for obstacle in obstacles {
+            let offset = obstacle.centre - self.position;
+            let ahead = Vector2D::dot(offset, heading);
+            let left = Vector2D::dot(offset, side);
+            let clearance = obstacle.radius + self.radius;
+            if ahead <= T::ZERO || ahead > look_ahead + clearance || left.abs() >= clearance {
+                continue;
+            }
+            match nearest {
+                Some((best, _, _)) if best <= ahead => {}
+                _ => nearest = Some((ahead, left, clearance)),
+            }
+        }
+
+        match nearest {
+            Some((ahead, left, clearance)) => {
+                // Turn away from the obstacle's side, harder the closer it is
+                let away = if left > T::ZERO { -side } else { side };
+                let urgency = T::ONE - ahead / (look_ahead + clearance);
+                let overlap = (clearance - left.abs()) / clearance;
+                away * (self.max_force * (urgency + overlap) / T::from_f64(2.0))
+            }
+            None => Vector2D::new(T::ZERO, T::ZERO),
+        }
+    }
+
+    /// Follow a path through `waypoints`, steering towards the point
+    /// `look_ahead` further along the path than the nearest point to the agent,
+    /// and arriving at the final waypoint. Returns a zero force for an empty
+    /// path.
+    pub fn follow_path(&self, waypoints: &[Vector2D<T>], look_ahead: T) -> Vector2D<T> {
+        let last = match waypoints.last() {
+            Some(&last) => last,
+            None => return Vector2D::new(T::ZERO, T::ZERO),
+        };
+
+        // Find how far along the path the nearest point to the agent is
+        let mut best = (T::ZERO, None);
+        let mut travelled = T::ZERO;
+        for pair in waypoints.windows(2) {
+            let segment = pair[1] - pair[0];
+            let length_squared = segment.length_squared();
+            let t = if length_squared > T::ZERO {
+                let t = Vector2D::dot(self.position - pair[0], segment) / length_squared;
+                if t < T::ZERO {
+                    T::ZERO
+                } else if t > T::ONE {
+                    T::ONE
+                } else {
+                    t
+                }
+            } else {
+                T::ZERO
+            };
+            let distance = (pair[0] + segment * t - self.position).length_squared();
+            let length = length_squared.sqrt();
+            match best.1 {
+                Some(nearest) if nearest <= distance => {}
+                _ => best = (travelled + length * t, Some(distance)),
+            }
+            travelled += length;
+        }
+
+        // Walk along the path to the target point
+        let mut remaining = best.0 + look_ahead;
+        for pair in waypoints.windows(2) {
+            let segment = pair[1] - pair[0];
+            let length = segment.length_squared().sqrt();
+            if remaining < length {
+                return self.seek(pair[0] + segment * (remaining / length));
+            }
+            remaining -= length;
+        }
+        self.arrive(last, look_ahead)
+    }
+
+    /// Steer away from neighbours within `radius`, more strongly from closer
+    /// ones. Neighbours at exactly the agent's position, including the agent
+    /// itself, are ignored.
+    pub fn separation(&self, neighbours: &[Agent<T>], radius: T) -> Vector2D<T> {
+        let mut away = Vector2D::new(T::ZERO, T::ZERO);
+        for n in self.neighbours(neighbours, radius) {
+            let offset = self.position - n.position;
+            away += offset / offset.length_squared();
+        }
+        if away.length_squared() == T::ZERO {
+            return away;
+        }
+        self.steer_towards(away, self.max_speed)
+    }
+
+    /// Steer to match the average velocity of neighbours within `radius`.
+    pub fn alignment(&self, neighbours: &[Agent<T>], radius: T) -> Vector2D<T> {
+        let mut count = T::ZERO;
+        let mut total = Vector2D::new(T::ZERO, T::ZERO);
+        for n in self.neighbours(neighbours, radius) {
+            total += n.velocity;
+            count += T::ONE;
+        }
+        if count == T::ZERO {
+            return total;
+        }
+        truncate(total / count - self.velocity, self.max_force)
+    }
+
+    /// Steer towards the average position of neighbours within `radius`.
+    pub fn cohesion(&self, neighbours: &[Agent<T>], radius: T) -> Vector2D<T> {
+        let mut count = T::ZERO;
+        let mut total = Vector2D::new(T::ZERO, T::ZERO);
+        for n in self.neighbours(neighbours, radius) {
+            total += n.position;
+            count += T::ONE;
+        }
+        if count == T::ZERO {
+            return total;
+        }
+        self.seek(total / count)
+    }
+
+    /// Get the force that turns the agent's velocity into one along `direction`
+    /// at `speed`.
+    fn steer_towards(&self, direction: Vector2D<T>, speed: T) -> Vector2D<T> {
+        let length = direction.length_squared().sqrt();
+        let desired = if length > T::ZERO {
+            direction * (speed / length)
+        } else {
+            direction
+        };
+        truncate(desired - self.velocity, self.max_force)
+    }
+
+    /// Predict where a moving target will be by the time the agent could reach
+    /// it at full speed.
+    fn predict(&self, target: Vector2D<T>, target_velocity: Vector2D<T>) -> Vector2D<T> {
+        let distance = (target - self.position).length_squared().sqrt();
+        target + target_velocity * (distance / self.max_speed)
+    }
+
+    fn neighbours<'a>(
+        &'a self,
+        agents: &'a [Agent<T>],
+        radius: T,
+    ) -> impl Iterator<Item = &'a Agent<T>> + 'a {
+        agents.iter().filter(move |n| {
+            let distance_squared = (n.position - self.position).length_squared();
+            distance_squared > T::ZERO && distance_squared <= radius * radius
+        })
+    }
+}
+
+/// A circular obstacle, for [`Agent::avoid_obstacles()`].
+///
+/// [`Agent::avoid_obstacles()`]: struct.Agent.html#method.avoid_obstacles
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct Obstacle<T> {
+    pub centre: Vector2D<T>,
+    pub radius: T,
+}
+
+impl<T> Obstacle<T> {
+    /// Create a new `Obstacle` with the provided centre and radius.
+    pub fn new(centre: Vector2D<T>, radius: T) -> Self {
+        Self { centre, radius }
+    }
+}
+
+/// Wandering aimlessly, by steering towards a target that drifts around a
+/// circle projected in front of the agent.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct Wander<T> {
+    /// How far in front of the agent the circle is.
+    pub distance: T,
+    /// The radius of the circle. Larger circles make sharper turns.
+    pub radius: T,
+    /// The most the target can move around the circle each update.
+    pub jitter: Angle<T>,
+    angle: Angle<T>,
+}
+
+impl<T: Float> Wander<T> {
+    /// Create a new `Wander` with the provided circle and jitter, with the
+    /// target straight ahead.
+    pub fn new(distance: T, radius: T, jitter: Angle<T>) -> Self {
+        Self {
+            distance,
+            radius,
+            jitter,
+            angle: Angle::zero(),
+        }
+    }
+
+    /// Move the target around the circle by `random`, from -1 to 1, times the
+    /// jitter, and get the force steering the agent towards it. The random
+    /// value is provided by the caller, so that any random number generator can
+    /// be used.
+    pub fn force(&mut self, agent: &Agent<T>, random: T) -> Vector2D<T> {
+        self.angle = (self.angle + self.jitter * random).normalised_signed();
+        let heading = agent.heading();
+        let centre = agent.position + heading * self.distance;
+        let target = centre + heading.rotate(self.angle) * self.radius;
+        agent.seek(target)
+    }
+}
+
+/// Combines steering forces from several behaviours.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Steering<T> {
+    forces: Vec<(Vector2D<T>, T)>,
+}
+
+impl<T: Float> Steering<T> {
+    /// Create an empty combination of forces.
+    pub fn new() -> Self {
+        Self { forces: Vec::new() }
+    }
+
+    /// Add a force with the provided weight. For prioritised blending, forces
+    /// should be added from most to least important.
+    pub fn with(mut self, force: Vector2D<T>, weight: T) -> Self {
+        self.forces.push((force, weight));
+        self
+    }
+
+    /// Add the weighted forces together, limiting the result to `max_force`.
+    pub fn weighted(&self, max_force: T) -> Vector2D<T> {
+        let total = self
+            .forces
+            .iter()
+            .fold(Vector2D::new(T::ZERO, T::ZERO), |sum, &(f, w)| sum + f * w);
+        truncate(total, max_force)
+    }
+
+    /// Add the weighted forces together in order until `max_force` is used up,
+    /// so that more important behaviours, such as avoiding obstacles, are not
+    /// drowned out by less important ones.
+    pub fn prioritised(&self, max_force: T) -> Vector2D<T> {
+        let mut total = Vector2D::new(T::ZERO, T::ZERO);
+        let mut remaining = max_force;
+        for &(force, weight) in &self.forces {
+            let force = force * weight;
+            let magnitude = force.length_squared().sqrt();
+            if magnitude > remaining {
+                return total + force * (remaining / magnitude);
+            }
+            total += force;
+            remaining -= magnitude;
+        }
+        total
+    }
+}
+
+/// Limit the length of a vector to `max`.
+fn truncate<T: Float>(v: Vector2D<T>, max: T) -> Vector2D<T> {
+    let length_squared = v.length_squared();
+    if length_squared > max * max {
+        v * (max / length_squared.sqrt())
+    } else {
+        v
+    }
+}
@@ -1,4 +1,35 @@
-use crate::Vector2D;
+use crate::angle::Angle;
+use crate::ballistics::{ballistic_arcs, intercept};
+use crate::bezier::Bezier;
+use crate::boolean::{clip_convex, difference, intersection, union, xor, Polygon};
+use crate::box2d::Box2D;
+use crate::collision::collide;
+use crate::delaunay::{Delaunay, Voronoi};
+use crate::easing::Easing;
+use crate::flowfield::{CostMap, FlowField, IntegrationField};
+use crate::hull::{convex_hull, convex_hull_with, quickhull, Collinear};
+use crate::integrate::{Integrator, ParticleState};
+use crate::navmesh::NavMesh;
+use crate::offset::{Cap, Join, Offset};
+use crate::pathfinding::{CornerCutting, GridPathfinder, Heuristic, Neighbourhood};
+use crate::physics::{self, RigidBody};
+use crate::point::Point2D;
+use crate::polar::Polar;
+use crate::polyline::{
+    chaikin, ramer_douglas_peucker, resample, resample_by_length, visvalingam_whyatt,
+};
+use crate::predicates::{incircle, orient2d, CirclePosition, Orientation};
+use crate::shape::{Pose, Shape};
+use crate::smoothing::SpringDamper;
+use crate::space::{SpaceTransform, TypedVector2D};
+use crate::spline::{BSpline, CatmullRom, Hermite, Parameterisation};
+use crate::steering::{Agent, Obstacle, Steering, Wander};
+use crate::transform::Transform2D;
+use crate::triangulate::{ear_clipping, monotone};
+use crate::tween::{Animation, Animator, Parallel, Sequence, Tween};
+use crate::verlet::{Constraint, VerletPoint, VerletWorld};
+use crate::visibility::{field_of_view, line_of_sight, visibility_polygon};
+use crate::{Fixed, Vector2D};
 
 #[test]
 fn dot() {
@@ -188,3 +219,2425 @@ fn neg() {
     let v = Vector2D::new(10.3, -5.4);
     assert_eq!(Vector2D::new(-10.3, 5.4), -v);
 }
+
+#[test]
+fn fixed_arithmetic() {
+    let a: Fixed = Fixed::from_f64(2.5);
+    let b: Fixed = Fixed::from_int(-4);
+
+    assert_eq!(Fixed::from_f64(-1.5), a + b);
+    assert_eq!(Fixed::from_f64(6.5), a - b);
+    assert_eq!(Fixed::from_int(-10), a * b);
+    assert_eq!(Fixed::from_f64(-0.625), a / b);
+    assert_eq!(Fixed::from_int(-3), Fixed::<16>::from_f64(-2.5).floor());
+    assert_eq!(Fixed::from_int(-2), Fixed::<16>::from_f64(-2.5).ceil());
+}
+
+#[test]
+fn fixed_sqrt() {
+    let v: Fixed = Fixed::from_int(16);
+    assert_eq!(Fixed::from_int(4), v.sqrt());
+    assert_eq!(Fixed::ZERO, Fixed::<16>::from_int(-1).sqrt());
+
+    // The extremes don't overflow, and lengths too long to represent saturate
+    let min = Vector2D::new(Fixed::<16>::MIN, Fixed::MIN);
+    assert_eq!(Fixed::MAX, min.length());
+    let max = Vector2D::new(Fixed::<16>::MAX, Fixed::MAX);
+    assert_eq!(Fixed::MAX, max.length());
+    let edge = Vector2D::new(Fixed::<16>::MIN, Fixed::ZERO);
+    assert_eq!(Fixed::MAX, edge.length());
+    let axis = Vector2D::new(Fixed::<16>::ZERO, Fixed::MAX);
+    assert_eq!(Fixed::MAX, axis.length());
+}
+
+#[test]
+fn fixed_trig() {
+    let tolerance = 1e-4;
+    for i in -20..=20 {
+        let f = i as f64 * 0.37;
+        let v: Fixed = Fixed::from_f64(f);
+        assert!((v.sin().to_f64() - f.sin()).abs() < tolerance);
+        assert!((v.cos().to_f64() - f.cos()).abs() < tolerance);
+        assert!((v.atan().to_f64() - f.atan()).abs() < tolerance);
+        let w: Fixed = Fixed::from_f64(-f * 0.5 + 0.1);
+        assert!((v.atan2(w).to_f64() - f.atan2(-f * 0.5 + 0.1)).abs() < tolerance);
+    }
+}
+
+#[test]
+fn fixed_vector() {
+    let v: Vector2D<Fixed> = Vector2D::new(Fixed::from_int(3), Fixed::from_int(-4));
+
+    assert_eq!(Fixed::from_int(5), v.length());
+    assert_eq!(Fixed::from_int(25), v.length_squared());
+    let n = v.normalise();
+    assert!((n.x.to_f64() - 0.6).abs() < 1e-4);
+    assert!((n.y.to_f64() + 0.8).abs() < 1e-4);
+    assert!((v.angle().to_f64() - (-4.0f64).atan2(3.0)).abs() < 1e-4);
+    assert_eq!(Vector2D::new(3.0, -4.0), v.into_vec2d::<f64>());
+}
+
+enum Screen {}
+enum World {}
+enum Tile {}
+
+#[test]
+fn typed_vector_ops() {
+    let a: TypedVector2D<f64, World> = TypedVector2D::new(10.0, 5.0);
+    let b: TypedVector2D<f64, World> = Vector2D::new(1.5, 2.0).into();
+
+    assert_eq!(TypedVector2D::new(11.5, 7.0), a + b);
+    assert_eq!(TypedVector2D::new(8.5, 3.0), a - b);
+    assert_eq!(TypedVector2D::new(20.0, 10.0), a * 2.0);
+    assert_eq!(25.0, TypedVector2D::dot(a, b));
+    assert_eq!(Vector2D::new(10.0, 5.0), a.to_untyped());
+}
+
+#[test]
+fn space_transform() {
+    let screen_to_world: SpaceTransform<f64, Screen, World> =
+        SpaceTransform::new(Vector2D::new(0.5, 0.5), Vector2D::new(100.0, 50.0));
+    let world_to_tile: SpaceTransform<f64, World, Tile> = SpaceTransform::new(
+        Vector2D::new(1.0 / 16.0, 1.0 / 16.0),
+        Vector2D::new(0.0, 0.0),
+    );
+
+    let cursor: TypedVector2D<f64, Screen> = TypedVector2D::new(60.0, 28.0);
+    let world = screen_to_world.transform(cursor);
+    assert_eq!(TypedVector2D::new(130.0, 64.0), world);
+    assert_eq!(cursor, screen_to_world.inverse().transform(world));
+    assert_eq!(
+        TypedVector2D::new(30.0, 14.0),
+        screen_to_world.transform_vector(cursor)
+    );
+
+    let screen_to_tile = screen_to_world.then(&world_to_tile);
+    assert_eq!(
+        world_to_tile.transform(world),
+        screen_to_tile.transform(cursor)
+    );
+}
+
+#[test]
+fn point_ops() {
+    let p1 = Point2D::new(10.0, 5.0);
+    let p2 = Point2D::new(1.5, 2.0);
+    let v = Vector2D::new(1.5, 2.0);
+
+    assert_eq!(Vector2D::new(8.5, 3.0), p1 - p2);
+    assert_eq!(Point2D::new(11.5, 7.0), p1 + v);
+    assert_eq!(Point2D::new(8.5, 3.0), p1 - v);
+    assert_eq!(Point2D::new(5.75, 3.5), Point2D::lerp(p1, p2, 0.5));
+    assert_eq!(81.25, p1.distance_squared(p2));
+}
+
+#[test]
+fn point_vector_conversion() {
+    let v = Vector2D::new(3, 4);
+    let p: Point2D<i32> = v.into();
+    assert_eq!(Point2D::new(3, 4), p);
+    assert_eq!(v, Vector2D::from(p));
+    assert_eq!(Point2D::new(3.0, 4.0), p.into_point2d::<f64>());
+}
+
+#[test]
+fn transform_points_and_vectors() {
+    let t = Transform2D::translation(Vector2D::new(5, -2));
+    assert_eq!(Point2D::new(6, -1), t.transform_point(Point2D::new(1, 1)));
+    assert_eq!(Vector2D::new(1, 1), t.transform_vector(Vector2D::new(1, 1)));
+
+    let r = Transform2D::<f64>::rotation(std::f64::consts::FRAC_PI_2);
+    let rotated = r.transform_vector(Vector2D::new(1.0, 0.0));
+    assert!((rotated - Vector2D::new(0.0, 1.0)).length() < 1e-12);
+}
+
+#[test]
+fn transform_inverse() {
+    let t = Transform2D::scale(Vector2D::new(2.0, 4.0))
+        .then(&Transform2D::translation(Vector2D::new(3.0, -1.0)));
+    let inv = t.inverse().unwrap();
+    let p = Point2D::new(7.0, 9.0);
+    assert_eq!(p, inv.transform_point(t.transform_point(p)));
+    assert_eq!(Transform2D::identity(), t.then(&inv));
+
+    let flat = Transform2D::scale(Vector2D::new(1.0, 0.0));
+    assert_eq!(None, flat.inverse());
+}
+
+#[test]
+fn angle_units() {
+    let a = Angle::degrees(180.0);
+    assert_eq!(std::f64::consts::PI, a.to_radians());
+    assert_eq!(180.0, a.to_degrees());
+}
+
+#[test]
+fn angle_normalise() {
+    let a = Angle::degrees(-90.0f64);
+    assert!((a.normalised_positive().to_degrees() - 270.0).abs() < 1e-9);
+    assert!((Angle::degrees(270.0f64).normalised_signed().to_degrees() + 90.0).abs() < 1e-9);
+    assert!((Angle::degrees(-180.0f64).normalised_signed().to_degrees() - 180.0).abs() < 1e-9);
+    assert!((Angle::degrees(720.0f64).normalised_positive().to_degrees()).abs() < 1e-9);
+}
+
+#[test]
+fn angle_shortest_arc_and_lerp() {
+    let a = Angle::degrees(10.0f64);
+    let b = Angle::degrees(350.0f64);
+    assert!((a.shortest_arc_to(b).to_degrees() + 20.0).abs() < 1e-9);
+    let mid = Angle::lerp(a, b, 0.5).normalised_positive();
+    assert!(mid.to_degrees().abs() < 1e-9);
+}
+
+#[test]
+fn vector_angle_integration() {
+    let v = Vector2D::new(0.0f32, 2.0);
+    assert_eq!(v.angle(), v.direction().to_radians());
+
+    let rotated = Vector2D::new(1.0f64, 0.0).rotate(Angle::degrees(90.0));
+    assert!((rotated - Vector2D::new(0.0, 1.0)).length() < 1e-12);
+
+    let between = Vector2D::new(1.0f64, 0.0).angle_to(Vector2D::new(0.0, -1.0));
+    assert!((between.to_degrees() + 90.0).abs() < 1e-9);
+
+    let fv = Vector2D::from_angle(Angle::<Fixed>::degrees(Fixed::from_int(45)));
+    assert!((fv.x.to_f64() - 0.5f64.sqrt()).abs() < 1e-4);
+    assert!((fv.direction().to_degrees().to_f64() - 45.0).abs() < 1e-2);
+}
+
+#[test]
+fn polar_round_trip() {
+    let v = Vector2D::new(-3.0f64, 4.0);
+    let p = v.to_polar();
+    assert_eq!(5.0, p.radius);
+    assert!((p.to_vector() - v).length() < 1e-12);
+    assert_eq!(
+        Polar::new(0.0, Angle::zero()),
+        Vector2D::new(0.0f64, 0.0).to_polar()
+    );
+}
+
+#[test]
+fn polar_normalise() {
+    let p = Polar::new(-2.0f64, Angle::degrees(90.0)).normalised();
+    assert_eq!(2.0, p.radius);
+    assert!((p.angle.to_degrees() + 90.0).abs() < 1e-9);
+}
+
+#[test]
+fn polar_lerp() {
+    let start = Polar::new(1.0f64, Angle::degrees(170.0));
+    let end = Polar::new(3.0f64, Angle::degrees(-170.0));
+    let mid = Polar::lerp(start, end, 0.5);
+    assert_eq!(2.0, mid.radius);
+    assert!((mid.angle.normalised_positive().to_degrees() - 180.0).abs() < 1e-9);
+}
+
+#[test]
+fn box2d() {
+    let a = Box2D::new(Vector2D::new(0, 0), Vector2D::new(4, 4));
+    let b = Box2D::new(Vector2D::new(2, 3), Vector2D::new(6, 8));
+
+    assert!(a.intersects(&b));
+    assert_eq!(
+        Box2D::new(Vector2D::new(0, 0), Vector2D::new(6, 8)),
+        a.union(b)
+    );
+    assert_eq!(
+        Box2D::new(Vector2D::new(2, 3), Vector2D::new(4, 4)),
+        a.intersection(b)
+    );
+    assert_eq!(Vector2D::new(4, 8), a.size() + Vector2D::new(0, 4));
+    assert_eq!(Vector2D::new(2, 2), a.center());
+    assert!(a
+        .intersection(Box2D::new(Vector2D::new(5, 5), Vector2D::new(6, 6)))
+        .is_empty());
+}
+
+#[test]
+fn bezier_evaluate_and_derive() {
+    let curve = Bezier::quadratic(
+        Vector2D::new(0.0f64, 0.0),
+        Vector2D::new(1.0, 2.0),
+        Vector2D::new(2.0, 0.0),
+    );
+    assert_eq!(2, curve.degree());
+    assert_eq!(Vector2D::new(1.0, 1.0), curve.point_at(0.5));
+    assert_eq!(Vector2D::new(2.0, 0.0), curve.velocity_at(0.5));
+    assert_eq!(Vector2D::new(2.0, 4.0), curve.velocity_at(0.0));
+}
+
+#[test]
+fn bezier_split() {
+    let curve = Bezier::new(vec![
+        Vector2D::new(0.0, 0.0),
+        Vector2D::new(1.0, 3.0),
+        Vector2D::new(2.0, -1.0),
+        Vector2D::new(4.0, 2.0),
+        Vector2D::new(5.0, 0.0),
+    ]);
+    let (first, second) = curve.split(0.25);
+    assert_eq!(curve.start(), first.start());
+    assert_eq!(first.end(), second.start());
+    assert_eq!(curve.end(), second.end());
+    for i in 0..=4 {
+        let t = i as f64 / 4.0;
+        assert!((first.point_at(t) - curve.point_at(t * 0.25)).length() < 1e-12);
+        assert!((second.point_at(t) - curve.point_at(0.25 + t * 0.75)).length() < 1e-12);
+    }
+}
+
+#[test]
+fn bezier_bounding_box() {
+    let curve = Bezier::quadratic(
+        Vector2D::new(0.0f64, 0.0),
+        Vector2D::new(1.0, 2.0),
+        Vector2D::new(2.0, 0.0),
+    );
+    let bounds = curve.bounding_box();
+    assert_eq!(Vector2D::new(0.0, 0.0), bounds.min);
+    assert!((bounds.max - Vector2D::new(2.0, 1.0)).length() < 1e-6);
+}
+
+#[test]
+fn bezier_arc_length() {
+    let line = Bezier::linear(Vector2D::new(0.0f64, 0.0), Vector2D::new(3.0, 4.0));
+    assert!((line.length() - 5.0).abs() < 1e-12);
+
+    // A cubic approximation of a quarter circle
+    let k = 0.552_284_749_8;
+    let arc = Bezier::cubic(
+        Vector2D::new(1.0, 0.0),
+        Vector2D::new(1.0, k),
+        Vector2D::new(k, 1.0),
+        Vector2D::new(0.0, 1.0),
+    );
+    assert!((arc.length() - std::f64::consts::FRAC_PI_2).abs() < 1e-3);
+
+    let table = arc.arc_length_table(64);
+    let t = table.t_at_fraction(0.5);
+    assert!((t - 0.5).abs() < 1e-6);
+    assert_eq!(0.0, table.t_at_length(-1.0));
+    assert_eq!(1.0, table.t_at_length(10.0));
+}
+
+#[test]
+fn bezier_nearest_point() {
+    let curve = Bezier::quadratic(
+        Vector2D::new(0.0f64, 0.0),
+        Vector2D::new(1.0, 2.0),
+        Vector2D::new(2.0, 0.0),
+    );
+    assert!((curve.nearest_t(Vector2D::new(1.0, 5.0)) - 0.5).abs() < 1e-6);
+    assert_eq!(1.0, curve.nearest_t(Vector2D::new(10.0, -1.0)));
+    let p = curve.nearest_point(Vector2D::new(0.3, 1.0));
+    let step = 1e-3;
+    let dist = (p - Vector2D::new(0.3, 1.0)).length();
+    for i in 0..=1000 {
+        let q = curve.point_at(i as f64 * step);
+        assert!(dist <= (q - Vector2D::new(0.3, 1.0)).length() + 1e-9);
+    }
+}
+
+fn square_waypoints() -> Vec<Vector2D<f64>> {
+    vec![
+        Vector2D::new(0.0, 0.0),
+        Vector2D::new(10.0, 0.0),
+        Vector2D::new(10.0, 10.0),
+        Vector2D::new(0.0, 10.0),
+    ]
+}
+
+#[test]
+fn catmull_rom_passes_through_waypoints() {
+    for &param in &[
+        Parameterisation::Uniform,
+        Parameterisation::Centripetal,
+        Parameterisation::Chordal,
+    ] {
+        let open = CatmullRom::new(square_waypoints(), param, false);
+        let beziers = open.to_beziers();
+        assert_eq!(3, beziers.len());
+        for (i, b) in beziers.iter().enumerate() {
+            assert_eq!(square_waypoints()[i], b.start());
+            assert_eq!(square_waypoints()[i + 1], b.end());
+        }
+
+        let closed = CatmullRom::new(square_waypoints(), param, true)
+            .to_path()
+            .unwrap();
+        assert_eq!(4, closed.segments().len());
+        assert_eq!(closed.point_at(0.0), closed.point_at(1.0));
+        assert_eq!(Vector2D::new(10.0, 10.0), closed.point_at(0.5));
+    }
+}
+
+#[test]
+fn catmull_rom_repeated_waypoints() {
+    let mut points = square_waypoints();
+    points.insert(1, Vector2D::new(0.0, 0.0));
+    let path = CatmullRom::new(points, Parameterisation::Centripetal, false)
+        .to_path()
+        .unwrap();
+    for p in path.sample_evenly(50) {
+        assert!(!p.x.is_nan() && !p.y.is_nan());
+    }
+}
+
+#[test]
+fn hermite_tangents() {
+    let points = vec![Vector2D::new(0.0, 0.0), Vector2D::new(4.0, 0.0)];
+    let tangents = vec![Vector2D::new(0.0, 6.0), Vector2D::new(0.0, -6.0)];
+    let spline = Hermite::new(points, tangents, false);
+    let beziers = spline.to_beziers();
+    assert_eq!(Vector2D::new(0.0, 6.0), beziers[0].velocity_at(0.0));
+    assert_eq!(Vector2D::new(0.0, -6.0), beziers[0].velocity_at(1.0));
+    assert_eq!(
+        Vector2D::new(0.0, 1.0),
+        spline.to_path().unwrap().tangent_at(0.0)
+    );
+}
+
+#[test]
+fn bspline_segments() {
+    let spline = BSpline::new(square_waypoints(), false);
+    let beziers = spline.to_beziers();
+    assert_eq!(1, beziers.len());
+    assert_eq!(Vector2D::new(50.0 / 6.0, 10.0 / 6.0), beziers[0].start());
+    assert_eq!(4, BSpline::new(square_waypoints(), true).to_beziers().len());
+
+    let closed = BSpline::new(square_waypoints(), true).to_beziers();
+    for i in 0..4 {
+        assert!((closed[i].end() - closed[(i + 1) % 4].start()).length() < 1e-12);
+        let v0 = closed[i].velocity_at(1.0);
+        let v1 = closed[(i + 1) % 4].velocity_at(0.0);
+        assert!((v0 - v1).length() < 1e-12);
+    }
+}
+
+#[test]
+fn spline_too_short_for_path() {
+    let point = vec![Vector2D::new(1.0, 2.0)];
+    assert_eq!(
+        None,
+        CatmullRom::new(point.clone(), Parameterisation::Uniform, false).to_path()
+    );
+    assert_eq!(None, Hermite::new(point.clone(), point, true).to_path());
+    assert_eq!(
+        None,
+        BSpline::new(square_waypoints()[..3].to_vec(), false).to_path()
+    );
+    assert!(BSpline::new(square_waypoints()[..3].to_vec(), true)
+        .to_path()
+        .is_some());
+}
+
+#[test]
+fn spline_path_constant_speed() {
+    let path = CatmullRom::new(square_waypoints(), Parameterisation::Centripetal, false)
+        .to_path()
+        .unwrap();
+    let samples = path.sample_evenly(31);
+    assert_eq!(square_waypoints()[0], samples[0]);
+    assert!((samples[30] - square_waypoints()[3]).length() < 1e-9);
+
+    let step = path.length() / 30.0;
+    for pair in samples.windows(2) {
+        let chord = (pair[1] - pair[0]).length();
+        assert!((chord - step).abs() < step * 0.02);
+    }
+}
+
+#[test]
+fn fixed_exp() {
+    for i in -40..=40 {
+        let f = i as f64 * 0.25;
+        let v: Fixed = Fixed::from_f64(f);
+        let expected = f.exp();
+        assert!((v.exp().to_f64() - expected).abs() < 1e-4 * expected.max(1.0));
+    }
+    assert_eq!(Fixed::MAX, Fixed::<16>::from_int(100).exp());
+    assert_eq!(Fixed::ZERO, Fixed::<16>::from_int(-100).exp());
+}
+
+#[test]
+fn easing_endpoints() {
+    let all = [
+        Easing::Linear,
+        Easing::QuadIn,
+        Easing::QuadOut,
+        Easing::QuadInOut,
+        Easing::CubicIn,
+        Easing::CubicOut,
+        Easing::CubicInOut,
+        Easing::QuartIn,
+        Easing::QuartOut,
+        Easing::QuartInOut,
+        Easing::QuintIn,
+        Easing::QuintOut,
+        Easing::QuintInOut,
+        Easing::SineIn,
+        Easing::SineOut,
+        Easing::SineInOut,
+        Easing::ExpoIn,
+        Easing::ExpoOut,
+        Easing::ExpoInOut,
+        Easing::CircIn,
+        Easing::CircOut,
+        Easing::CircInOut,
+        Easing::BackIn,
+        Easing::BackOut,
+        Easing::BackInOut,
+        Easing::ElasticIn,
+        Easing::ElasticOut,
+        Easing::ElasticInOut,
+        Easing::BounceIn,
+        Easing::BounceOut,
+        Easing::BounceInOut,
+    ];
+    for easing in all.iter() {
+        assert!(easing.apply(0.0f64).abs() < 1e-9, "{:?}", easing);
+        assert!((easing.apply(1.0f64) - 1.0).abs() < 1e-9, "{:?}", easing);
+        assert!(
+            (easing.apply(0.5f64) - (1.0 - easing.apply(0.5f64))).abs() < 1e-2 || {
+                // Only the symmetric InOut curves pass through the midpoint
+                !format!("{:?}", easing).ends_with("InOut")
+            }
+        );
+    }
+}
+
+#[test]
+fn easing_values() {
+    assert_eq!(0.125, Easing::CubicIn.apply(0.5));
+    assert_eq!(0.875, Easing::CubicOut.apply(0.5));
+    assert_eq!(0.5, Easing::QuadInOut.apply(0.5));
+    assert!(Easing::BackIn.apply(0.2f64) < 0.0);
+    assert!(Easing::ElasticOut.apply(0.2f64) > 1.0);
+    assert_eq!(1.0, Easing::BounceOut.apply(2.0));
+}
+
+#[test]
+fn tween_delay_and_repeat() {
+    let tween = Tween::new(Vector2D::new(0.0, 0.0), Vector2D::new(10.0, 20.0), 2.0)
+        .with_delay(1.0)
+        .with_repeats(1);
+    assert_eq!(Some(5.0), tween.duration());
+    assert_eq!(Vector2D::new(0.0, 0.0), tween.sample(0.5));
+    assert_eq!(Vector2D::new(5.0, 10.0), tween.sample(2.0));
+    assert_eq!(Vector2D::new(5.0, 10.0), tween.sample(4.0));
+    assert_eq!(Vector2D::new(10.0, 20.0), tween.sample(100.0));
+
+    let forever = tween.repeat_forever().with_yoyo(true);
+    assert_eq!(None, forever.duration());
+    assert_eq!(Vector2D::new(7.5, 15.0), forever.sample(11.5));
+}
+
+#[test]
+fn tween_composition() {
+    let movement = Tween::new(Vector2D::new(0.0, 0.0), Vector2D::new(8.0, 0.0), 1.0);
+    let lift = Tween::new(Vector2D::new(0.0, 0.0), Vector2D::new(0.0, 4.0), 2.0);
+    let layered = Parallel::new().with(movement).with(lift);
+    assert_eq!(Some(2.0), layered.duration());
+    assert_eq!(Vector2D::new(4.0, 1.0), layered.sample(0.5));
+    assert_eq!(
+        vec![Vector2D::new(4.0, 0.0), Vector2D::new(0.0, 1.0)],
+        layered.sample_each(0.5)
+    );
+
+    let back = Tween::new(Vector2D::new(8.0, 4.0), Vector2D::new(0.0, 0.0), 1.0);
+    let mut animator = Animator::new(Sequence::new().then(layered).then(back));
+    assert_eq!(Some(3.0), animator.duration());
+    assert_eq!(Vector2D::new(8.0, 4.0), animator.update(2.0));
+    assert_eq!(Vector2D::new(4.0, 2.0), animator.update(0.5));
+    assert!(!animator.is_finished());
+    animator.update(0.5);
+    assert!(animator.is_finished());
+    animator.reset();
+    assert_eq!(Vector2D::new(0.0, 0.0), animator.value());
+}
+
+#[test]
+fn damp_frame_rate_independent() {
+    let target = Vector2D::new(10.0f64, -4.0);
+    let mut fast = Vector2D::new(0.0, 0.0);
+    for _ in 0..144 {
+        fast = Vector2D::damp(fast, target, 3.0, 1.0 / 144.0);
+    }
+    let mut slow = Vector2D::new(0.0, 0.0);
+    for _ in 0..30 {
+        slow = Vector2D::damp(slow, target, 3.0, 1.0 / 30.0);
+    }
+    assert!((fast - slow).length() < 1e-9);
+    let expected = target * (1.0 - (-3.0f64).exp());
+    assert!((fast - expected).length() < 1e-9);
+}
+
+#[test]
+fn smooth_damp_reaches_target_without_overshoot() {
+    let target = Vector2D::new(10.0f64, 0.0);
+    let mut position = Vector2D::new(0.0, 0.0);
+    let mut velocity = Vector2D::new(0.0, 0.0);
+    for _ in 0..300 {
+        position = Vector2D::smooth_damp(position, target, &mut velocity, 0.3, None, 1.0 / 60.0);
+        assert!(position.x <= target.x);
+    }
+    assert!((position - target).length() < 1e-3);
+}
+
+#[test]
+fn smooth_damp_max_speed() {
+    let target = Vector2D::new(1000.0f64, 0.0);
+    let mut position = Vector2D::new(0.0, 0.0);
+    let mut velocity = Vector2D::new(0.0, 0.0);
+    for _ in 0..60 {
+        let next =
+            Vector2D::smooth_damp(position, target, &mut velocity, 0.1, Some(5.0), 1.0 / 60.0);
+        assert!((next - position).length() <= 5.0 / 60.0 + 1e-9);
+        position = next;
+    }
+}
+
+#[test]
+fn spring_damper_step_independent() {
+    let target = Vector2D::new(5.0f64, 5.0);
+    for &ratio in &[0.2, 1.0, 2.5] {
+        let spring = SpringDamper::new(1.5, ratio);
+        let mut p1 = Vector2D::new(0.0, 0.0);
+        let mut v1 = Vector2D::new(1.0, 0.0);
+        for _ in 0..100 {
+            p1 = spring.update(p1, &mut v1, target, 0.01);
+        }
+        let mut v2 = Vector2D::new(1.0, 0.0);
+        let p2 = spring.update(Vector2D::new(0.0, 0.0), &mut v2, target, 1.0);
+        assert!((p1 - p2).length() < 1e-9);
+        assert!((v1 - v2).length() < 1e-9);
+    }
+}
+
+#[test]
+fn spring_damper_overshoot() {
+    let target = Vector2D::new(1.0f64, 0.0);
+    let bouncy = SpringDamper::new(1.0, 0.1);
+    let critical = SpringDamper::critically_damped(1.0);
+    let (mut bp, mut bv) = (Vector2D::new(0.0, 0.0), Vector2D::new(0.0, 0.0));
+    let (mut cp, mut cv) = (Vector2D::new(0.0, 0.0), Vector2D::new(0.0, 0.0));
+    let mut bouncy_max: f64 = 0.0;
+    for _ in 0..200 {
+        bp = bouncy.update(bp, &mut bv, target, 0.01);
+        cp = critical.update(cp, &mut cv, target, 0.01);
+        bouncy_max = bouncy_max.max(bp.x);
+        assert!(cp.x <= 1.0);
+    }
+    assert!(bouncy_max > 1.5);
+}
+
+fn oscillator_energy(state: &ParticleState<f64>) -> f64 {
+    0.5 * state.velocity.length_squared() + 0.5 * state.position.length_squared()
+}
+
+#[test]
+fn integrators_constant_acceleration() {
+    let gravity = Vector2D::new(0.0f64, -10.0);
+    let start = ParticleState::new(Vector2D::new(0.0, 0.0), Vector2D::new(2.0, 5.0));
+    let expected = Vector2D::new(2.0, 0.0);
+    for &integrator in &[Integrator::VelocityVerlet, Integrator::Rk4] {
+        let end = integrator.steps(start, 0.0, 0.1, 10, |_, _| gravity);
+        assert!((end.position - expected).length() < 1e-9);
+        assert!((end.velocity - Vector2D::new(2.0, -5.0)).length() < 1e-9);
+    }
+
+    let euler = Integrator::ExplicitEuler.step(start, 0.0, 0.5, |_, _| gravity);
+    assert_eq!(Vector2D::new(1.0, 2.5), euler.position);
+    let semi = Integrator::SemiImplicitEuler.step(start, 0.0, 0.5, |_, _| gravity);
+    assert_eq!(Vector2D::new(1.0, 0.0), semi.position);
+}
+
+#[test]
+fn integrators_oscillator_energy() {
+    let spring = |s: &ParticleState<f64>, _| -s.position;
+    let start = ParticleState::new(Vector2D::new(1.0, 0.0), Vector2D::new(0.0, 1.0));
+    let initial = oscillator_energy(&start);
+
+    let explicit = Integrator::ExplicitEuler.steps(start, 0.0, 0.05, 1000, spring);
+    assert!(oscillator_energy(&explicit) > initial * 2.0);
+
+    let semi = Integrator::SemiImplicitEuler.steps(start, 0.0, 0.05, 1000, spring);
+    assert!((oscillator_energy(&semi) - initial).abs() < 0.05);
+
+    let rk4 = Integrator::Rk4.steps(start, 0.0, 0.05, 1000, spring);
+    let (sin, cos) = 50.0f64.sin_cos();
+    assert!((rk4.position - Vector2D::new(cos, sin)).length() < 1e-5);
+}
+
+#[test]
+fn integrators_time_dependent() {
+    // a = t, so from rest v = t²/2 and x = t³/6
+    let start = ParticleState::new(Vector2D::new(0.0f64, 0.0), Vector2D::new(0.0, 0.0));
+    let end = Integrator::Rk4.steps(start, 0.0, 0.1, 20, |_, t| Vector2D::new(t, 0.0));
+    assert!((end.velocity.x - 2.0).abs() < 1e-9);
+    assert!((end.position.x - 8.0 / 6.0).abs() < 1e-9);
+}
+
+#[test]
+fn shape_mass_properties() {
+    let rect = Shape::rectangle(Vector2D::new(2.0f64, 1.0));
+    assert_eq!(8.0, rect.area());
+    // m(w² + h²) / 12
+    assert!((rect.moment_of_inertia(3.0) - 3.0 * (16.0 + 4.0) / 12.0).abs() < 1e-9);
+
+    let clockwise = Shape::polygon(vec![
+        Vector2D::new(0.0f64, 0.0),
+        Vector2D::new(0.0, 1.0),
+        Vector2D::new(1.0, 0.0),
+    ]);
+    assert!((clockwise.area() - 0.5).abs() < 1e-12);
+    let pose = Pose::new(Vector2D::new(5.0, 5.0), Angle::zero());
+    assert!(clockwise.contains(&pose, Vector2D::new(5.2, 5.2)));
+    assert!(!clockwise.contains(&pose, Vector2D::new(5.8, 5.8)));
+}
+
+#[test]
+fn collide_polygons() {
+    let ground = Shape::rectangle(Vector2D::new(5.0f64, 0.5));
+    let block = Shape::rectangle(Vector2D::new(0.5, 0.5));
+    let block_pose = Pose::new(Vector2D::new(1.0, 0.9), Angle::zero());
+
+    let contact = collide(&ground, &Pose::identity(), &block, &block_pose).unwrap();
+    assert!((contact.normal - Vector2D::new(0.0, 1.0)).length() < 1e-9);
+    assert_eq!(2, contact.points.len());
+    assert!((contact.depth() - 0.1).abs() < 1e-9);
+
+    // Swapping the shapes reverses the normal
+    let swapped = collide(&block, &block_pose, &ground, &Pose::identity()).unwrap();
+    assert!((swapped.normal + contact.normal).length() < 1e-9);
+
+    let apart = Pose::new(Vector2D::new(1.0, 1.1), Angle::zero());
+    assert_eq!(None, collide(&ground, &Pose::identity(), &block, &apart));
+
+    // A circle near a corner is pushed away from the corner
+    let corner = collide(
+        &block,
+        &Pose::identity(),
+        &Shape::circle(0.5),
+        &Pose::new(Vector2D::new(0.8, 0.8), Angle::zero()),
+    )
+    .unwrap();
+    let diagonal = Vector2D::new(1.0, 1.0) / 2.0f64.sqrt();
+    assert!((corner.normal - diagonal).length() < 1e-9);
+}
+
+#[test]
+fn physics_free_fall() {
+    let mut world = physics::World::new(Vector2D::new(0.0f64, -10.0), 0.1);
+    let ball = world.add_body(RigidBody::new(Shape::circle(1.0), 2.0));
+    assert_eq!(3, world.update(0.35));
+    assert!((world.interpolation_alpha() - 0.5).abs() < 1e-9);
+
+    let body = world.body(ball);
+    assert!((body.velocity - Vector2D::new(0.0, -3.0)).length() < 1e-9);
+    // Semi-implicit Euler: -(1 + 2 + 3) * 10 * 0.1²
+    assert!((body.position - Vector2D::new(0.0, -0.6)).length() < 1e-9);
+}
+
+#[test]
+fn physics_restitution() {
+    let drop = |restitution: f64| {
+        let mut world = physics::World::new(Vector2D::new(0.0, -10.0), 1.0 / 120.0);
+        world.add_body(RigidBody::fixed(Shape::rectangle(Vector2D::new(10.0, 0.5))));
+        let ball = world.add_body(
+            RigidBody::new(Shape::circle(0.5), 1.0)
+                .with_position(Vector2D::new(0.0, 6.0))
+                .with_restitution(restitution),
+        );
+        // Fall for long enough to hit the ground, then find the next peak
+        world.update(1.1);
+        let mut peak = world.body(ball).position.y;
+        for _ in 0..240 {
+            world.step();
+            let y = world.body(ball).position.y;
+            peak = if y > peak { y } else { peak };
+        }
+        peak
+    };
+
+    assert!(drop(0.0) < 1.05);
+    let bounce = drop(1.0);
+    assert!(bounce > 5.5 && bounce < 6.1);
+    let half = drop(0.5);
+    // Half the speed reaches a quarter of the height above the resting point
+    assert!((half - (1.0 + 5.0 / 4.0)).abs() < 0.2);
+}
+
+#[test]
+fn physics_friction_and_sleep() {
+    let slide = |friction: f64| {
+        let mut world = physics::World::new(Vector2D::new(0.0, -10.0), 1.0 / 60.0);
+        world.add_body(
+            RigidBody::fixed(Shape::rectangle(Vector2D::new(50.0, 0.5))).with_friction(friction),
+        );
+        let block = world.add_body(
+            RigidBody::new(Shape::rectangle(Vector2D::new(0.5, 0.5)), 1.0)
+                .with_position(Vector2D::new(0.0, 1.0))
+                .with_velocity(Vector2D::new(5.0, 0.0))
+                .with_friction(friction),
+        );
+        world.update(3.0);
+        world.body(block).clone()
+    };
+
+    // Without friction the block keeps sliding
+    let frictionless = slide(0.0);
+    assert!((frictionless.velocity.x - 5.0).abs() < 1e-6);
+    assert!(!frictionless.is_sleeping());
+
+    // With friction it stops after v² / 2μg = 2.5 units, without tipping over
+    let rough = slide(0.5);
+    assert!((rough.position.x - 2.5).abs() < 0.1);
+    assert!((rough.position.y - 1.0).abs() < 0.02);
+    assert!(rough.rotation.to_radians().abs() < 0.01);
+    assert!(rough.is_sleeping());
+}
+
+#[test]
+fn physics_sleeping_body_wakes_when_hit() {
+    let mut world = physics::World::new(Vector2D::new(0.0f64, -10.0), 1.0 / 60.0);
+    world.add_body(RigidBody::fixed(Shape::rectangle(Vector2D::new(10.0, 0.5))));
+    let target = world
+        .add_body(RigidBody::new(Shape::circle(0.5), 1.0).with_position(Vector2D::new(0.0, 1.0)));
+    world.update(1.0);
+    assert!(world.body(target).is_sleeping());
+
+    world.add_body(
+        RigidBody::new(Shape::circle(0.5), 1.0)
+            .with_position(Vector2D::new(-3.0, 1.0))
+            .with_velocity(Vector2D::new(6.0, 0.0))
+            .with_friction(0.0),
+    );
+    world.update(0.5);
+    assert!(!world.body(target).is_sleeping());
+    assert!(world.body(target).position.x > 0.1);
+}
+
+#[test]
+fn verlet_rope_keeps_length() {
+    let mut world = VerletWorld::new(Vector2D::new(0.0f64, -10.0), 1.0 / 60.0).with_iterations(20);
+    let rope = world.rope(Vector2D::new(0.0, 0.0), Vector2D::new(4.0, 0.0), 8, 0.5);
+    world.pin(rope[0]);
+    assert_eq!(9, world.constraints().len());
+
+    for _ in 0..60 {
+        world.step();
+        assert_eq!(Vector2D::new(0.0, 0.0), world.point(rope[0]).position);
+    }
+    for pair in rope.windows(2) {
+        let link = world.point(pair[1]).position - world.point(pair[0]).position;
+        assert!((link.length() - 0.5).abs() < 0.01);
+    }
+    // Swinging down, the end of the rope has fallen below its pin
+    assert!(world.point(rope[8]).position.y < -2.0);
+}
+
+#[test]
+fn verlet_angle_constraint() {
+    let mut world = VerletWorld::new(Vector2D::new(0.0f64, -10.0), 1.0 / 60.0);
+    let anchor = world.add_point(VerletPoint::fixed(Vector2D::new(-1.0, 0.0)));
+    let pivot = world.add_point(VerletPoint::fixed(Vector2D::new(0.0, 0.0)));
+    let arm = world.add_point(VerletPoint::new(Vector2D::new(0.0, 1.0), 1.0));
+    world.connect(pivot, arm, 1.0);
+    world.brace(anchor, pivot, arm, 1.0);
+    match world.constraints()[1] {
+        Constraint::Angle { angle, .. } => {
+            assert!((angle.to_degrees() + 90.0).abs() < 1e-9);
+        }
+        _ => panic!("expected an angle constraint"),
+    }
+
+    // The braced arm holds itself upright against gravity
+    world.update(2.0);
+    assert!((world.point(arm).position - Vector2D::new(0.0, 1.0)).length() < 0.01);
+}
+
+#[test]
+fn verlet_collides_with_shapes() {
+    let mut world = VerletWorld::new(Vector2D::new(0.0f64, -10.0), 1.0 / 60.0).with_friction(1.0);
+    world.add_collider(
+        Shape::rectangle(Vector2D::new(5.0, 1.0)),
+        Pose::new(Vector2D::new(0.0, -1.0), Angle::zero()),
+    );
+    let point = world.add_point(
+        VerletPoint::new(Vector2D::new(0.0, 3.0), 1.0)
+            .with_radius(0.25)
+            .with_velocity(Vector2D::new(2.0, 0.0), 1.0 / 60.0),
+    );
+
+    world.update(2.0);
+    let p = world.point(point);
+    // Resting on the surface, with friction having stopped it sliding
+    assert!((p.position.y - 0.25).abs() < 1e-6);
+    assert!(p.velocity(world.timestep).length() < 1e-6);
+}
+
+#[test]
+fn ballistics_straight_intercept() {
+    let shooter = Vector2D::new(0.0f64, 0.0);
+    let target = Vector2D::new(10.0, 0.0);
+    let firing = intercept(shooter, 10.0, target, Vector2D::new(0.0, 5.0)).unwrap();
+
+    // 100 + 25t² = 100t²
+    let time = (100.0f64 / 75.0).sqrt();
+    assert!((firing.time - time).abs() < 1e-9);
+    assert!((firing.point - Vector2D::new(10.0, 5.0 * time)).length() < 1e-9);
+    assert!((firing.velocity.length() - 10.0).abs() < 1e-9);
+    assert!((firing.direction().length() - 1.0).abs() < 1e-12);
+
+    // A target fleeing faster than the projectile can't be caught
+    assert_eq!(
+        None,
+        intercept(shooter, 10.0, target, Vector2D::new(12.0, 0.0))
+    );
+}
+
+#[test]
+fn ballistics_moving_target_arcs() {
+    let shooter = Vector2D::new(0.0f64, 0.0);
+    let target = Vector2D::new(30.0, 5.0);
+    let target_velocity = Vector2D::new(-4.0, 1.0);
+    let gravity = Vector2D::new(0.0, -9.81);
+
+    let arcs = ballistic_arcs(shooter, 25.0, target, target_velocity, gravity).unwrap();
+    assert!(arcs.low.time < arcs.high.time);
+    assert!(arcs.low.angle() < arcs.high.angle());
+    for firing in &[arcs.low, arcs.high] {
+        let t = firing.time;
+        let projectile = shooter + firing.velocity * t + gravity * (t * t / 2.0);
+        assert!((projectile - (target + target_velocity * t)).length() < 1e-6);
+        assert!((projectile - firing.point).length() < 1e-6);
+        assert!((firing.velocity.length() - 25.0).abs() < 1e-6);
+    }
+
+    assert_eq!(
+        None,
+        ballistic_arcs(shooter, 10.0, target, target_velocity, gravity)
+    );
+}
+
+#[test]
+fn ballistics_edge_of_range() {
+    // The furthest reach at height h is v √(v² - 2gh) / g, where the two arcs
+    // meet in one
+    let zero = Vector2D::new(0.0f64, 0.0);
+    let gravity = Vector2D::new(0.0, -9.81);
+    for &speed in &[10.0f64, 20.0, 25.0, 30.0] {
+        for &height in &[-3.0, -1.0, 0.0, 2.0, 3.0] {
+            let range = speed / 9.81 * (speed * speed - 2.0 * 9.81 * height).sqrt();
+            let target = Vector2D::new(range, height);
+            let arcs = ballistic_arcs(zero, speed, target, zero, gravity).unwrap();
+            assert!((arcs.low.time - arcs.high.time).abs() < 1e-6);
+            let t = arcs.high.time;
+            let projectile = arcs.high.velocity * t + gravity * (t * t / 2.0);
+            assert!((projectile - target).length() < 1e-6);
+            assert!((arcs.high.velocity.length() - speed).abs() < 1e-6);
+
+            let beyond = Vector2D::new(range * 1.001, height);
+            assert_eq!(None, ballistic_arcs(zero, speed, beyond, zero, gravity));
+        }
+    }
+}
+
+#[test]
+fn ballistics_fixed_point() {
+    let arcs = ballistic_arcs(
+        Vector2D::new(Fixed::<16>::from_int(0), Fixed::from_int(0)),
+        Fixed::from_int(20),
+        Vector2D::new(Fixed::from_int(20), Fixed::from_int(0)),
+        Vector2D::new(Fixed::from_int(0), Fixed::from_int(0)),
+        Vector2D::new(Fixed::from_int(0), Fixed::from_f64(-9.81)),
+    )
+    .unwrap();
+    let low = (9.81f64 * 20.0 / 400.0).asin() / 2.0;
+    assert!((arcs.low.angle().to_radians().to_f64() - low).abs() < 1e-3);
+}
+
+#[test]
+fn steering_seek_flee_arrive() {
+    let agent = Agent::new(Vector2D::new(0.0f64, 0.0), 4.0, 2.0);
+    let target = Vector2D::new(10.0, 0.0);
+    assert_eq!(Vector2D::new(2.0, 0.0), agent.seek(target));
+    assert_eq!(Vector2D::new(-2.0, 0.0), agent.flee(target));
+
+    // Arriving slows down on the approach, and brakes on the target itself
+    let moving = agent.with_velocity(Vector2D::new(4.0, 0.0));
+    assert_eq!(
+        Vector2D::new(-2.0, 0.0),
+        moving.arrive(Vector2D::new(0.0, 0.0), 5.0)
+    );
+    assert_eq!(
+        Vector2D::new(-1.0, 0.0),
+        moving.arrive(Vector2D::new(3.0, 0.0), 4.0)
+    );
+
+    let mut agent = Agent::new(Vector2D::new(0.0f64, 0.0), 4.0, 8.0);
+    for _ in 0..1200 {
+        let force = agent.arrive(target, 4.0);
+        agent.update(force, 1.0 / 60.0);
+        assert!(agent.velocity.length() <= 4.0 + 1e-9);
+    }
+    assert!((agent.position - target).length() < 0.05);
+    assert!(agent.velocity.length() < 0.05);
+}
+
+#[test]
+fn steering_pursue_evade() {
+    let agent = Agent::new(Vector2D::new(0.0f64, 0.0), 5.0, 100.0);
+    let target = Vector2D::new(10.0, 0.0);
+    let target_velocity = Vector2D::new(0.0, 5.0);
+    // Reaching the target takes two seconds, by which time it has moved 10 up
+    let pursue = agent.pursue(target, target_velocity);
+    assert!((pursue - Vector2D::new(5.0, 5.0) / 2.0f64.sqrt()).length() < 1e-9);
+    assert!((agent.evade(target, target_velocity) + pursue).length() < 1e-9);
+}
+
+#[test]
+fn steering_obstacles_and_paths() {
+    let agent = Agent::new(Vector2D::new(0.0f64, 0.0), 2.0, 1.0)
+        .with_radius(0.5)
+        .with_velocity(Vector2D::new(2.0, 0.0));
+
+    // An obstacle slightly to the left pushes the agent to the right
+    let ahead = [Obstacle::new(Vector2D::new(3.0, 0.5), 1.0)];
+    let force = agent.avoid_obstacles(&ahead, 5.0);
+    assert!(force.y < 0.0 && force.x.abs() < 1e-12);
+    let behind = [Obstacle::new(Vector2D::new(-3.0, 0.0), 1.0)];
+    assert_eq!(Vector2D::new(0.0, 0.0), agent.avoid_obstacles(&behind, 5.0));
+    let beside = [Obstacle::new(Vector2D::new(3.0, 3.0), 1.0)];
+    assert_eq!(Vector2D::new(0.0, 0.0), agent.avoid_obstacles(&beside, 5.0));
+
+    // Following a path around a corner ends at its last waypoint
+    let path = [
+        Vector2D::new(0.0, 0.0),
+        Vector2D::new(10.0, 0.0),
+        Vector2D::new(10.0, 10.0),
+    ];
+    let mut agent = Agent::new(Vector2D::new(0.0f64, -1.0), 3.0, 6.0);
+    let mut furthest_from_path: f64 = 0.0;
+    for _ in 0..900 {
+        let force = agent.follow_path(&path, 1.0);
+        agent.update(force, 1.0 / 60.0);
+        let p = agent.position;
+        let first = (Vector2D::new(p.x.clamp(0.0, 10.0), 0.0) - p).length();
+        let second = (Vector2D::new(10.0, p.y.clamp(0.0, 10.0)) - p).length();
+        furthest_from_path = furthest_from_path.max(first.min(second));
+    }
+    assert!((agent.position - path[2]).length() < 0.1);
+    assert!(furthest_from_path < 1.2);
+}
+
+#[test]
+fn steering_flocking() {
+    let flock = [
+        Agent::new(Vector2D::new(0.0f64, 0.0), 1.0, 1.0),
+        Agent::new(Vector2D::new(1.0, 0.0), 1.0, 1.0).with_velocity(Vector2D::new(0.0, 1.0)),
+        Agent::new(Vector2D::new(0.0, 1.0), 1.0, 1.0).with_velocity(Vector2D::new(0.0, 1.0)),
+        Agent::new(Vector2D::new(50.0, 50.0), 1.0, 1.0).with_velocity(Vector2D::new(-1.0, 0.0)),
+    ];
+    let agent = flock[0];
+    let diagonal = Vector2D::new(1.0, 1.0) / 2.0f64.sqrt();
+
+    // Neighbours up and to the right, ignoring the distant agent and itself
+    assert!((agent.separation(&flock, 5.0) + diagonal).length() < 1e-9);
+    assert!((agent.cohesion(&flock, 5.0) - diagonal).length() < 1e-9);
+    assert_eq!(Vector2D::new(0.0, 1.0), agent.alignment(&flock, 5.0));
+    assert_eq!(Vector2D::new(0.0, 0.0), agent.cohesion(&flock, 0.5));
+}
+
+#[test]
+fn steering_blending() {
+    let seek = Vector2D::new(3.0f64, 0.0);
+    let avoid = Vector2D::new(0.0, 4.0);
+    let steering = Steering::new().with(avoid, 1.0).with(seek, 1.0);
+    assert_eq!(Vector2D::new(3.0, 4.0), steering.weighted(10.0));
+    assert!((steering.weighted(3.0) - Vector2D::new(1.8, 2.4)).length() < 1e-12);
+
+    // Avoidance uses up the budget before seeking gets a say
+    assert_eq!(Vector2D::new(0.0, 4.0), steering.prioritised(4.0));
+    assert_eq!(Vector2D::new(1.0, 4.0), steering.prioritised(5.0));
+    assert_eq!(
+        Vector2D::new(0.0, 2.0),
+        Steering::new().with(avoid, 0.5).prioritised(10.0)
+    );
+
+    let mut wander = Wander::new(2.0, 1.0, Angle::degrees(90.0));
+    let agent =
+        Agent::new(Vector2D::new(0.0, 0.0), 1.0, 10.0).with_velocity(Vector2D::new(1.0, 0.0));
+    // Straight ahead leaves the velocity alone, and a full jitter turns left
+    assert_eq!(Vector2D::new(0.0, 0.0), wander.force(&agent, 0.0));
+    let left = wander.force(&agent, 1.0);
+    assert!(left.y > 0.0);
+}
+
+/// Parse a map where `#` is blocked, `~` costs 5 and anything else costs 1.
+fn grid_cost(map: &'static [&'static str]) -> impl Fn(Vector2D<i32>) -> Option<f64> {
+    move |cell| {
+        if cell.x < 0 || cell.y < 0 {
+            return None;
+        }
+        match map.get(cell.y as usize)?.as_bytes().get(cell.x as usize)? {
+            b'#' => None,
+            b'~' => Some(5.0),
+            _ => Some(1.0),
+        }
+    }
+}
+
+#[test]
+fn pathfinding_open_grid() {
+    const MAP: &[&str] = &["......", "......", "......", "......"];
+    let start = Vector2D::new(0, 0);
+    let goal = Vector2D::new(5, 3);
+
+    let four = GridPathfinder::new(grid_cost(MAP)).with_neighbourhood(Neighbourhood::Four);
+    let path = four.find_path(start, goal).unwrap();
+    assert_eq!(9, path.len());
+    assert_eq!((start, goal), (path[0], path[8]));
+    assert_eq!(Some(8.0), four.path_cost(&path));
+
+    let eight = GridPathfinder::new(grid_cost(MAP));
+    let path = eight.find_path(start, goal).unwrap();
+    assert_eq!(6, path.len());
+    let octile = 2.0 + 3.0 * 2.0f64.sqrt();
+    assert!((eight.path_cost(&path).unwrap() - octile).abs() < 1e-9);
+
+    for &heuristic in &[Heuristic::Zero, Heuristic::Euclidean, Heuristic::Manhattan] {
+        let other = GridPathfinder::new(grid_cost(MAP)).with_heuristic(heuristic);
+        let path = other.find_path(start, goal).unwrap();
+        assert!((other.path_cost(&path).unwrap() - octile).abs() < 1e-9);
+    }
+    assert_eq!(Some(vec![start]), eight.find_path(start, start));
+    assert_eq!(None, eight.find_path(start, Vector2D::new(9, 9)));
+}
+
+#[test]
+fn pathfinding_corner_cutting() {
+    const MAP: &[&str] = &[".#", "#."];
+    let start = Vector2D::new(0, 0);
+    let goal = Vector2D::new(1, 1);
+    let path = |rule| {
+        GridPathfinder::new(grid_cost(MAP))
+            .with_neighbourhood(Neighbourhood::Eight(rule))
+            .find_path(start, goal)
+    };
+    assert_eq!(Some(vec![start, goal]), path(CornerCutting::Always));
+    assert_eq!(None, path(CornerCutting::IfOneOpen));
+    assert_eq!(None, path(CornerCutting::Never));
+
+    const CORNER: &[&str] = &["..", "#."];
+    let pathfinder = GridPathfinder::new(grid_cost(CORNER));
+    assert_eq!(3, pathfinder.find_path(start, goal).unwrap().len());
+    let cutting = pathfinder.with_neighbourhood(Neighbourhood::Eight(CornerCutting::IfOneOpen));
+    assert_eq!(2, cutting.find_path(start, goal).unwrap().len());
+}
+
+#[test]
+fn pathfinding_costs() {
+    const MAP: &[&str] = &[".~~~.", ".~~~.", ".....", "#####"];
+    let pathfinder = GridPathfinder::new(grid_cost(MAP)).with_neighbourhood(Neighbourhood::Four);
+    let path = pathfinder
+        .find_path(Vector2D::new(0, 0), Vector2D::new(4, 0))
+        .unwrap();
+    // Going around the swamp is cheaper than wading through it
+    assert_eq!(Some(8.0), pathfinder.path_cost(&path));
+    assert!(path
+        .iter()
+        .all(|c| MAP[c.y as usize].as_bytes()[c.x as usize] == b'.'));
+}
+
+#[test]
+fn pathfinding_jump_point_search() {
+    const MAP: &[&str] = &[
+        "..........#.........",
+        "..####....#....###..",
+        ".....#....#......#..",
+        ".....#.......#...#..",
+        "######...#####...#..",
+        "........#......###..",
+        ".####...#...........",
+        "....#.......#####...",
+    ];
+    let astar = GridPathfinder::new(grid_cost(MAP));
+    let jps = GridPathfinder::new(grid_cost(MAP)).with_jump_point_search(true);
+    let pairs = [
+        (Vector2D::new(0, 0), Vector2D::new(19, 7)),
+        (Vector2D::new(0, 7), Vector2D::new(11, 0)),
+        (Vector2D::new(6, 3), Vector2D::new(0, 5)),
+        (Vector2D::new(19, 0), Vector2D::new(14, 2)),
+    ];
+    for &(start, goal) in &pairs {
+        let expected = astar
+            .path_cost(&astar.find_path(start, goal).unwrap())
+            .unwrap();
+        let path = jps.find_path(start, goal).unwrap();
+        assert_eq!((start, goal), (path[0], *path.last().unwrap()));
+        // Every step of the expanded path is a valid move
+        let cost = jps.path_cost(&path).unwrap();
+        assert!((cost - expected).abs() < 1e-9);
+    }
+    assert_eq!(
+        None,
+        jps.find_path(Vector2D::new(0, 0), Vector2D::new(10, 0))
+    );
+}
+
+fn l_shaped_mesh() -> NavMesh<f64> {
+    let square = |x: f64, y: f64| {
+        vec![
+            Vector2D::new(x, y),
+            Vector2D::new(x + 10.0, y),
+            Vector2D::new(x + 10.0, y + 10.0),
+            Vector2D::new(x, y + 10.0),
+        ]
+    };
+    // The middle square is given clockwise, and should be rewound
+    let mut middle = square(10.0, 0.0);
+    middle.reverse();
+    NavMesh::new(vec![square(0.0, 0.0), middle, square(10.0, 10.0)])
+}
+
+#[test]
+fn navmesh_locate() {
+    let mesh = l_shaped_mesh();
+    assert_eq!(1, mesh.portals(0).len());
+    assert_eq!(2, mesh.portals(1).len());
+    assert_eq!(Some(0), mesh.locate(Vector2D::new(5.0, 5.0)));
+    assert_eq!(Some(1), mesh.locate(Vector2D::new(15.0, 2.0)));
+    assert_eq!(Some(2), mesh.locate(Vector2D::new(12.0, 18.0)));
+    assert_eq!(None, mesh.locate(Vector2D::new(5.0, 15.0)));
+    assert_eq!(
+        None,
+        mesh.find_path(Vector2D::new(5.0, 5.0), Vector2D::new(5.0, 15.0))
+    );
+}
+
+#[test]
+fn navmesh_funnel() {
+    let mesh = l_shaped_mesh();
+    // Within one polygon, and in a straight line through several, the path
+    // goes directly to the goal
+    let start = Vector2D::new(5.0, 5.0);
+    assert_eq!(
+        Some(vec![start, Vector2D::new(8.0, 2.0)]),
+        mesh.find_path(start, Vector2D::new(8.0, 2.0))
+    );
+    assert_eq!(
+        Some(vec![start, Vector2D::new(15.0, 5.0)]),
+        mesh.find_path(start, Vector2D::new(15.0, 5.0))
+    );
+    assert_eq!(
+        Some(vec![
+            Vector2D::new(2.0, 5.0),
+            Vector2D::new(10.0, 10.0),
+            Vector2D::new(12.0, 18.0)
+        ]),
+        mesh.find_path(Vector2D::new(2.0, 5.0), Vector2D::new(12.0, 18.0))
+    );
+    // And the same going the other way around the corner
+    assert_eq!(
+        Some(vec![
+            Vector2D::new(12.0, 18.0),
+            Vector2D::new(10.0, 10.0),
+            Vector2D::new(2.0, 5.0)
+        ]),
+        mesh.find_path(Vector2D::new(12.0, 18.0), Vector2D::new(2.0, 5.0))
+    );
+}
+
+#[test]
+fn navmesh_agent_radius() {
+    let mesh = l_shaped_mesh();
+    let path = mesh
+        .find_path_with_radius(Vector2D::new(2.0, 5.0), Vector2D::new(12.0, 18.0), 1.0)
+        .unwrap();
+    assert_eq!(
+        vec![
+            Vector2D::new(2.0, 5.0),
+            Vector2D::new(10.0, 9.0),
+            Vector2D::new(11.0, 10.0),
+            Vector2D::new(12.0, 18.0),
+        ],
+        path
+    );
+    // Too wide to fit through the 10 unit gaps
+    assert_eq!(
+        None,
+        mesh.find_path_with_radius(Vector2D::new(2.0, 5.0), Vector2D::new(12.0, 18.0), 6.0)
+    );
+    assert_eq!(
+        Some(vec![0]),
+        mesh.find_corridor(Vector2D::new(2.0, 5.0), Vector2D::new(8.0, 5.0), 6.0)
+    );
+}
+
+#[test]
+fn navmesh_open_area_radius() {
+    // A 6 by 6 room divided into unit squares, much narrower than the agent
+    let square = |x: f64, y: f64| {
+        vec![
+            Vector2D::new(x, y),
+            Vector2D::new(x + 1.0, y),
+            Vector2D::new(x + 1.0, y + 1.0),
+            Vector2D::new(x, y + 1.0),
+        ]
+    };
+    let room = (0..36).map(|i| square((i % 6) as f64, (i / 6) as f64));
+    let mesh = NavMesh::new(room.collect());
+    let (start, goal) = (Vector2D::new(1.5, 1.5), Vector2D::new(4.5, 3.5));
+    assert_eq!(
+        Some(vec![start, goal]),
+        mesh.find_path_with_radius(start, goal, 0.8)
+    );
+
+    // But a corridor a single square wide is still too narrow
+    let corridor = NavMesh::new((0..6).map(|i| square(i as f64, 0.0)).collect());
+    assert_eq!(
+        None,
+        corridor.find_path_with_radius(Vector2D::new(0.5, 0.5), Vector2D::new(5.5, 0.5), 0.8)
+    );
+    assert!(corridor
+        .find_path_with_radius(Vector2D::new(0.5, 0.5), Vector2D::new(5.5, 0.5), 0.4)
+        .is_some());
+}
+
+#[test]
+fn flowfield_integration() {
+    const MAP: &[&str] = &["......", ".~~##.", "......"];
+    let map = CostMap::from_fn(6, 3, grid_cost(MAP));
+    assert_eq!(None, map.cost(Vector2D::new(3, 1)));
+    assert_eq!(None, map.cost(Vector2D::new(6, 0)));
+
+    // Costs match the cheapest path found by the pathfinder to the nearest of
+    // several goals
+    let goals = [Vector2D::new(0, 0), Vector2D::new(5, 2)];
+    let neighbourhood = Neighbourhood::Eight(CornerCutting::Never);
+    let integration = IntegrationField::new(&map, &goals, neighbourhood);
+    let pathfinder = GridPathfinder::new(grid_cost(MAP)).with_neighbourhood(neighbourhood);
+    for y in 0..3 {
+        for x in 0..6 {
+            let cell = Vector2D::new(x, y);
+            let expected = goals
+                .iter()
+                .filter_map(|&goal| {
+                    let path = pathfinder.find_path(cell, goal)?;
+                    pathfinder.path_cost(&path)
+                })
+                .fold(None, |best: Option<f64>, cost| match best {
+                    Some(best) if best <= cost => Some(best),
+                    _ => Some(cost),
+                });
+            let cost = integration.cost(cell);
+            match map.cost(cell) {
+                Some(_) => assert!((cost.unwrap() - expected.unwrap()).abs() < 1e-9),
+                None => assert_eq!(None, cost),
+            }
+        }
+    }
+}
+
+#[test]
+fn flowfield_directions() {
+    let mut map = CostMap::new(4, 4);
+    map.set(Vector2D::new(1, 1), None);
+    map.set(Vector2D::new(3, 3), None);
+    let integration = IntegrationField::new(
+        &map,
+        &[Vector2D::new(0, 0)],
+        Neighbourhood::Eight(CornerCutting::Never),
+    );
+    let flow: FlowField<f64> = FlowField::new(&integration);
+
+    let zero = Vector2D::new(0.0f64, 0.0);
+    assert_eq!(zero, flow.direction(Vector2D::new(0, 0)));
+    assert_eq!(zero, flow.direction(Vector2D::new(1, 1)));
+    assert_eq!(zero, flow.direction(Vector2D::new(-1, 0)));
+    assert_eq!(
+        Vector2D::new(-1.0, 0.0),
+        flow.direction(Vector2D::new(1, 0))
+    );
+    // Diagonal moves are normalised, and don't cut past the blocked cell
+    let diagonal = flow.direction(Vector2D::new(3, 2));
+    assert!((diagonal.length() - 1.0).abs() < 1e-9);
+    assert_eq!(
+        Vector2D::new(0.0, -1.0),
+        flow.direction(Vector2D::new(2, 1))
+    );
+    // Following the field from any open cell reaches the goal
+    for y in 0..4 {
+        for x in 0..4 {
+            let mut cell = Vector2D::new(x, y);
+            if map.cost(cell).is_none() {
+                continue;
+            }
+            for _ in 0..16 {
+                let d = flow.direction(cell);
+                cell = Vector2D::new(cell.x + d.x.round() as i32, cell.y + d.y.round() as i32);
+            }
+            assert_eq!(Vector2D::new(0, 0), cell);
+        }
+    }
+}
+
+#[test]
+fn flowfield_sampling() {
+    // A goal on the left of an open corridor, so everything flows left
+    let map = CostMap::new(3, 2);
+    let integration = IntegrationField::new(&map, &[Vector2D::new(0, 0)], Neighbourhood::Four);
+    let flow: FlowField<f64> = FlowField::new(&integration);
+
+    // Samples at cell centres match the cells
+    assert_eq!(
+        flow.direction(Vector2D::new(2, 1)),
+        flow.sample(Vector2D::new(2.0, 1.0))
+    );
+    // Between cells pointing the same way, the direction is unchanged
+    assert_eq!(
+        Vector2D::new(-1.0, 0.0),
+        flow.sample(Vector2D::new(1.5, 0.25))
+    );
+    // Between the goal and the cell next to it, the direction fades out
+    let sample = flow.sample(Vector2D::new(0.75, 0.0));
+    assert!((sample.x + 0.75).abs() < 1e-9 && sample.y.abs() < 1e-9);
+    // Off the edge of the grid, only the cells on the grid are used
+    assert_eq!(
+        Vector2D::new(-1.0, 0.0),
+        flow.sample(Vector2D::new(2.5, 0.0))
+    );
+    assert_eq!(
+        Vector2D::new(0.0, 0.0),
+        flow.sample(Vector2D::new(5.0, 5.0))
+    );
+}
+
+#[test]
+fn visibility_polygon_open_room() {
+    // With no walls, the whole room is visible
+    let bounds = Box2D::new(Vector2D::new(-2.0f64, -1.0), Vector2D::new(3.0, 4.0));
+    let polygon = visibility_polygon(Vector2D::new(0.0, 0.0), &[], bounds);
+    assert_eq!(
+        vec![
+            Vector2D::new(-2.0, -1.0),
+            Vector2D::new(3.0, -1.0),
+            Vector2D::new(3.0, 4.0),
+            Vector2D::new(-2.0, 4.0),
+        ],
+        polygon
+    );
+}
+
+#[test]
+fn visibility_polygon_shadows() {
+    let bounds = Box2D::new(Vector2D::new(-10.0f64, -10.0), Vector2D::new(10.0, 10.0));
+    let walls = [
+        (Vector2D::new(2.0, -1.0), Vector2D::new(2.0, 1.0)),
+        // Seen edge-on, so it hides nothing
+        (Vector2D::new(-2.0, 0.0), Vector2D::new(-5.0, 0.0)),
+    ];
+    let origin = Vector2D::new(0.0, 0.0);
+    let polygon = visibility_polygon(origin, &walls, bounds);
+    assert_eq!(
+        vec![
+            Vector2D::new(-10.0, -10.0),
+            Vector2D::new(10.0, -10.0),
+            Vector2D::new(10.0, -5.0),
+            Vector2D::new(2.0, -1.0),
+            Vector2D::new(2.0, 1.0),
+            Vector2D::new(10.0, 5.0),
+            Vector2D::new(10.0, 10.0),
+            Vector2D::new(-10.0, 10.0),
+        ],
+        polygon
+    );
+
+    // Points visible by line of sight lie within the polygon
+    let inside = |p: Vector2D<f64>| {
+        (0..polygon.len()).all(|i| {
+            let (a, b) = (polygon[i], polygon[(i + 1) % polygon.len()]);
+            // The polygon is star-shaped around the origin, so each edge only
+            // needs checking within its own wedge
+            let (da, db) = (a - origin, b - origin);
+            let in_wedge = da.x * p.y - da.y * p.x >= 0.0 && p.x * db.y - p.y * db.x >= 0.0;
+            !in_wedge || (b.x - a.x) * (p.y - a.y) - (b.y - a.y) * (p.x - a.x) >= 0.0
+        })
+    };
+    for y in -9..10 {
+        for x in -9..10 {
+            let p = Vector2D::new(x as f64 + 0.5, y as f64 + 0.5);
+            assert_eq!(line_of_sight(origin, p, &walls), inside(p));
+        }
+    }
+    assert!(line_of_sight(origin, Vector2D::new(2.0, 1.0), &walls));
+    assert!(!line_of_sight(origin, Vector2D::new(5.0, 0.0), &walls));
+}
+
+#[test]
+fn visibility_polygon_crossing_walls() {
+    let bounds = Box2D::new(Vector2D::new(-10.0f64, -10.0), Vector2D::new(10.0, 10.0));
+    // An X, crossing at (3, 0)
+    let walls = [
+        (Vector2D::new(2.0, -2.0), Vector2D::new(4.0, 2.0)),
+        (Vector2D::new(2.0, 2.0), Vector2D::new(4.0, -2.0)),
+    ];
+    let polygon = Polygon::new(visibility_polygon(Vector2D::new(0.0, 0.0), &walls, bounds));
+    assert!(polygon.contains(Vector2D::new(2.5, 0.0)));
+    assert!(polygon.contains(Vector2D::new(2.9, 0.0)));
+    assert!(!polygon.contains(Vector2D::new(3.1, 0.0)));
+    assert!(!polygon.contains(Vector2D::new(2.5, 1.2)));
+    assert!(polygon.contains(Vector2D::new(2.5, 0.8)));
+    assert!(polygon.exterior.contains(&Vector2D::new(3.0, 0.0)));
+}
+
+#[test]
+fn visibility_field_of_view() {
+    const MAP: &[&str] = &[
+        ".........",
+        ".........",
+        "....#....",
+        ".........",
+        ".........",
+    ];
+    let opaque = |cell: Vector2D<i32>| {
+        MAP.get(cell.y as usize)
+            .and_then(|row| row.as_bytes().get(cell.x as usize))
+            .is_none_or(|&c| c == b'#')
+    };
+    let visible = field_of_view(Vector2D::new(4, 4), 10, opaque);
+    assert!(visible.contains(&Vector2D::new(4, 4)));
+    assert!(visible.contains(&Vector2D::new(4, 2)));
+    assert!(visible.contains(&Vector2D::new(0, 0)));
+    assert!(visible.contains(&Vector2D::new(8, 0)));
+    // Directly behind the pillar is hidden
+    assert!(!visible.contains(&Vector2D::new(4, 1)));
+    assert!(!visible.contains(&Vector2D::new(4, 0)));
+    // The edges of the map are visible, but nothing beyond them
+    assert!(visible.contains(&Vector2D::new(-1, 4)));
+    assert!(!visible.contains(&Vector2D::new(-2, 4)));
+
+    // The radius limits how far can be seen, and the view is symmetric in an
+    // open area
+    let near = field_of_view(Vector2D::new(0, 0), 2, |_| false);
+    assert_eq!(13, near.len());
+    assert!(near.contains(&Vector2D::new(-2, 0)) && near.contains(&Vector2D::new(1, -1)));
+    assert!(!near.contains(&Vector2D::new(2, 2)));
+}
+
+#[test]
+fn hull_collinear_and_duplicates() {
+    let points = [
+        Vector2D::new(0, 0),
+        Vector2D::new(1, 0),
+        Vector2D::new(2, 0),
+        Vector2D::new(2, 2),
+        Vector2D::new(2, 1),
+        Vector2D::new(1, 1),
+        Vector2D::new(0, 2),
+        Vector2D::new(2, 2),
+        Vector2D::new(0, 1),
+    ];
+    let corners = vec![
+        Vector2D::new(0, 0),
+        Vector2D::new(2, 0),
+        Vector2D::new(2, 2),
+        Vector2D::new(0, 2),
+    ];
+    assert_eq!(corners, convex_hull(&points));
+    assert_eq!(corners, quickhull(&points));
+    assert_eq!(
+        vec![
+            Vector2D::new(0, 0),
+            Vector2D::new(1, 0),
+            Vector2D::new(2, 0),
+            Vector2D::new(2, 1),
+            Vector2D::new(2, 2),
+            Vector2D::new(0, 2),
+            Vector2D::new(0, 1),
+        ],
+        convex_hull_with(&points, Collinear::Include)
+    );
+}
+
+#[test]
+fn hull_degenerate() {
+    let none: [Vector2D<i64>; 0] = [];
+    assert!(convex_hull(&none).is_empty());
+    assert!(quickhull(&none).is_empty());
+
+    let same = [Vector2D::new(3, 4); 3];
+    assert_eq!(vec![Vector2D::new(3, 4)], convex_hull(&same));
+    assert_eq!(vec![Vector2D::new(3, 4)], quickhull(&same));
+
+    let line = [
+        Vector2D::new(2.0f64, 2.0),
+        Vector2D::new(0.0, 0.0),
+        Vector2D::new(1.0, 1.0),
+        Vector2D::new(2.0, 2.0),
+    ];
+    let ends = vec![Vector2D::new(0.0, 0.0), Vector2D::new(2.0, 2.0)];
+    assert_eq!(ends, convex_hull(&line));
+    assert_eq!(ends, quickhull(&line));
+    assert_eq!(
+        vec![
+            Vector2D::new(0.0, 0.0),
+            Vector2D::new(1.0, 1.0),
+            Vector2D::new(2.0, 2.0),
+        ],
+        convex_hull_with(&line, Collinear::Include)
+    );
+}
+
+#[test]
+fn hull_algorithms_agree() {
+    // Scattered points from a simple linear congruential generator
+    let mut seed = 12345u32;
+    let mut next = || {
+        seed = seed.wrapping_mul(1103515245).wrapping_add(12345);
+        ((seed >> 16) % 200) as f32 / 10.0 - 10.0
+    };
+    for count in [5, 20, 100, 500] {
+        let points: Vec<Vector2D<f32>> =
+            (0..count).map(|_| Vector2D::new(next(), next())).collect();
+        let hull = convex_hull(&points);
+        assert_eq!(hull, quickhull(&points));
+        // Every point is inside or on every edge, which turns counter-clockwise
+        for i in 0..hull.len() {
+            let (a, b) = (hull[i], hull[(i + 1) % hull.len()]);
+            assert!(points
+                .iter()
+                .all(|&p| { (b.x - a.x) * (p.y - a.y) - (b.y - a.y) * (p.x - a.x) >= 0.0 }));
+        }
+    }
+
+    // Several points equally far from a hull edge, of which only the ends are
+    // vertices
+    let level = [
+        Vector2D::new(0.0f32, 10.0),
+        Vector2D::new(7.0, 4.0),
+        Vector2D::new(3.0, 4.0),
+        Vector2D::new(8.0, 4.0),
+        Vector2D::new(10.0, 10.0),
+    ];
+    assert_eq!(4, convex_hull(&level).len());
+    assert_eq!(convex_hull(&level), quickhull(&level));
+
+    // And on grids, where such ties are common
+    for _ in 0..200 {
+        let points: Vec<Vector2D<f32>> = (0..12)
+            .map(|_| Vector2D::new(next().round(), next().round()))
+            .collect();
+        assert_eq!(convex_hull(&points), quickhull(&points));
+    }
+}
+
+#[test]
+fn predicates_nearly_collinear() {
+    // A grid of points within a few ulps of (0.5, 0.5), tested against a line
+    // through it, checked against exact integer arithmetic in units of 2^-53
+    let ulp = 2.0f64.powi(-53);
+    let (b, c) = (Vector2D::new(12.0, 12.0), Vector2D::new(24.0, 24.0));
+    let scale = |v: f64| (v / ulp) as i128;
+    for i in 0..16 {
+        for j in 0..16 {
+            let a = Vector2D::new(0.5 + i as f64 * ulp, 0.5 + j as f64 * ulp);
+            let exact = (scale(a.x) - scale(c.x)) * (scale(b.y) - scale(c.y))
+                - (scale(a.y) - scale(c.y)) * (scale(b.x) - scale(c.x));
+            let expected = match exact {
+                e if e > 0 => Orientation::CounterClockwise,
+                e if e < 0 => Orientation::Clockwise,
+                _ => Orientation::Collinear,
+            };
+            assert_eq!(expected, orient2d(a, b, c));
+            // Reordering the points is consistent
+            let reversed = match expected {
+                Orientation::Clockwise => Orientation::CounterClockwise,
+                Orientation::CounterClockwise => Orientation::Clockwise,
+                Orientation::Collinear => Orientation::Collinear,
+            };
+            assert_eq!(expected, orient2d(b, c, a));
+            assert_eq!(reversed, orient2d(b, a, c));
+        }
+    }
+}
+
+#[test]
+fn predicates_incircle() {
+    let (a, b, c) = (
+        Vector2D::new(1.0f64, 0.0),
+        Vector2D::new(0.0, 1.0),
+        Vector2D::new(-1.0, 0.0),
+    );
+    assert_eq!(
+        CirclePosition::Inside,
+        incircle(a, b, c, Vector2D::new(0.0, 0.0))
+    );
+    assert_eq!(
+        CirclePosition::Outside,
+        incircle(a, b, c, Vector2D::new(2.0, 0.0))
+    );
+    assert_eq!(
+        CirclePosition::On,
+        incircle(a, b, c, Vector2D::new(0.0, -1.0))
+    );
+    // Clockwise points swap the inside and outside
+    assert_eq!(
+        CirclePosition::Outside,
+        incircle(c, b, a, Vector2D::new(0.0, 0.0))
+    );
+
+    // Just inside and outside of a point on the circle, by a single ulp
+    let below = 1.0f64 - f64::EPSILON / 2.0;
+    let above = 1.0f64 + f64::EPSILON;
+    assert_eq!(
+        CirclePosition::Inside,
+        incircle(a, b, c, Vector2D::new(0.0, -below))
+    );
+    assert_eq!(
+        CirclePosition::Outside,
+        incircle(a, b, c, Vector2D::new(0.0, -above))
+    );
+    // Four cocircular points far from the origin, where the approximation
+    // can't be trusted
+    let offset = Vector2D::new(1e15f64, -1e15);
+    assert_eq!(
+        CirclePosition::On,
+        incircle(
+            a + offset,
+            b + offset,
+            c + offset,
+            Vector2D::new(0.0, -1.0) + offset
+        )
+    );
+}
+
+#[test]
+fn predicates_integers() {
+    // Points on a circle of radius 2^62, which overflow any fixed width
+    // calculation
+    let r = 1i64 << 62;
+    let (a, b, c) = (
+        Vector2D::new(r, 0),
+        Vector2D::new(0, r),
+        Vector2D::new(-r, 0),
+    );
+    assert_eq!(CirclePosition::On, incircle(a, b, c, Vector2D::new(0, -r)));
+    assert_eq!(
+        CirclePosition::Inside,
+        incircle(a, b, c, Vector2D::new(0, 1 - r))
+    );
+    assert_eq!(
+        CirclePosition::Outside,
+        incircle(a, b, c, Vector2D::new(1, -r))
+    );
+    assert_eq!(
+        Orientation::Clockwise,
+        orient2d(
+            Vector2D::new(i64::MIN, i64::MIN),
+            Vector2D::new(i64::MAX, i64::MAX),
+            Vector2D::new(i64::MAX, i64::MAX - 1)
+        )
+    );
+    assert_eq!(
+        Orientation::Collinear,
+        orient2d(
+            Vector2D::new(i64::MIN + 1, i64::MIN + 1),
+            Vector2D::new(0, 0),
+            Vector2D::new(i64::MAX, i64::MAX)
+        )
+    );
+
+    let r = i32::MAX;
+    let (a, b, c) = (
+        Vector2D::new(r, 0),
+        Vector2D::new(0, r),
+        Vector2D::new(-r, 0),
+    );
+    assert_eq!(CirclePosition::On, incircle(a, b, c, Vector2D::new(0, -r)));
+    assert_eq!(
+        CirclePosition::Inside,
+        incircle(a, b, c, Vector2D::new(0, 1 - r))
+    );
+    assert_eq!(
+        Orientation::CounterClockwise,
+        orient2d(
+            Vector2D::new(i32::MIN, i32::MIN),
+            Vector2D::new(i32::MAX, i32::MIN),
+            Vector2D::new(i32::MAX, i32::MIN + 1)
+        )
+    );
+}
+
+/// Check that triangles wind counter-clockwise, and cover exactly the area of
+/// a polygon with holes.
+fn check_triangulation(points: &[Vector2D<f64>], holes: &[usize], triangles: &[[usize; 3]]) {
+    let twice_area = |ring: &[Vector2D<f64>]| {
+        (0..ring.len())
+            .map(|i| {
+                let (a, b) = (ring[i], ring[(i + 1) % ring.len()]);
+                a.x * b.y - a.y * b.x
+            })
+            .sum::<f64>()
+            .abs()
+    };
+    let mut bounds = vec![0];
+    bounds.extend_from_slice(holes);
+    bounds.push(points.len());
+    let expected = twice_area(&points[..bounds[1]])
+        - bounds[1..]
+            .windows(2)
+            .map(|pair| twice_area(&points[pair[0]..pair[1]]))
+            .sum::<f64>();
+    let mut total = 0.0;
+    for &[a, b, c] in triangles {
+        let area = twice_area(&[points[a], points[b], points[c]]);
+        let (ab, ac) = (points[b] - points[a], points[c] - points[a]);
+        assert!(ab.x * ac.y - ab.y * ac.x > 0.0);
+        total += area;
+    }
+    assert!((total - expected).abs() < 1e-9 * expected.max(1.0));
+    assert_eq!(points.len() + 2 * holes.len() - 2, triangles.len());
+}
+
+#[test]
+fn triangulate_concave() {
+    // A comb with teeth pointing up, given clockwise
+    let mut comb = vec![Vector2D::new(0.0f64, 0.0), Vector2D::new(0.0, 1.0)];
+    for i in 0..5 {
+        let x = i as f64 * 2.0;
+        comb.extend_from_slice(&[
+            Vector2D::new(x, 3.0),
+            Vector2D::new(x + 1.0, 3.0),
+            Vector2D::new(x + 1.0, 1.0),
+            Vector2D::new(x + 2.0, 1.0),
+        ]);
+    }
+    comb.push(Vector2D::new(10.0, 0.0));
+    comb.reverse();
+    check_triangulation(&comb, &[], &ear_clipping(&comb, &[]));
+    check_triangulation(&comb, &[], &monotone(&comb, &[]));
+
+    // Star-shaped polygons with jagged edges
+    let mut seed = 7u32;
+    let mut next = || {
+        seed = seed.wrapping_mul(1103515245).wrapping_add(12345);
+        (seed >> 16) as f64 / 65536.0
+    };
+    for n in [5, 17, 60] {
+        let star: Vec<Vector2D<f64>> = (0..n)
+            .map(|i| {
+                let angle = (i as f64 + next() * 0.5) / n as f64 * std::f64::consts::TAU;
+                let radius = 1.0 + next() * 4.0;
+                Vector2D::new(angle.cos() * radius, angle.sin() * radius)
+            })
+            .collect();
+        check_triangulation(&star, &[], &ear_clipping(&star, &[]));
+        check_triangulation(&star, &[], &monotone(&star, &[]));
+    }
+}
+
+#[test]
+fn triangulate_holes() {
+    let square = |x: f64, y: f64, size: f64| {
+        vec![
+            Vector2D::new(x, y),
+            Vector2D::new(x + size, y),
+            Vector2D::new(x + size, y + size),
+            Vector2D::new(x, y + size),
+        ]
+    };
+    // Three holes in a row, and a diamond, inside a large square
+    let mut points = square(0.0, 0.0, 10.0);
+    let mut holes = Vec::new();
+    for i in 0..3 {
+        holes.push(points.len());
+        points.extend(square(1.0 + i as f64 * 3.0, 1.0, 2.0));
+    }
+    holes.push(points.len());
+    points.extend_from_slice(&[
+        Vector2D::new(5.0, 5.5),
+        Vector2D::new(6.5, 7.0),
+        Vector2D::new(5.0, 8.5),
+        Vector2D::new(3.5, 7.0),
+    ]);
+    check_triangulation(&points, &holes, &ear_clipping(&points, &holes));
+    check_triangulation(&points, &holes, &monotone(&points, &holes));
+}
+
+#[test]
+fn triangulate_nearly_collinear() {
+    // A long thin sliver whose middle vertex is a single ulp off the line
+    let ulp = f64::EPSILON;
+    let sliver = [
+        Vector2D::new(0.0f64, 0.0),
+        Vector2D::new(0.5, 0.5 - ulp / 4.0),
+        Vector2D::new(1.0, 1.0),
+        Vector2D::new(0.0, 1.0),
+    ];
+    for triangles in [ear_clipping(&sliver, &[]), monotone(&sliver, &[])] {
+        assert_eq!(2, triangles.len());
+        // Every triangle winds counter-clockwise, however thin
+        for &[a, b, c] in &triangles {
+            assert_eq!(
+                Orientation::CounterClockwise,
+                orient2d(sliver[a], sliver[b], sliver[c])
+            );
+        }
+    }
+
+    // Integer coordinates work too, and points along straight edges can be
+    // left out
+    let points = [
+        Vector2D::new(0, 0),
+        Vector2D::new(2, 0),
+        Vector2D::new(4, 0),
+        Vector2D::new(4, 4),
+        Vector2D::new(0, 4),
+    ];
+    for triangles in [ear_clipping(&points, &[]), monotone(&points, &[])] {
+        let area: i64 = triangles
+            .iter()
+            .map(|&[a, b, c]| {
+                let (ab, ac) = (points[b] - points[a], points[c] - points[a]);
+                (ab.x * ac.y - ab.y * ac.x) as i64
+            })
+            .sum();
+        assert_eq!(32, area);
+    }
+
+    // A bowtie crosses itself, which the sweep notices
+    let bowtie = [
+        Vector2D::new(0.0, 0.0),
+        Vector2D::new(4.0, 4.0),
+        Vector2D::new(4.0, 0.0),
+        Vector2D::new(0.0, 4.0),
+    ];
+    assert!(monotone(&bowtie, &[]).is_empty());
+}
+
+/// Check that no site lies inside the circumcircle of a triangle across an
+/// unconstrained edge.
+fn check_delaunay(delaunay: &Delaunay) {
+    let points = delaunay.points();
+    let triangles: Vec<[usize; 3]> = delaunay.triangles().collect();
+    for (t, &[a, b, c]) in triangles.iter().enumerate() {
+        assert_eq!(
+            Orientation::CounterClockwise,
+            orient2d(points[a], points[b], points[c])
+        );
+        for (i, neighbour) in delaunay.triangle_neighbours(t).iter().enumerate() {
+            let (u, v) = (triangles[t][i], triangles[t][(i + 1) % 3]);
+            if let Some(n) = *neighbour {
+                let opposite = triangles[n].iter().copied().find(|&w| w != u && w != v);
+                if !delaunay.is_constrained(u, v) {
+                    assert_ne!(
+                        CirclePosition::Inside,
+                        incircle(points[a], points[b], points[c], points[opposite.unwrap()])
+                    );
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn delaunay_triangulation() {
+    // A scattering of points, with a repeat
+    let mut points: Vec<Vector2D<f64>> = (0..50)
+        .map(|i| {
+            let t = i as f64;
+            Vector2D::new((t * 7.3).sin() * 10.0 + t * 0.1, (t * 3.7).cos() * 10.0)
+        })
+        .collect();
+    points.push(points[3]);
+    let delaunay = Delaunay::new(&points);
+    check_delaunay(&delaunay);
+    let hull = convex_hull_with(&points, Collinear::Include).len();
+    assert_eq!(2 * 50 - 2 - hull, delaunay.triangles().count());
+    assert!(delaunay.neighbours(50).is_empty());
+    for site in 0..50 {
+        for other in delaunay.neighbours(site) {
+            assert!(delaunay.neighbours(other).contains(&site));
+        }
+    }
+
+    // A grid, where every square's corners are cocircular
+    let grid: Vec<Vector2D<f64>> = (0..36)
+        .map(|i| Vector2D::new((i % 6) as f64, (i / 6) as f64))
+        .collect();
+    let delaunay = Delaunay::new(&grid);
+    check_delaunay(&delaunay);
+    assert_eq!(50, delaunay.triangles().count());
+    let mut corner = delaunay.neighbours(0);
+    corner.sort();
+    assert!(corner == vec![1, 6] || corner == vec![1, 6, 7]);
+
+    // Points along a line have no triangles
+    let line = [
+        Vector2D::new(0.0, 0.0),
+        Vector2D::new(1.0, 1.0),
+        Vector2D::new(2.0, 2.0),
+    ];
+    assert_eq!(0, Delaunay::new(&line).triangles().count());
+}
+
+#[test]
+fn delaunay_constraints() {
+    // A row of points above and below a long edge which crosses between them
+    let mut points = vec![Vector2D::new(0.0f64, 0.0), Vector2D::new(10.0, 0.0)];
+    for i in 1..10 {
+        points.push(Vector2D::new(i as f64, 0.5));
+        points.push(Vector2D::new(i as f64 + 0.5, -0.5));
+    }
+    let mut delaunay = Delaunay::new(&points);
+    assert!(!delaunay.neighbours(0).contains(&1));
+    let triangles = delaunay.triangles().count();
+
+    assert!(delaunay.add_constraint(0, 1));
+    assert!(delaunay.is_constrained(1, 0));
+    assert!(delaunay.neighbours(0).contains(&1));
+    assert_eq!(triangles, delaunay.triangles().count());
+    check_delaunay(&delaunay);
+
+    // Constrained edges can't cross each other
+    assert!(!delaunay.add_constraint(2, 3));
+    assert!(!delaunay.is_constrained(2, 3));
+
+    // Sites on the edge split it
+    let points = [
+        Vector2D::new(0.0f64, 0.0),
+        Vector2D::new(1.0, 0.0),
+        Vector2D::new(2.0, 0.0),
+        Vector2D::new(1.0, 1.0),
+        Vector2D::new(1.0, -1.0),
+    ];
+    let mut delaunay = Delaunay::new(&points);
+    assert!(delaunay.add_constraint(0, 2));
+    assert!(delaunay.is_constrained(0, 1));
+    assert!(delaunay.is_constrained(1, 2));
+    assert!(!delaunay.is_constrained(0, 2));
+}
+
+#[test]
+fn voronoi_cells() {
+    let sites: Vec<Vector2D<f64>> = (0..16)
+        .map(|i| Vector2D::new((i % 4) as f64 * 2.0 + 1.0, (i / 4) as f64 * 2.0 + 1.0))
+        .collect();
+    let bounds = Box2D::new(Vector2D::new(0.0, 0.0), Vector2D::new(8.0, 8.0));
+    let voronoi = Voronoi::new(&sites, bounds);
+
+    // A grid of sites divides the bounds into squares, which only border the
+    // squares beside them, not those touching at a corner
+    let mut area = 0.0;
+    for (site, &centre) in sites.iter().enumerate() {
+        let cell = voronoi.cell(site);
+        for i in 0..cell.len() {
+            let (a, b) = (cell[i], cell[(i + 1) % cell.len()]);
+            area += (a.x * b.y - a.y * b.x) / 2.0;
+        }
+        assert!(cell
+            .iter()
+            .all(|&p| (p - centre).length_squared() <= 2.0 + 1e-9));
+    }
+    assert!((area - 64.0).abs() < 1e-9);
+    let mut neighbours = voronoi.neighbours(5).to_vec();
+    neighbours.sort();
+    assert_eq!(vec![1, 4, 6, 9], neighbours);
+    assert_eq!(2, voronoi.neighbours(0).len());
+
+    // Sites outside the bounds and repeated sites have empty cells
+    let sites = [
+        Vector2D::new(1.0, 1.0),
+        Vector2D::new(3.0, 1.0),
+        Vector2D::new(2.0, 20.0),
+        Vector2D::new(1.0, 1.0),
+    ];
+    let voronoi = Voronoi::new(
+        &sites,
+        Box2D::new(Vector2D::new(0.0, 0.0), Vector2D::new(4.0, 2.0)),
+    );
+    assert_eq!(4, voronoi.cell(0).len());
+    assert_eq!(&[1], voronoi.neighbours(0));
+    assert!(voronoi.cell(2).is_empty());
+    assert!(voronoi.cell(3).is_empty());
+}
+
+fn rectangle(x0: f64, y0: f64, x1: f64, y1: f64) -> Polygon<f64> {
+    Polygon::new(vec![
+        Vector2D::new(x0, y0),
+        Vector2D::new(x1, y0),
+        Vector2D::new(x1, y1),
+        Vector2D::new(x0, y1),
+    ])
+}
+
+fn total_area(polygons: &[Polygon<f64>]) -> f64 {
+    polygons.iter().map(Polygon::area).sum()
+}
+
+#[test]
+fn boolean_overlapping() {
+    // Two squares overlapping in a 1 by 1 corner, one wound clockwise
+    let a = [rectangle(0.0, 0.0, 2.0, 2.0)];
+    let mut b = [rectangle(1.0, 1.0, 3.0, 3.0)];
+    b[0].exterior.reverse();
+
+    let merged = union(&a, &b);
+    assert_eq!(1, merged.len());
+    assert_eq!(8, merged[0].exterior.len());
+    assert_eq!(7.0, total_area(&merged));
+
+    let overlap = intersection(&a, &b);
+    assert_eq!(1, overlap.len());
+    assert_eq!(1.0, overlap[0].area());
+    assert!(overlap[0].contains(Vector2D::new(1.5, 1.5)));
+
+    assert_eq!(3.0, total_area(&difference(&a, &b)));
+    let either = xor(&a, &b);
+    assert_eq!(2, either.len());
+    assert_eq!(6.0, total_area(&either));
+    for polygon in either {
+        assert!(!polygon.contains(Vector2D::new(1.5, 1.5)));
+    }
+}
+
+#[test]
+fn boolean_shared_edges() {
+    // Squares sharing an edge merge into a rectangle with only four corners
+    let left = [rectangle(0.0, 0.0, 1.0, 1.0)];
+    let right = [rectangle(1.0, 0.0, 2.0, 1.0)];
+    let merged = union(&left, &right);
+    assert_eq!(1, merged.len());
+    assert_eq!(4, merged[0].exterior.len());
+    assert_eq!(2.0, merged[0].area());
+    assert!(intersection(&left, &right).is_empty());
+    assert_eq!(left.to_vec(), difference(&left, &right));
+
+    // A shape minus itself leaves nothing
+    assert!(difference(&left, &left).is_empty());
+    assert!(xor(&left, &left).is_empty());
+    assert_eq!(1.0, total_area(&intersection(&left, &left)));
+
+    // Squares touching at a corner stay separate
+    let corner = [rectangle(1.0, 1.0, 2.0, 2.0)];
+    assert_eq!(2, union(&left, &corner).len());
+}
+
+#[test]
+fn boolean_holes() {
+    // Punching a hole, then filling it again
+    let ground = [rectangle(0.0, 0.0, 10.0, 4.0)];
+    let crater = [rectangle(4.0, 1.0, 6.0, 3.0)];
+    let holed = difference(&ground, &crater);
+    assert_eq!(1, holed.len());
+    assert_eq!(1, holed[0].holes.len());
+    assert_eq!(36.0, holed[0].area());
+    assert!(!holed[0].contains(Vector2D::new(5.0, 2.0)));
+    let filled = union(&holed, &crater);
+    assert_eq!(1, filled.len());
+    assert!(filled[0].holes.is_empty());
+    assert_eq!(40.0, filled[0].area());
+
+    // A crater breaking through the surface opens the hole up
+    let opened = difference(&holed, &[rectangle(4.5, 2.0, 5.5, 5.0)]);
+    assert_eq!(1, opened.len());
+    assert!(opened[0].holes.is_empty());
+    assert_eq!(35.0, opened[0].area());
+
+    // Clipping to a convex triangle, wound either way
+    let square = rectangle(0.0, 0.0, 2.0, 2.0).exterior;
+    let mut triangle = vec![
+        Vector2D::new(1.0, -1.0),
+        Vector2D::new(3.0, 1.0),
+        Vector2D::new(1.0, 3.0),
+    ];
+    for _ in 0..2 {
+        let clipped = clip_convex(&square, &triangle);
+        assert_eq!(2.0, Polygon::new(clipped).area());
+        triangle.reverse();
+    }
+    assert!(clip_convex(&square, &rectangle(5.0, 5.0, 6.0, 6.0).exterior).is_empty());
+    assert!(clip_convex(&square, &[]).is_empty());
+    assert!(clip_convex(&square, &triangle[..2]).is_empty());
+}
+
+#[test]
+fn offset_polygon_joins() {
+    // Clockwise, to check the winding doesn't matter
+    let square = [
+        Vector2D::new(0.0f32, 0.0),
+        Vector2D::new(0.0, 4.0),
+        Vector2D::new(4.0, 4.0),
+        Vector2D::new(4.0, 0.0),
+    ];
+    let area = |join| {
+        let grown = Offset::new(1.0).with_join(join).polygon(&square);
+        assert_eq!(1, grown.len());
+        grown[0].area()
+    };
+    assert_eq!(36.0, area(Join::Miter(2.0)));
+    // Square corners reach √2 times the distance, so a lower limit bevels them
+    assert_eq!(34.0, area(Join::Miter(1.4)));
+    assert_eq!(34.0, area(Join::Bevel));
+    let round = area(Join::Round);
+    assert!(round < 32.0 + std::f32::consts::PI && round > 32.0 + std::f32::consts::PI * 0.98);
+
+    // The inside corner of an L shape stays sharp when growing it, and the
+    // outside corners stay sharp when shrinking it, while the inside corner
+    // is rounded
+    let l_shape = [
+        Vector2D::new(0.0f32, 0.0),
+        Vector2D::new(4.0, 0.0),
+        Vector2D::new(4.0, 2.0),
+        Vector2D::new(2.0, 2.0),
+        Vector2D::new(2.0, 4.0),
+        Vector2D::new(0.0, 4.0),
+    ];
+    let grown = Offset::new(1.0).with_join(Join::Round).polygon(&l_shape);
+    assert!(grown[0].exterior.contains(&Vector2D::new(3.0, 3.0)));
+    let shrunk = Offset::new(-0.5).with_join(Join::Round).polygon(&l_shape);
+    assert_eq!(1, shrunk.len());
+    assert!(shrunk[0].exterior.contains(&Vector2D::new(0.5, 0.5)));
+    assert!(shrunk[0].exterior.contains(&Vector2D::new(3.5, 0.5)));
+    let expected = 5.25 - std::f32::consts::PI / 16.0;
+    assert!((shrunk[0].area() - expected).abs() < 0.01);
+}
+
+#[test]
+fn offset_polyline_caps() {
+    let line = [Vector2D::new(0.0f64, 0.0), Vector2D::new(4.0, 0.0)];
+    let area = |cap| {
+        let thick = Offset::new(1.0).with_cap(cap).polyline(&line);
+        assert_eq!(1, thick.len());
+        thick[0].area()
+    };
+    assert_eq!(8.0, area(Cap::Butt));
+    assert_eq!(12.0, area(Cap::Square));
+    assert!((area(Cap::Round) - (8.0 + std::f64::consts::PI)).abs() < 0.1);
+
+    // A hairpin bend overlaps itself, but comes out as a single shape, with
+    // the gap inside the bend filled in
+    let hairpin = [
+        Vector2D::new(0.0f64, 0.0),
+        Vector2D::new(4.0, 0.0),
+        Vector2D::new(4.0, 1.0),
+        Vector2D::new(0.0, 1.0),
+    ];
+    let thick = Offset::new(1.0)
+        .with_join(Join::Miter(2.0))
+        .polyline(&hairpin);
+    assert_eq!(1, thick.len());
+    assert!(thick[0].holes.is_empty());
+    assert_eq!(5.0 * 3.0, thick[0].area());
+
+    // A single point only shows up with caps
+    let point = [Vector2D::new(1.0f64, 1.0)];
+    assert!(Offset::new(1.0).polyline(&point).is_empty());
+    let dot = Offset::new(1.0).with_cap(Cap::Square).polyline(&point);
+    assert_eq!(4.0, dot[0].area());
+}
+
+#[test]
+fn offset_erosion() {
+    // Two rooms joined by a narrow corridor, which closes when eroded by more
+    // than half its width
+    let rooms = [
+        Vector2D::new(0.0f32, 0.0),
+        Vector2D::new(4.0, 0.0),
+        Vector2D::new(4.0, 1.5),
+        Vector2D::new(6.0, 1.5),
+        Vector2D::new(6.0, 0.0),
+        Vector2D::new(10.0, 0.0),
+        Vector2D::new(10.0, 4.0),
+        Vector2D::new(6.0, 4.0),
+        Vector2D::new(6.0, 2.5),
+        Vector2D::new(4.0, 2.5),
+        Vector2D::new(4.0, 4.0),
+        Vector2D::new(0.0, 4.0),
+    ];
+    assert_eq!(1, Offset::new(-0.4).polygon(&rooms).len());
+
+    // Eroding a room around a pillar grows the pillar, while growing the room
+    // fills the pillar in
+    let floor = Polygon::new(vec![
+        Vector2D::new(0.0f32, 0.0),
+        Vector2D::new(10.0, 0.0),
+        Vector2D::new(10.0, 10.0),
+        Vector2D::new(0.0, 10.0),
+    ])
+    .with_hole(vec![
+        Vector2D::new(4.0, 4.0),
+        Vector2D::new(6.0, 4.0),
+        Vector2D::new(6.0, 6.0),
+        Vector2D::new(4.0, 6.0),
+    ]);
+    let eroded = Offset::new(-1.0).polygon_with_holes(&floor);
+    assert_eq!(1, eroded.len());
+    assert_eq!(1, eroded[0].holes.len());
+    assert_eq!(64.0 - 16.0, eroded[0].area());
+    // Rounding off the pillar's corners, but not the room's, to within the
+    // arcs' tolerance
+    let rounded = Offset::new(-1.0)
+        .with_join(Join::Round)
+        .polygon_with_holes(&floor);
+    let expected = 64.0 - (4.0 + 8.0 + std::f32::consts::PI);
+    assert!((rounded[0].area() - expected).abs() < 0.05);
+    let grown = Offset::new(1.5).polygon_with_holes(&floor);
+    assert_eq!(1, grown.len());
+    assert!(grown[0].holes.is_empty());
+    assert_eq!(13.0 * 13.0, grown[0].area());
+
+    let eroded = Offset::new(-0.6).polygon(&rooms);
+    assert_eq!(2, eroded.len());
+    for room in &eroded {
+        assert!((room.area() - 2.8 * 2.8).abs() < 1e-4);
+    }
+
+    // Growing a ring-shaped polyline closes it into a shape with a hole
+    let ring = [
+        Vector2D::new(0.0f32, 0.0),
+        Vector2D::new(6.0, 0.0),
+        Vector2D::new(6.0, 6.0),
+        Vector2D::new(0.0, 6.0),
+        Vector2D::new(0.0, 0.0),
+    ];
+    let wall = Offset::new(1.0).with_cap(Cap::Square).polyline(&ring);
+    assert_eq!(1, wall.len());
+    assert_eq!(1, wall[0].holes.len());
+    assert_eq!(64.0 - 16.0, wall[0].area());
+
+    // Which can be cut without converting from f32
+    let door = Polygon::new(vec![
+        Vector2D::new(2.5f32, -2.0),
+        Vector2D::new(3.5, -2.0),
+        Vector2D::new(3.5, 2.0),
+        Vector2D::new(2.5, 2.0),
+    ]);
+    let opened = difference(&wall, &[door]);
+    assert_eq!(1, opened.len());
+    assert!(opened[0].holes.is_empty());
+    assert_eq!(64.0 - 16.0 - 2.0, opened[0].area());
+}
+
+#[test]
+fn polyline_ramer_douglas_peucker() {
+    let points = [
+        Vector2D::new(0.0f64, 0.0),
+        Vector2D::new(1.0, 0.1),
+        Vector2D::new(2.0, -0.1),
+        Vector2D::new(3.0, 5.0),
+        Vector2D::new(4.0, 6.0),
+        Vector2D::new(5.0, 7.0),
+        Vector2D::new(6.0, 8.1),
+        Vector2D::new(7.0, 9.0),
+    ];
+    assert_eq!(
+        vec![points[0], points[2], points[3], points[7]],
+        ramer_douglas_peucker(&points, 0.5)
+    );
+    // Everything but the ends lies within a large epsilon
+    assert_eq!(
+        vec![points[0], points[7]],
+        ramer_douglas_peucker(&points, 10.0)
+    );
+    // No epsilon keeps every point off the line, only losing (4, 6)
+    let mut exact = points.to_vec();
+    exact.remove(4);
+    assert_eq!(exact, ramer_douglas_peucker(&points, 0.0));
+
+    // A closed loop, which starts and ends at the same point
+    let square = [
+        Vector2D::new(0.0f64, 0.0),
+        Vector2D::new(1.0, 0.0),
+        Vector2D::new(2.0, 0.0),
+        Vector2D::new(2.0, 2.0),
+        Vector2D::new(0.0, 2.0),
+        Vector2D::new(0.0, 0.0),
+    ];
+    assert_eq!(
+        vec![square[0], square[2], square[3], square[4], square[5]],
+        ramer_douglas_peucker(&square, 0.1)
+    );
+}
+
+#[test]
+fn polyline_visvalingam_whyatt() {
+    // A line with a small bump and a large one
+    let points = [
+        Vector2D::new(0.0f32, 0.0),
+        Vector2D::new(1.0, 0.0),
+        Vector2D::new(2.0, 0.2),
+        Vector2D::new(3.0, 0.0),
+        Vector2D::new(4.0, 0.0),
+        Vector2D::new(5.0, 3.0),
+        Vector2D::new(6.0, 0.0),
+        Vector2D::new(7.0, 0.0),
+    ];
+    assert_eq!(
+        vec![points[0], points[4], points[5], points[6], points[7]],
+        visvalingam_whyatt(&points, 0.5)
+    );
+    // Straight runs make triangles of no area, so only disappear with a
+    // threshold above zero
+    assert_eq!(points.to_vec(), visvalingam_whyatt(&points, 0.0));
+    let coarse = visvalingam_whyatt(&points, 100.0);
+    assert_eq!(vec![points[0], points[7]], coarse);
+}
+
+#[test]
+fn polyline_resample_and_smooth() {
+    let corner = [
+        Vector2D::new(0.0f64, 0.0),
+        Vector2D::new(3.0, 0.0),
+        Vector2D::new(3.0, 1.0),
+    ];
+    let even = resample(&corner, 5);
+    assert_eq!(
+        vec![
+            Vector2D::new(0.0, 0.0),
+            Vector2D::new(1.0, 0.0),
+            Vector2D::new(2.0, 0.0),
+            Vector2D::new(3.0, 0.0),
+            Vector2D::new(3.0, 1.0),
+        ],
+        even
+    );
+    assert!(resample(&corner, 0).is_empty());
+    assert_eq!(vec![corner[0]], resample(&corner, 1));
+
+    let spaced = resample_by_length(&corner, 1.5);
+    assert_eq!(
+        vec![
+            Vector2D::new(0.0, 0.0),
+            Vector2D::new(1.5, 0.0),
+            Vector2D::new(3.0, 0.0),
+            Vector2D::new(3.0, 1.0),
+        ],
+        spaced
+    );
+    assert_eq!(
+        vec![corner[0], corner[2]],
+        resample_by_length(&corner, 10.0)
+    );
+
+    // Cutting the corner keeps the ends of an open line
+    let smooth = chaikin(&corner, 1, false);
+    assert_eq!(
+        vec![
+            Vector2D::new(0.0, 0.0),
+            Vector2D::new(2.25, 0.0),
+            Vector2D::new(3.0, 0.25),
+            Vector2D::new(3.0, 1.0),
+        ],
+        smooth
+    );
+    assert_eq!(10, chaikin(&corner, 3, false).len());
+    // Closed lines have every corner cut, doubling the points each time
+    let closed = chaikin(&corner, 2, true);
+    assert_eq!(12, closed.len());
+    assert!(!closed.contains(&corner[0]));
+}
@@ -0,0 +1,164 @@
+//! Affine transforms of points and vectors.
+//!
+//! A [`Transform2D`] is a linear map followed by a translation. Applying it to a
+//! [`Point2D`] performs the translation, whereas applying it to a [`Vector2D`]
+//! does not, as displacements are unaffected by moving the origin.
+//!
+//! # Example
+//! ```
+//! use vector2d::point::Point2D;
+//! use vector2d::transform::Transform2D;
+//! use vector2d::Vector2D;
+//!
+//! let t = Transform2D::scale(Vector2D::new(2.0, 3.0))
+//!     .then(&Transform2D::translation(Vector2D::new(10.0, 0.0)));
+//!
+//! assert_eq!(Point2D::new(12.0, 3.0), t.transform_point(Point2D::new(1.0, 1.0)));
+//! assert_eq!(Vector2D::new(2.0, 3.0), t.transform_vector(Vector2D::new(1.0, 1.0)));
+//! ```
+//!
+//! [`Transform2D`]: struct.Transform2D.html
+//! [`Point2D`]: ../point/struct.Point2D.html
+//! [`Vector2D`]: ../struct.Vector2D.html
+
+use crate::point::Point2D;
+use crate::Vector2D;
+use std::ops::{Add, Div, Mul, Neg, Sub};
+
+/// An affine transform, mapping a point `p` to
+/// `x_axis * p.x + y_axis * p.y + translation`.
+///
+/// `x_axis` and `y_axis` are the images of the unit vectors, forming the columns
+/// of the linear part of the transform.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct Transform2D<T> {
+    pub x_axis: Vector2D<T>,
+    pub y_axis: Vector2D<T>,
+    pub translation: Vector2D<T>,
+}
+
+impl<T: Copy + Clone> Transform2D<T> {
+    /// Create a new `Transform2D` from its linear part and translation.
+    pub fn new(x_axis: Vector2D<T>, y_axis: Vector2D<T>, translation: Vector2D<T>) -> Self {
+        Self {
+            x_axis,
+            y_axis,
+            translation,
+        }
+    }
+}
+
+impl<T: From<u8> + Copy + Clone> Transform2D<T> {
+    /// Get the transform that leaves everything unchanged.
+    pub fn identity() -> Self {
+        Self::scale(Vector2D::new(T::from(1), T::from(1)))
+    }
+
+    /// Get a transform that moves points by `v`, and leaves vectors unchanged.
+    pub fn translation(v: Vector2D<T>) -> Self {
+        let (zero, one) = (T::from(0), T::from(1));
+        Self::new(Vector2D::new(one, zero), Vector2D::new(zero, one), v)
+    }
+
+    /// Get a transform that scales each axis by the corresponding component of
+    /// `v`.
+    pub fn scale(v: Vector2D<T>) -> Self {
+        let zero = T::from(0);
+        Self::new(
+            Vector2D::new(v.x, zero),
+            Vector2D::new(zero, v.y),
+            Vector2D::new(zero, zero),
+        )
+    }
+}
+
+impl<T> Transform2D<T>
+where
+    T: Add<T, Output = T> + Mul<T, Output = T> + Copy + Clone,
+{
+    /// Apply the transform to a position, including its translation.
+    pub fn transform_point(&self, p: Point2D<T>) -> Point2D<T> {
+        Point2D::from_vector(self.transform_vector(p.to_vector()) + self.translation)
+    }
+
+    /// Apply the transform to a displacement, ignoring its translation.
+    pub fn transform_vector(&self, v: Vector2D<T>) -> Vector2D<T> {
+        self.x_axis * v.x + self.y_axis * v.y
+    }
+
+    /// Combine this transform with one that follows it, producing a single
+    /// transform equivalent to applying `self` and then `next`.
+    pub fn then(&self, next: &Self) -> Self {
+        Self::new(
+            next.transform_vector(self.x_axis),
+            next.transform_vector(self.y_axis),
+            next.transform_point(Point2D::from_vector(self.translation))
+                .to_vector(),
+        )
+    }
+}
+
+impl<T> Transform2D<T>
+where
+    T: Sub<T, Output = T> + Mul<T, Output = T> + Copy + Clone,
+{
+    /// Get the determinant of the linear part of the transform. This is the
+    /// factor by which the transform scales areas, and is negative when the
+    /// transform mirrors.
+    pub fn determinant(&self) -> T {
+        self.x_axis.x * self.y_axis.y - self.y_axis.x * self.x_axis.y
+    }
+}
+
+impl<T> Transform2D<T>
+where
+    T: Add<T, Output = T>
+        + Sub<T, Output = T>
+        + Mul<T, Output = T>
+        + Div<T, Output = T>
+        + Neg<Output = T>
+        + PartialEq
+        + From<u8>
+        + Copy
+        + Clone,
+{
+    /// Get the transform that undoes this one, or `None` if the transform
+    /// collapses space onto a line or point and so cannot be undone.
+    pub fn inverse(&self) -> Option<Self> {
+        let det = self.determinant();
+        if det == T::from(0) {
+            return None;
+        }
+        let x_axis = Vector2D::new(self.y_axis.y / det, -self.x_axis.y / det);
+        let y_axis = Vector2D::new(-self.y_axis.x / det, self.x_axis.x / det);
+        let inv = Self::new(x_axis, y_axis, Vector2D::new(T::from(0), T::from(0)));
+        let translation = -inv.transform_vector(self.translation);
+        Some(Self::new(x_axis, y_axis, translation))
+    }
+}
+
+impl Transform2D<f32> {
+    /// Get a transform that rotates counter-clockwise about the origin by the
+    /// provided angle, in radians.
+    pub fn rotation(radians: f32) -> Self {
+        let (sin, cos) = radians.sin_cos();
+        Self::new(
+            Vector2D::new(cos, sin),
+            Vector2D::new(-sin, cos),
+            Vector2D::new(0.0, 0.0),
+        )
+    }
+}
+
+impl Transform2D<f64> {
+    /// Get a transform that rotates counter-clockwise about the origin by the
+    /// provided angle, in radians.
+    pub fn rotation(radians: f64) -> Self {
+        let (sin, cos) = radians.sin_cos();
+        Self::new(
+            Vector2D::new(cos, sin),
+            Vector2D::new(-sin, cos),
+            Vector2D::new(0.0, 0.0),
+        )
+    }
+}
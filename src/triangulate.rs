@@ -0,0 +1,739 @@
+//! Triangulation of simple polygons, with or without holes.
+//!
+//! Polygons are passed as a single slice of vertices: first the outer
+//! boundary, followed by each hole, with a separate list of the indices at
+//! which the holes start. Rings can wind in either direction, and don't repeat
+//! their first vertex at the end. Triangles are returned as triples of indices
+//! into the slice, winding counter-clockwise, so they can be used directly as
+//! an index buffer.
+//!
+//! Two methods are provided:
+//!   - [`ear_clipping()`] joins each hole to the outer boundary with a bridge,
+//!     then repeatedly cuts off triangles ("ears") from the resulting polygon.
+//!     It is simple and forgiving, but takes O(n²) time or worse.
+//!   - [`monotone()`] splits the polygon into pieces that are monotone in y
+//!     with a sweep line, then triangulates each piece in linear time, for
+//!     O(n log n) time overall.
+//!
+//! A polygon with n vertices and h holes is split into n + 2h - 2 triangles.
+//! Every decision is made with the exact [`orient2d()`] predicate, so nearly
+//! collinear vertices can't produce overlapping or inverted triangles.
+//! Vertices which lie exactly on a straight edge can be left out of every
+//! triangle, and then fewer triangles are returned.
+//!
+//! # Example
+//! ```
+//! use vector2d::triangulate::{ear_clipping, monotone};
+//! use vector2d::Vector2D;
+//!
+//! // A square with a square hole
+//! let points = [
+//!     Vector2D::new(0, 0),
+//!     Vector2D::new(4, 0),
+//!     Vector2D::new(4, 4),
+//!     Vector2D::new(0, 4),
+//!     Vector2D::new(1, 1),
+//!     Vector2D::new(1, 3),
+//!     Vector2D::new(3, 3),
+//!     Vector2D::new(3, 1),
+//! ];
+//! let triangles = ear_clipping(&points, &[4]);
+//! assert_eq!(8, triangles.len());
+//! assert_eq!(8, monotone(&points, &[4]).len());
+//!
+//! // Twice the area of every triangle adds up to twice the area of the shape
+//! let area: i32 = triangles
+//!     .iter()
+//!     .map(|&[a, b, c]| {
+//!         let (ab, ac) = (points[b] - points[a], points[c] - points[a]);
+//!         ab.x * ac.y - ab.y * ac.x
+//!     })
+//!     .sum();
+//! assert_eq!(2 * (16 - 4), area);
+//! ```
+//!
+//! [`ear_clipping()`]: fn.ear_clipping.html
+//! [`monotone()`]: fn.monotone.html
+//! [`orient2d()`]: ../predicates/fn.orient2d.html
+
+use crate::hull::compare;
+use crate::predicates::{orient2d, Exact, Orientation};
+use crate::Vector2D;
+use std::cmp::Ordering;
+
+/// Triangulate a polygon by ear clipping, after bridging its holes to the outer
+/// boundary. `holes` contains the index in `points` at which each hole starts,
+/// in increasing order.
+///
+/// # Panics
+/// Panics if the hole indices are out of order or out of bounds.
+pub fn ear_clipping<T: Exact + PartialOrd>(
+    points: &[Vector2D<T>],
+    holes: &[usize],
+) -> Vec<[usize; 3]> {
+    let rings = rings(points, holes);
+    let mut list = RingList::default();
+    let outer = match rings.first() {
+        Some(outer) if outer.len() >= 3 => list.push_ring(outer),
+        _ => return Vec::new(),
+    };
+
+    // Bridge the holes in order of their rightmost vertex, from right to left,
+    // so that each bridge can't cross a hole yet to be bridged
+    let mut holes: Vec<&Vec<usize>> = rings[1..].iter().filter(|ring| ring.len() >= 3).collect();
+    holes.sort_by(|a, b| compare(&points[rightmost(points, b)], &points[rightmost(points, a)]));
+    let mut start = outer;
+    for (i, hole) in holes.iter().enumerate() {
+        let hole_start = list.push_ring(hole);
+        let right = list.find(hole_start, rightmost(points, hole));
+        if let Some(bridge) = find_bridge(points, &list, start, right, &holes[i + 1..]) {
+            start = list.split(bridge, right);
+        }
+    }
+    clip_ears(points, &mut list, start)
+}
+
+/// Triangulate a polygon by splitting it into y-monotone pieces, and then
+/// triangulating each piece. `holes` contains the index in `points` at which
+/// each hole starts, in increasing order.
+///
+/// Unlike [`ear_clipping()`], the input must be a valid polygon, with no
+/// duplicate vertices, and holes that don't touch each other or the outer
+/// boundary. Invalid polygons which the sweep notices, such as many that cross
+/// themselves, give no triangles, while others give a meaningless result.
+///
+/// # Panics
+/// Panics if the hole indices are out of order or out of bounds.
+///
+/// [`ear_clipping()`]: fn.ear_clipping.html
+pub fn monotone<T: Exact + PartialOrd>(points: &[Vector2D<T>], holes: &[usize]) -> Vec<[usize; 3]> {
+    let rings: Vec<Vec<usize>> = rings(points, holes)
+        .into_iter()
+        .filter(|ring| ring.len() >= 3)
+        .collect();
+    let mut next = vec![usize::MAX; points.len()];
+    let mut prev = vec![usize::MAX; points.len()];
+    for ring in &rings {
+        for (i, &v) in ring.iter().enumerate() {
+            next[v] = ring[(i + 1) % ring.len()];
+            prev[ring[(i + 1) % ring.len()]] = v;
+        }
+    }
+
+    // Sweep from top to bottom, adding diagonals to remove the vertices at
+    // which the boundary turns back on itself vertically
+    let mut order: Vec<usize> = rings.iter().flatten().copied().collect();
+    order.sort_by(|&a, &b| sweep_order(points[b], points[a]));
+    let mut sweep = Sweep {
+        points,
+        next: &next,
+        status: Vec::new(),
+        helper: vec![usize::MAX; points.len()],
+        merge: vec![false; points.len()],
+        diagonals: Vec::new(),
+    };
+    for &v in &order {
+        if sweep.handle(v, prev[v]).is_none() {
+            return Vec::new();
+        }
+    }
+
+    // Follow the boundary and the diagonals around each monotone piece
+    let mut outgoing: Vec<Vec<usize>> = (0..points.len())
+        .map(|v| {
+            if next[v] == usize::MAX {
+                Vec::new()
+            } else {
+                vec![next[v]]
+            }
+        })
+        .collect();
+    for &(a, b) in &sweep.diagonals {
+        outgoing[a].push(b);
+        outgoing[b].push(a);
+    }
+    let mut used: Vec<Vec<bool>> = outgoing
+        .iter()
+        .map(|edges| vec![false; edges.len()])
+        .collect();
+    let mut triangles = Vec::new();
+    for v in 0..points.len() {
+        for e in 0..outgoing[v].len() {
+            if used[v][e] {
+                continue;
+            }
+            let mut piece = Vec::new();
+            let (mut from, mut edge) = (v, e);
+            while !used[from][edge] {
+                used[from][edge] = true;
+                piece.push(from);
+                let to = outgoing[from][edge];
+                edge = next_edge(points, &outgoing[to], from, to);
+                from = to;
+            }
+            triangulate_monotone(points, &piece, &mut triangles);
+        }
+    }
+    triangles
+}
+
+/// Split the vertex indices into rings, reversing them as needed so that the
+/// outer boundary winds counter-clockwise and the holes clockwise.
+fn rings<T: Exact>(points: &[Vector2D<T>], holes: &[usize]) -> Vec<Vec<usize>> {
+    let mut bounds = vec![0];
+    bounds.extend_from_slice(holes);
+    bounds.push(points.len());
+    bounds
+        .windows(2)
+        .enumerate()
+        .map(|(i, pair)| {
+            assert!(
+                pair[0] <= pair[1],
+                "hole indices must be in increasing order"
+            );
+            let mut ring: Vec<usize> = (pair[0]..pair[1]).collect();
+            let mut area = 0.0;
+            for (j, &a) in ring.iter().enumerate() {
+                let (a, b) = (points[a], points[ring[(j + 1) % ring.len()]]);
+                area += a.x.to_f64() * b.y.to_f64() - a.y.to_f64() * b.x.to_f64();
+            }
+            if (i == 0) == (area < 0.0) {
+                ring.reverse();
+            }
+            ring
+        })
+        .collect()
+}
+
+/// Find the index of the rightmost vertex of a ring, breaking ties towards the
+/// top.
+fn rightmost<T: PartialOrd + Copy>(points: &[Vector2D<T>], ring: &[usize]) -> usize {
+    let mut best = ring[0];
+    for &v in ring {
+        if compare(&points[v], &points[best]) == Ordering::Greater {
+            best = v;
+        }
+    }
+    best
+}
+
+/// Order points by their y, and then x, coordinates, so that points higher up
+/// come later, and points at the same height further left come later.
+fn sweep_order<T: PartialOrd>(a: Vector2D<T>, b: Vector2D<T>) -> Ordering {
+    a.y.partial_cmp(&b.y)
+        .unwrap_or(Ordering::Equal)
+        .then_with(|| b.x.partial_cmp(&a.x).unwrap_or(Ordering::Equal))
+}
+
+/// Get the orientation of three points, as 1 for counter-clockwise, -1 for
+/// clockwise, or 0 for collinear.
+fn orient<T: Exact>(a: Vector2D<T>, b: Vector2D<T>, c: Vector2D<T>) -> i8 {
+    match orient2d(a, b, c) {
+        Orientation::CounterClockwise => 1,
+        Orientation::Collinear => 0,
+        Orientation::Clockwise => -1,
+    }
+}
+
+/// A vertex in a doubly linked ring, referring to an input point.
+#[derive(Copy, Clone, Debug)]
+struct Node {
+    point: usize,
+    prev: usize,
+    next: usize,
+}
+
+/// Rings of vertices which can be spliced together and have vertices removed.
+#[derive(Clone, Debug, Default)]
+struct RingList {
+    nodes: Vec<Node>,
+}
+
+impl RingList {
+    /// Add a ring of points, returning the node of its first point.
+    fn push_ring(&mut self, ring: &[usize]) -> usize {
+        let start = self.nodes.len();
+        for (i, &point) in ring.iter().enumerate() {
+            self.nodes.push(Node {
+                point,
+                prev: start + (i + ring.len() - 1) % ring.len(),
+                next: start + (i + 1) % ring.len(),
+            });
+        }
+        start
+    }
+
+    /// Find the node referring to `point` in the ring containing `start`.
+    fn find(&self, start: usize, point: usize) -> usize {
+        let mut node = start;
+        while self.nodes[node].point != point {
+            node = self.nodes[node].next;
+        }
+        node
+    }
+
+    /// Join two rings with a pair of edges between `a` and `b`, duplicating
+    /// both nodes. Returns the duplicate of `a`.
+    fn split(&mut self, a: usize, b: usize) -> usize {
+        let (a_next, b_prev) = (self.nodes[a].next, self.nodes[b].prev);
+        let a2 = self.nodes.len();
+        let b2 = a2 + 1;
+        self.nodes.push(Node {
+            point: self.nodes[a].point,
+            prev: b2,
+            next: a_next,
+        });
+        self.nodes.push(Node {
+            point: self.nodes[b].point,
+            prev: b_prev,
+            next: a2,
+        });
+        self.nodes[a].next = b;
+        self.nodes[b].prev = a;
+        self.nodes[a_next].prev = a2;
+        self.nodes[b_prev].next = b2;
+        a2
+    }
+
+    /// Remove a node from its ring, returning the node after it.
+    fn remove(&mut self, node: usize) -> usize {
+        let Node { prev, next, .. } = self.nodes[node];
+        self.nodes[prev].next = next;
+        self.nodes[next].prev = prev;
+        next
+    }
+}
+
+/// Find a node on the ring containing `start` which can be joined to the hole
+/// vertex `hole` with a bridge that stays inside the polygon.
+fn find_bridge<T: Exact + PartialOrd>(
+    points: &[Vector2D<T>],
+    list: &RingList,
+    start: usize,
+    hole: usize,
+    remaining: &[&Vec<usize>],
+) -> Option<usize> {
+    let m = points[list.nodes[hole].point];
+    let distance = |p: Vector2D<T>| {
+        let (dx, dy) = (p.x.to_f64() - m.x.to_f64(), p.y.to_f64() - m.y.to_f64());
+        dx * dx + dy * dy
+    };
+    // Every edge the bridge must not cross: the boundary so far, the hole being
+    // bridged, and the holes still to come
+    let mut edges = Vec::new();
+    for &first in &[start, hole] {
+        let mut node = first;
+        loop {
+            let Node { point, next, .. } = list.nodes[node];
+            edges.push((point, list.nodes[next].point));
+            node = next;
+            if node == first {
+                break;
+            }
+        }
+    }
+    for ring in remaining {
+        for (i, &a) in ring.iter().enumerate() {
+            edges.push((a, ring[(i + 1) % ring.len()]));
+        }
+    }
+
+    let mut best: Option<(f64, usize)> = None;
+    let mut node = start;
+    loop {
+        let Node { point, prev, next } = list.nodes[node];
+        let p = points[point];
+        let (a, b) = (
+            points[list.nodes[prev].point],
+            points[list.nodes[next].point],
+        );
+        // The bridge must leave the boundary into the interior
+        let inside = if orient(a, p, b) > 0 {
+            orient(a, p, m) > 0 && orient(p, b, m) > 0
+        } else {
+            orient(a, p, m) > 0 || orient(p, b, m) > 0
+        };
+        let d = distance(p);
+        if inside
+            && best.is_none_or(|(best, _)| d < best)
+            && edges
+                .iter()
+                .all(|&(e, f)| !blocks(m, p, points[e], points[f]))
+        {
+            best = Some((d, node));
+        }
+        node = next;
+        if node == start {
+            break;
+        }
+    }
+    best.map(|(_, node)| node)
+}
+
+/// Check whether the edge `e` to `f` gets in the way of a bridge from `m` to
+/// `p`, by crossing it, or by touching it anywhere other than its ends.
+fn blocks<T: Exact + PartialOrd>(
+    m: Vector2D<T>,
+    p: Vector2D<T>,
+    e: Vector2D<T>,
+    f: Vector2D<T>,
+) -> bool {
+    let ends = [m, p];
+    if ends.contains(&e) && ends.contains(&f) {
+        return false;
+    }
+    let (e_side, f_side) = (orient(m, p, e), orient(m, p, f));
+    let (m_side, p_side) = (orient(e, f, m), orient(e, f, p));
+    if e_side * f_side < 0 && m_side * p_side < 0 {
+        return true;
+    }
+    // A vertex of the edge lying within the bridge
+    let within = |q: Vector2D<T>| {
+        !ends.contains(&q) && orient(m, p, q) == 0 && compare(&q, &m) != compare(&q, &p)
+    };
+    within(e) || within(f)
+}
+
+/// Check whether `p` lies inside or on the edge of the triangle `a`, `b`, `c`,
+/// which winds counter-clockwise.
+fn in_triangle<T: Exact>(a: Vector2D<T>, b: Vector2D<T>, c: Vector2D<T>, p: Vector2D<T>) -> bool {
+    orient(a, b, p) >= 0 && orient(b, c, p) >= 0 && orient(c, a, p) >= 0
+}
+
+/// Cut ears off the ring containing `start` until only a triangle remains.
+fn clip_ears<T: Exact + PartialOrd>(
+    points: &[Vector2D<T>],
+    list: &mut RingList,
+    start: usize,
+) -> Vec<[usize; 3]> {
+    let mut triangles = Vec::new();
+    let mut remaining = {
+        let mut count = 1;
+        let mut node = list.nodes[start].next;
+        while node != start {
+            count += 1;
+            node = list.nodes[node].next;
+        }
+        count
+    };
+    let position = |list: &RingList, node: usize| points[list.nodes[node].point];
+
+    let mut node = start;
+    // The number of vertices visited since an ear was last cut
+    let mut stalled = 0;
+    while remaining > 3 {
+        let Node { prev, next, .. } = list.nodes[node];
+        let (a, b, c) = (
+            position(list, prev),
+            position(list, node),
+            position(list, next),
+        );
+        let turn = orient(a, b, c);
+
+        // Drop vertices with no area to cut, such as repeated points, or
+        // points on a straight edge
+        let is_ear = if turn == 0 {
+            if a == b || b == c || stalled >= remaining {
+                node = list.remove(node);
+                remaining -= 1;
+                stalled = 0;
+                continue;
+            }
+            false
+        } else if turn > 0 {
+            // No reflex vertex of the rest of the polygon may lie within the
+            // ear, other than copies of its own corners
+            let mut other = list.nodes[next].next;
+            let mut clear = true;
+            while other != prev {
+                let Node {
+                    prev: op, next: on, ..
+                } = list.nodes[other];
+                let p = position(list, other);
+                if p != a
+                    && p != b
+                    && p != c
+                    && orient(position(list, op), p, position(list, on)) <= 0
+                    && in_triangle(a, b, c, p)
+                {
+                    clear = false;
+                    break;
+                }
+                other = on;
+            }
+            clear
+        } else {
+            false
+        };
+
+        // When no ear can be found, the input is degenerate, so cut one anyway
+        if is_ear || stalled >= 2 * remaining {
+            if turn > 0 {
+                triangles.push([
+                    list.nodes[prev].point,
+                    list.nodes[node].point,
+                    list.nodes[next].point,
+                ]);
+            } else if turn < 0 {
+                triangles.push([
+                    list.nodes[next].point,
+                    list.nodes[node].point,
+                    list.nodes[prev].point,
+                ]);
+            }
+            list.remove(node);
+            remaining -= 1;
+            stalled = 0;
+            node = prev;
+        } else {
+            stalled += 1;
+            node = next;
+        }
+    }
+
+    let Node { prev, next, .. } = list.nodes[node];
+    let corners = [
+        list.nodes[prev].point,
+        list.nodes[node].point,
+        list.nodes[next].point,
+    ];
+    match orient(points[corners[0]], points[corners[1]], points[corners[2]]) {
+        1 => triangles.push(corners),
+        -1 => triangles.push([corners[2], corners[1], corners[0]]),
+        _ => {}
+    }
+    triangles
+}
+
+/// The state of the sweep line while splitting a polygon into monotone pieces.
+struct Sweep<'a, T> {
+    points: &'a [Vector2D<T>],
+    /// The vertex following each vertex around its ring.
+    next: &'a [usize],
+    /// The edges crossing the sweep line with the polygon to their right,
+    /// from left to right. Edges are identified by their first vertex.
+    status: Vec<usize>,
+    /// For each edge, the lowest vertex above the sweep line which can see it.
+    helper: Vec<usize>,
+    /// Whether each vertex is a merge vertex, where two parts of the polygon
+    /// above the sweep line join.
+    merge: Vec<bool>,
+    diagonals: Vec<(usize, usize)>,
+}
+
+impl<T: Exact + PartialOrd> Sweep<'_, T> {
+    /// Handle the sweep line reaching vertex `v`, which follows `prev`, with
+    /// the interior of the polygon on the left. Returns `None` if there is no
+    /// edge to the left of a vertex which needs one, as for a polygon which
+    /// crosses itself.
+    fn handle(&mut self, v: usize, prev: usize) -> Option<()> {
+        let points = self.points;
+        let next = self.next[v];
+        let below = |a: usize, b: usize| sweep_order(points[a], points[b]) == Ordering::Less;
+        let convex = orient(points[prev], points[v], points[next]) > 0;
+
+        // The edge from `prev` to `v` is `prev`, and the edge from `v` to
+        // `next` is `v`
+        match (below(prev, v), below(next, v)) {
+            (true, true) => {
+                if !convex {
+                    // A split vertex, where the polygon above divides in two
+                    let left = self.left_of(v)?;
+                    self.diagonals.push((v, self.helper[left]));
+                    self.helper[left] = v;
+                }
+                self.insert(v);
+            }
+            (false, false) => {
+                self.fix_up(v, prev);
+                self.remove(prev);
+                if !convex {
+                    // A merge vertex, where two parts of the polygon above join
+                    self.merge[v] = true;
+                    let left = self.left_of(v)?;
+                    self.fix_up(v, left);
+                    self.helper[left] = v;
+                }
+            }
+            (false, true) => {
+                // The boundary heads downwards, so the interior is to the right
+                self.fix_up(v, prev);
+                self.remove(prev);
+                self.insert(v);
+            }
+            (true, false) => {
+                let left = self.left_of(v)?;
+                self.fix_up(v, left);
+                self.helper[left] = v;
+            }
+        }
+        Some(())
+    }
+
+    /// Connect `v` to the helper of `edge` if that is a merge vertex.
+    fn fix_up(&mut self, v: usize, edge: usize) {
+        let helper = self.helper[edge];
+        if self.merge[helper] {
+            self.diagonals.push((v, helper));
+        }
+    }
+
+    /// Add the edge starting at `v` to the status, with `v` as its helper.
+    fn insert(&mut self, v: usize) {
+        let i = self.status.partition_point(|&e| self.is_left(e, v));
+        self.status.insert(i, v);
+        self.helper[v] = v;
+    }
+
+    /// Remove an edge from the status.
+    fn remove(&mut self, edge: usize) {
+        if let Some(i) = self.status.iter().position(|&e| e == edge) {
+            self.status.remove(i);
+        }
+    }
+
+    /// Find the edge directly to the left of vertex `v`, if there is one.
+    fn left_of(&self, v: usize) -> Option<usize> {
+        let p = self.points[v];
+        let i = self.status.partition_point(|&e| {
+            let (upper, lower) = self.ends(e);
+            orient(upper, lower, p) > 0
+        });
+        Some(self.status[i.checked_sub(1)?])
+    }
+
+    /// Check whether edge `a` is to the left of edge `b` where they cross the
+    /// sweep line. The edges mustn't cross each other.
+    fn is_left(&self, a: usize, b: usize) -> bool {
+        let (a_upper, a_lower) = self.ends(a);
+        let (b_upper, b_lower) = self.ends(b);
+        // If both ends of one edge are on the same side of the other, that
+        // decides it. Otherwise the other edge must be decisive.
+        match (
+            orient(b_upper, b_lower, a_upper),
+            orient(b_upper, b_lower, a_lower),
+        ) {
+            (s, t) if s <= 0 && t <= 0 && (s, t) != (0, 0) => true,
+            (s, t) if s >= 0 && t >= 0 && (s, t) != (0, 0) => false,
+            _ => {
+                let s = orient(a_upper, a_lower, b_upper);
+                let t = orient(a_upper, a_lower, b_lower);
+                s >= 0 && t >= 0 && (s, t) != (0, 0)
+            }
+        }
+    }
+
+    /// Get the upper and lower ends of an edge.
+    fn ends(&self, edge: usize) -> (Vector2D<T>, Vector2D<T>) {
+        let (a, b) = (self.points[edge], self.points[self.next[edge]]);
+        if sweep_order(a, b) == Ordering::Greater {
+            (a, b)
+        } else {
+            (b, a)
+        }
+    }
+}
+
+/// Choose which edge to follow around a piece of the polygon after arriving at
+/// `to` from `from`, keeping the piece on the left by turning as sharply
+/// clockwise as possible. Returns an index into `outgoing`, the ends of the
+/// edges leaving `to`.
+fn next_edge<T: Exact + PartialOrd>(
+    points: &[Vector2D<T>],
+    outgoing: &[usize],
+    from: usize,
+    to: usize,
+) -> usize {
+    let (origin, back) = (points[to], points[from]);
+    // Which part of the turn clockwise from the way back each edge is in
+    let part = |w: usize| {
+        let p = points[w];
+        match orient(origin, back, p) {
+            -1 => 0,
+            1 => 2,
+            _ if compare(&p, &origin) == compare(&back, &origin) => 3,
+            _ => 1,
+        }
+    };
+    let mut best = 0;
+    for i in 1..outgoing.len() {
+        let (w, b) = (outgoing[i], outgoing[best]);
+        let (part_w, part_b) = (part(w), part(b));
+        if part_w < part_b
+            || (part_w == part_b && part_w != 1 && orient(origin, points[w], points[b]) < 0)
+        {
+            best = i;
+        }
+    }
+    best
+}
+
+/// Triangulate a piece of a polygon which is monotone in y, given its vertices
+/// in counter-clockwise order.
+fn triangulate_monotone<T: Exact + PartialOrd>(
+    points: &[Vector2D<T>],
+    piece: &[usize],
+    triangles: &mut Vec<[usize; 3]>,
+) {
+    let n = piece.len();
+    if n < 3 {
+        return;
+    }
+    let mut emit = |a: usize, b: usize, c: usize| match orient(points[a], points[b], points[c]) {
+        1 => triangles.push([a, b, c]),
+        -1 => triangles.push([a, c, b]),
+        _ => {}
+    };
+
+    // Going counter-clockwise from the top, the boundary runs down the left
+    // chain to the bottom, and back up the right chain
+    let mut order: Vec<usize> = (0..n).collect();
+    order.sort_by(|&a, &b| sweep_order(points[piece[b]], points[piece[a]]));
+    let (top, bottom) = (order[0], order[n - 1]);
+    let mut left = vec![false; n];
+    let mut i = (top + 1) % n;
+    while i != bottom {
+        left[i] = true;
+        i = (i + 1) % n;
+    }
+
+    let mut stack = vec![order[0], order[1]];
+    for &u in &order[2..n - 1] {
+        let top_of_stack = *stack.last().expect("the stack is never empty");
+        if left[u] != left[top_of_stack] {
+            // The vertex can see every vertex on the stack, across the piece
+            while stack.len() > 1 {
+                let v = stack.pop().expect("the stack has vertices");
+                emit(
+                    piece[u],
+                    piece[v],
+                    piece[*stack.last().expect("the stack has vertices")],
+                );
+            }
+            stack.clear();
+            stack.push(top_of_stack);
+            stack.push(u);
+        } else {
+            // Cut off triangles along the same chain while they're inside
+            let mut last = stack.pop().expect("the stack has vertices");
+            while let Some(&v) = stack.last() {
+                let turn = orient(points[piece[v]], points[piece[last]], points[piece[u]]);
+                if (left[u] && turn <= 0) || (!left[u] && turn >= 0) {
+                    break;
+                }
+                emit(piece[v], piece[last], piece[u]);
+                last = stack.pop().expect("the stack has vertices");
+            }
+            stack.push(last);
+            stack.push(u);
+        }
+    }
+    while stack.len() > 1 {
+        let v = stack.pop().expect("the stack has vertices");
+        emit(
+            piece[bottom],
+            piece[v],
+            piece[*stack.last().expect("the stack has vertices")],
+        );
+    }
+}
@@ -0,0 +1,311 @@
+//! Animating `Vector2D` values over time.
+//!
+//! A [`Tween`] moves a value from one vector to another over a duration,
+//! following an [`Easing`] curve, with optional delays, repeats, and yoyoing.
+//! Animations can be chained with a [`Sequence`], layered with a [`Parallel`],
+//! and played back by an [`Animator`], which keeps track of elapsed time.
+//!
+//! All animations implement [`Animation`], which samples the animated value at
+//! any point in time without any internal state, so they can be scrubbed
+//! backwards and forwards freely.
+//!
+//! # Example
+//! ```
+//! use vector2d::easing::Easing;
+//! use vector2d::tween::{Animator, Sequence, Tween};
+//! use vector2d::Vector2D;
+//!
+//! let slide_in = Tween::new(Vector2D::new(-100.0, 0.0), Vector2D::new(0.0, 0.0), 0.5)
+//!     .with_easing(Easing::CubicOut);
+//! let bob = Tween::new(Vector2D::new(0.0, 0.0), Vector2D::new(0.0, 10.0), 0.25)
+//!     .with_yoyo(true)
+//!     .with_repeats(3);
+//!
+//! let mut animator = Animator::new(Sequence::new().then(slide_in).then(bob));
+//! assert_eq!(1.5, animator.duration().unwrap());
+//!
+//! animator.update(0.5);
+//! assert_eq!(Vector2D::new(0.0, 0.0), animator.value());
+//!
+//! animator.update(0.25);
+//! assert_eq!(Vector2D::new(0.0, 10.0), animator.value());
+//!
+//! animator.update(10.0);
+//! assert!(animator.is_finished());
+//! assert_eq!(Vector2D::new(0.0, 0.0), animator.value());
+//! ```
+//!
+//! [`Tween`]: struct.Tween.html
+//! [`Easing`]: ../easing/enum.Easing.html
+//! [`Sequence`]: struct.Sequence.html
+//! [`Parallel`]: struct.Parallel.html
+//! [`Animator`]: struct.Animator.html
+//! [`Animation`]: trait.Animation.html
+
+use crate::easing::Easing;
+use crate::float::Float;
+use crate::Vector2D;
+
+/// An animated `Vector2D` value, which can be sampled at any point in time.
+pub trait Animation<T> {
+    /// Get the total length of the animation, or `None` if it repeats forever.
+    fn duration(&self) -> Option<T>;
+
+    /// Get the animated value at the provided time since the animation began.
+    /// Times before the start give the initial value, and times after the end
+    /// give the final value.
+    fn sample(&self, time: T) -> Vector2D<T>;
+}
+
+/// Moves a value between two vectors over a duration. See the [module
+/// documentation] for an example.
+///
+/// [module documentation]: index.html
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Tween<T> {
+    pub from: Vector2D<T>,
+    pub to: Vector2D<T>,
+    pub duration: T,
+    pub delay: T,
+    pub easing: Easing,
+    /// The number of times the tween plays after the first, or `None` to play
+    /// forever.
+    pub repeats: Option<u32>,
+    /// Whether every other repeat plays in reverse.
+    pub yoyo: bool,
+}
+
+impl<T: Float> Tween<T> {
+    /// Create a tween moving linearly from `from` to `to` over `duration`,
+    /// playing once without any delay.
+    pub fn new(from: Vector2D<T>, to: Vector2D<T>, duration: T) -> Self {
+        Self {
+            from,
+            to,
+            duration,
+            delay: T::ZERO,
+            easing: Easing::Linear,
+            repeats: Some(0),
+            yoyo: false,
+        }
+    }
+
+    /// Use the provided easing curve.
+    pub fn with_easing(mut self, easing: Easing) -> Self {
+        self.easing = easing;
+        self
+    }
+
+    /// Wait for `delay` before starting.
+    pub fn with_delay(mut self, delay: T) -> Self {
+        self.delay = delay;
+        self
+    }
+
+    /// Play `count` more times after the first.
+    pub fn with_repeats(mut self, count: u32) -> Self {
+        self.repeats = Some(count);
+        self
+    }
+
+    /// Repeat forever.
+    pub fn repeat_forever(mut self) -> Self {
+        self.repeats = None;
+        self
+    }
+
+    /// Play every other repeat in reverse, if `yoyo` is true.
+    pub fn with_yoyo(mut self, yoyo: bool) -> Self {
+        self.yoyo = yoyo;
+        self
+    }
+
+    /// Get the value at the provided progress through a single play, from 0 to
+    /// 1.
+    fn value_at(&self, progress: T, reversed: bool) -> Vector2D<T> {
+        let progress = if reversed {
+            T::ONE - progress
+        } else {
+            progress
+        };
+        Vector2D::lerp(self.from, self.to, self.easing.apply(progress))
+    }
+}
+
+impl<T: Float> Animation<T> for Tween<T> {
+    fn duration(&self) -> Option<T> {
+        self.repeats
+            .map(|r| self.delay + self.duration * T::from_f64(r as f64 + 1.0))
+    }
+
+    fn sample(&self, time: T) -> Vector2D<T> {
+        let local = time - self.delay;
+        if local <= T::ZERO {
+            return self.value_at(T::ZERO, false);
+        }
+        let plays = self.repeats.map(|r| r as f64 + 1.0);
+        if self.duration <= T::ZERO {
+            let last = plays.map_or(0.0, |p| p - 1.0);
+            return self.value_at(T::ONE, self.yoyo && last % 2.0 == 1.0);
+        }
+
+        let cycle = (local / self.duration).floor();
+        match plays {
+            Some(plays) if cycle.to_f64() >= plays => {
+                // Finished; hold the value at the end of the final play
+                self.value_at(T::ONE, self.yoyo && (plays - 1.0) % 2.0 == 1.0)
+            }
+            _ => {
+                let progress = (local - cycle * self.duration) / self.duration;
+                self.value_at(progress, self.yoyo && cycle.to_f64() % 2.0 == 1.0)
+            }
+        }
+    }
+}
+
+/// A series of animations, each starting when the previous one ends.
+///
+/// An animation that repeats forever prevents any that follow it from playing.
+pub struct Sequence<T> {
+    steps: Vec<Box<dyn Animation<T>>>,
+}
+
+impl<T: Float> Sequence<T> {
+    /// Create an empty sequence.
+    pub fn new() -> Self {
+        Self { steps: Vec::new() }
+    }
+
+    /// Add an animation to the end of the sequence.
+    pub fn then<A: Animation<T> + 'static>(mut self, animation: A) -> Self {
+        self.steps.push(Box::new(animation));
+        self
+    }
+}
+
+impl<T: Float> Default for Sequence<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Float> Animation<T> for Sequence<T> {
+    fn duration(&self) -> Option<T> {
+        self.steps
+            .iter()
+            .try_fold(T::ZERO, |total, step| Some(total + step.duration()?))
+    }
+
+    fn sample(&self, time: T) -> Vector2D<T> {
+        let mut start = T::ZERO;
+        for (i, step) in self.steps.iter().enumerate() {
+            match step.duration() {
+                Some(d) if time >= start + d && i + 1 < self.steps.len() => start += d,
+                _ => return step.sample(time - start),
+            }
+        }
+        Vector2D::new(T::ZERO, T::ZERO)
+    }
+}
+
+/// A set of animations playing at the same time, whose values are added
+/// together. This can be used to layer an offset, such as a shake or a bob,
+/// onto another animation.
+pub struct Parallel<T> {
+    layers: Vec<Box<dyn Animation<T>>>,
+}
+
+impl<T: Float> Parallel<T> {
+    /// Create an empty set of animations.
+    pub fn new() -> Self {
+        Self { layers: Vec::new() }
+    }
+
+    /// Add an animation to play alongside the others.
+    pub fn with<A: Animation<T> + 'static>(mut self, animation: A) -> Self {
+        self.layers.push(Box::new(animation));
+        self
+    }
+
+    /// Get the value of each animation at the provided time, without adding
+    /// them together.
+    pub fn sample_each(&self, time: T) -> Vec<Vector2D<T>> {
+        self.layers.iter().map(|l| l.sample(time)).collect()
+    }
+}
+
+impl<T: Float> Default for Parallel<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Float> Animation<T> for Parallel<T> {
+    fn duration(&self) -> Option<T> {
+        self.layers.iter().try_fold(T::ZERO, |longest, layer| {
+            let d = layer.duration()?;
+            Some(if d > longest { d } else { longest })
+        })
+    }
+
+    fn sample(&self, time: T) -> Vector2D<T> {
+        self.layers
+            .iter()
+            .fold(Vector2D::new(T::ZERO, T::ZERO), |sum, l| {
+                sum + l.sample(time)
+            })
+    }
+}
+
+/// Plays an animation, keeping track of how much time has passed.
+pub struct Animator<T> {
+    animation: Box<dyn Animation<T>>,
+    elapsed: T,
+}
+
+impl<T: Float> Animator<T> {
+    /// Create an animator at the start of the provided animation.
+    pub fn new<A: Animation<T> + 'static>(animation: A) -> Self {
+        Self {
+            animation: Box::new(animation),
+            elapsed: T::ZERO,
+        }
+    }
+
+    /// Advance the animation by `dt`, returning the new value.
+    pub fn update(&mut self, dt: T) -> Vector2D<T> {
+        self.elapsed += dt;
+        self.value()
+    }
+
+    /// Get the current value of the animation.
+    pub fn value(&self) -> Vector2D<T> {
+        self.animation.sample(self.elapsed)
+    }
+
+    /// Get the time elapsed since the animation began.
+    pub fn elapsed(&self) -> T {
+        self.elapsed
+    }
+
+    /// Get the total length of the animation, or `None` if it repeats forever.
+    pub fn duration(&self) -> Option<T> {
+        self.animation.duration()
+    }
+
+    /// Check whether the animation has played to its end. Animations that
+    /// repeat forever never finish.
+    pub fn is_finished(&self) -> bool {
+        self.duration().is_some_and(|d| self.elapsed >= d)
+    }
+
+    /// Jump to the provided time since the animation began.
+    pub fn seek(&mut self, time: T) {
+        self.elapsed = time;
+    }
+
+    /// Return to the start of the animation.
+    pub fn reset(&mut self) {
+        self.elapsed = T::ZERO;
+    }
+}
@@ -0,0 +1,412 @@
+//! Position-based dynamics for ropes, cloth and soft bodies.
+//!
+//! A [`VerletWorld`] moves point masses with Verlet integration, where each
+//! point's velocity is implied by how far it moved in the previous step. Points
+//! are then linked with [`Constraint`]s, which are satisfied by moving the
+//! points directly, a few times each step. Keeping points at a distance from
+//! each other makes ropes and cloth, bracing the angles between them stiffens
+//! them into jelly-like soft bodies, and pinning points holds them in place.
+//! Points also collide with static [`Shape`]s.
+//!
+//! # Example
+//! ```
+//! use vector2d::verlet::VerletWorld;
+//! use vector2d::Vector2D;
+//!
+//! let mut world = VerletWorld::new(Vector2D::new(0.0f64, -9.81), 1.0 / 60.0);
+//! let rope = world.rope(Vector2D::new(0.0, 0.0), Vector2D::new(5.0, 0.0), 10, 0.1);
+//! world.pin(rope[0]);
+//!
+//! // The rope swings down and comes to rest hanging below its pin
+//! world.update(20.0);
+//! let end = world.point(rope[10]).position;
+//! assert!((end - Vector2D::new(0.0, -5.0)).length() < 0.1);
+//! ```
+//!
+//! [`VerletWorld`]: struct.VerletWorld.html
+//! [`Constraint`]: enum.Constraint.html
+//! [`Shape`]: ../shape/enum.Shape.html
+
+use crate::angle::Angle;
+use crate::collision::collide;
+use crate::float::Float;
+use crate::shape::{Pose, Shape};
+use crate::Vector2D;
+
+/// A point mass.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct VerletPoint<T> {
+    pub position: Vector2D<T>,
+    /// The position at the previous step. The difference between this and
+    /// `position` is how far the point moves next step.
+    pub previous: Vector2D<T>,
+    /// The radius the point has when colliding with shapes.
+    pub radius: T,
+    inv_mass: T,
+}
+
+impl<T: Float> VerletPoint<T> {
+    /// Create a point of the provided mass, at rest.
+    ///
+    /// # Panics
+    /// Panics if `mass` is not positive.
+    pub fn new(position: Vector2D<T>, mass: T) -> Self {
+        assert!(mass > T::ZERO, "a point's mass must be positive");
+        Self {
+            inv_mass: T::ONE / mass,
+            ..Self::fixed(position)
+        }
+    }
+
+    /// Create a point with infinite mass, which is never moved by gravity or
+    /// constraints.
+    pub fn fixed(position: Vector2D<T>) -> Self {
+        Self {
+            position,
+            previous: position,
+            radius: T::ZERO,
+            inv_mass: T::ZERO,
+        }
+    }
+
+    /// Use the provided radius when colliding with shapes.
+    pub fn with_radius(mut self, radius: T) -> Self {
+        self.radius = radius;
+        self
+    }
+
+    /// Set the point moving, so that it travels `velocity * dt` in its first
+    /// step.
+    pub fn with_velocity(mut self, velocity: Vector2D<T>, dt: T) -> Self {
+        self.previous = self.position - velocity * dt;
+        self
+    }
+
+    /// Get the mass of the point, or zero if it is fixed.
+    pub fn mass(&self) -> T {
+        if self.inv_mass == T::ZERO {
+            T::ZERO
+        } else {
+            T::ONE / self.inv_mass
+        }
+    }
+
+    /// Check whether the point is fixed in place.
+    pub fn is_fixed(&self) -> bool {
+        self.inv_mass == T::ZERO
+    }
+
+    /// Get the point's velocity over the previous step of length `dt`.
+    pub fn velocity(&self, dt: T) -> Vector2D<T> {
+        (self.position - self.previous) / dt
+    }
+}
+
+/// Identifies a point within a [`VerletWorld`].
+///
+/// [`VerletWorld`]: struct.VerletWorld.html
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct PointHandle(usize);
+
+/// Identifies a collision shape within a [`VerletWorld`].
+///
+/// [`VerletWorld`]: struct.VerletWorld.html
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct ColliderHandle(usize);
+
+/// A rule relating the positions of points.
+///
+/// Each constraint has a stiffness from 0 to 1, which is the fraction of its
+/// error corrected each time it is solved. Since constraints are solved
+/// several times per step, even low stiffnesses end up fairly stiff.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Constraint<T> {
+    /// Keeps two points `length` apart.
+    Distance {
+        a: PointHandle,
+        b: PointHandle,
+        length: T,
+        stiffness: T,
+    },
+    /// Keeps the signed angle at `pivot`, from the direction of `a` to the
+    /// direction of `b`, at `angle`. Only `a` and `b` are moved.
+    Angle {
+        a: PointHandle,
+        pivot: PointHandle,
+        b: PointHandle,
+        angle: Angle<T>,
+        stiffness: T,
+    },
+    /// Holds a point at a position.
+    Pin {
+        point: PointHandle,
+        position: Vector2D<T>,
+    },
+}
+
+/// A collection of point masses and constraints between them, simulated with
+/// a fixed time step. See the [module documentation] for an example.
+///
+/// [module documentation]: index.html
+#[derive(Clone, Debug, PartialEq)]
+pub struct VerletWorld<T> {
+    pub gravity: Vector2D<T>,
+    /// The length of each simulation step, in seconds.
+    pub timestep: T,
+    /// The number of times constraints are solved each step.
+    pub iterations: usize,
+    /// The fraction of each point's velocity kept from one step to the next.
+    /// Values slightly below 1 stop ropes from swinging forever.
+    pub damping: T,
+    /// The fraction of a point's sliding motion removed when it touches a
+    /// shape, from 0 (slippery) to 1 (sticky).
+    pub friction: T,
+    points: Vec<VerletPoint<T>>,
+    constraints: Vec<Constraint<T>>,
+    colliders: Vec<(Shape<T>, Pose<T>)>,
+    accumulator: T,
+}
+
+impl<T: Float> VerletWorld<T> {
+    /// Create an empty world with the provided gravity and time step.
+    ///
+    /// # Panics
+    /// Panics if `timestep` isn't positive.
+    pub fn new(gravity: Vector2D<T>, timestep: T) -> Self {
+        assert!(timestep > T::ZERO, "timestep must be positive");
+        Self {
+            gravity,
+            timestep,
+            iterations: 8,
+            damping: T::from_f64(0.99),
+            friction: T::from_f64(0.5),
+            points: Vec::new(),
+            constraints: Vec::new(),
+            colliders: Vec::new(),
+            accumulator: T::ZERO,
+        }
+    }
+
+    /// Solve constraints the provided number of times each step.
+    pub fn with_iterations(mut self, iterations: usize) -> Self {
+        self.iterations = iterations;
+        self
+    }
+
+    /// Keep the provided fraction of each point's velocity every step.
+    pub fn with_damping(mut self, damping: T) -> Self {
+        self.damping = damping;
+        self
+    }
+
+    /// Remove the provided fraction of sliding motion when points touch shapes.
+    pub fn with_friction(mut self, friction: T) -> Self {
+        self.friction = friction;
+        self
+    }
+
+    /// Add a point to the world, returning a handle to it.
+    pub fn add_point(&mut self, point: VerletPoint<T>) -> PointHandle {
+        self.points.push(point);
+        PointHandle(self.points.len() - 1)
+    }
+
+    /// Get the point with the provided handle.
+    pub fn point(&self, handle: PointHandle) -> &VerletPoint<T> {
+        &self.points[handle.0]
+    }
+
+    /// Get the point with the provided handle, mutably.
+    pub fn point_mut(&mut self, handle: PointHandle) -> &mut VerletPoint<T> {
+        &mut self.points[handle.0]
+    }
+
+    /// Get every point in the world, in the order they were added.
+    pub fn points(&self) -> &[VerletPoint<T>] {
+        &self.points
+    }
+
+    /// Add a constraint to the world.
+    pub fn add_constraint(&mut self, constraint: Constraint<T>) {
+        self.constraints.push(constraint);
+    }
+
+    /// Get every constraint in the world, in the order they were added.
+    pub fn constraints(&self) -> &[Constraint<T>] {
+        &self.constraints
+    }
+
+    /// Keep two points at their current distance apart, with the provided
+    /// stiffness.
+    pub fn connect(&mut self, a: PointHandle, b: PointHandle, stiffness: T) {
+        let length = (self.point(b).position - self.point(a).position)
+            .length_squared()
+            .sqrt();
+        self.add_constraint(Constraint::Distance {
+            a,
+            b,
+            length,
+            stiffness,
+        });
+    }
+
+    /// Keep the angle at `pivot` between `a` and `b` at its current value, with
+    /// the provided stiffness.
+    pub fn brace(&mut self, a: PointHandle, pivot: PointHandle, b: PointHandle, stiffness: T) {
+        let centre = self.point(pivot).position;
+        let angle = (self.point(a).position - centre).angle_to(self.point(b).position - centre);
+        self.add_constraint(Constraint::Angle {
+            a,
+            pivot,
+            b,
+            angle,
+            stiffness,
+        });
+    }
+
+    /// Hold a point at its current position.
+    pub fn pin(&mut self, point: PointHandle) {
+        let position = self.point(point).position;
+        self.add_constraint(Constraint::Pin { point, position });
+    }
+
+    /// Add a rope of `segments` rigid links from `start` to `end`, made of
+    /// points of the provided mass. Returns the rope's points, from start to
+    /// end.
+    pub fn rope(
+        &mut self,
+        start: Vector2D<T>,
+        end: Vector2D<T>,
+        segments: usize,
+        mass: T,
+    ) -> Vec<PointHandle> {
+        let count = T::from_f64(segments as f64);
+        let handles: Vec<_> = (0..=segments)
+            .map(|i| {
+                let position = Vector2D::lerp(start, end, T::from_f64(i as f64) / count);
+                self.add_point(VerletPoint::new(position, mass))
+            })
+            .collect();
+        for pair in handles.windows(2) {
+            self.connect(pair[0], pair[1], T::ONE);
+        }
+        handles
+    }
+
+    /// Add a static shape for points to collide with, returning a handle to it.
+    pub fn add_collider(&mut self, shape: Shape<T>, pose: Pose<T>) -> ColliderHandle {
+        self.colliders.push((shape, pose));
+        ColliderHandle(self.colliders.len() - 1)
+    }
+
+    /// Move a collider to a new pose.
+    pub fn set_collider_pose(&mut self, handle: ColliderHandle, pose: Pose<T>) {
+        self.colliders[handle.0].1 = pose;
+    }
+
+    /// Advance the world by `dt`, running as many fixed steps as fit into the
+    /// time passed so far. Time left over is carried into the next call. Returns
+    /// the number of steps run.
+    ///
+    /// # Panics
+    /// Panics if [`timestep`] has been set to a value that isn't positive.
+    ///
+    /// [`timestep`]: #structfield.timestep
+    pub fn update(&mut self, dt: T) -> usize {
+        assert!(self.timestep > T::ZERO, "timestep must be positive");
+        self.accumulator += dt;
+        let mut steps = 0;
+        while self.accumulator >= self.timestep {
+            self.step();
+            self.accumulator -= self.timestep;
+            steps += 1;
+        }
+        steps
+    }
+
+    /// Advance the world by a single time step.
+    pub fn step(&mut self) {
+        let acceleration = self.gravity * (self.timestep * self.timestep);
+        for point in self.points.iter_mut().filter(|p| !p.is_fixed()) {
+            let velocity = (point.position - point.previous) * self.damping;
+            point.previous = point.position;
+            point.position += velocity + acceleration;
+        }
+
+        for i in 0..self.iterations {
+            for c in 0..self.constraints.len() {
+                self.solve(self.constraints[c]);
+            }
+            // Friction is only applied once, rather than compounding every
+            // iteration
+            self.collide(i + 1 == self.iterations);
+        }
+    }
+
+    /// Move points to satisfy a constraint.
+    fn solve(&mut self, constraint: Constraint<T>) {
+        match constraint {
+            Constraint::Distance {
+                a,
+                b,
+                length,
+                stiffness,
+            } => {
+                let (pa, pb) = (self.points[a.0], self.points[b.0]);
+                let total = pa.inv_mass + pb.inv_mass;
+                let offset = pb.position - pa.position;
+                let distance = offset.length_squared().sqrt();
+                if total == T::ZERO || distance == T::ZERO {
+                    return;
+                }
+                let correction = offset * ((distance - length) / (distance * total) * stiffness);
+                self.points[a.0].position += correction * pa.inv_mass;
+                self.points[b.0].position -= correction * pb.inv_mass;
+            }
+            Constraint::Angle {
+                a,
+                pivot,
+                b,
+                angle,
+                stiffness,
+            } => {
+                let (pa, pb) = (self.points[a.0], self.points[b.0]);
+                let total = pa.inv_mass + pb.inv_mass;
+                if total == T::ZERO {
+                    return;
+                }
+                let centre = self.points[pivot.0].position;
+                let arm_a = pa.position - centre;
+                let arm_b = pb.position - centre;
+                let error = (arm_a.angle_to(arm_b) - angle).normalised_signed() * stiffness;
+                // Turn both arms to share the correction, so the lighter point moves more
+                self.points[a.0].position = centre + arm_a.rotate(error * (pa.inv_mass / total));
+                self.points[b.0].position = centre + arm_b.rotate(-error * (pb.inv_mass / total));
+            }
+            Constraint::Pin { point, position } => {
+                self.points[point.0].position = position;
+            }
+        }
+    }
+
+    /// Push points out of any shapes they have moved into, optionally removing
+    /// some of their sliding motion.
+    fn collide(&mut self, apply_friction: bool) {
+        for point in self.points.iter_mut().filter(|p| !p.is_fixed()) {
+            for (shape, pose) in &self.colliders {
+                let circle = Shape::Circle(point.radius);
+                let at = Pose::new(point.position, Angle::zero());
+                let contact = match collide(shape, pose, &circle, &at) {
+                    Some(contact) => contact,
+                    None => continue,
+                };
+                point.position += contact.normal * contact.depth();
+                if apply_friction {
+                    let motion = point.position - point.previous;
+                    let sliding = motion - contact.normal * Vector2D::dot(motion, contact.normal);
+                    point.previous += sliding * self.friction;
+                }
+            }
+        }
+    }
+}
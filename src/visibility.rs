@@ -0,0 +1,274 @@
+//! Working out what can be seen from a point.
+//!
+//! Two models of occlusion are supported:
+//!   - [`visibility_polygon()`] finds the region visible from a point among
+//!     occluding wall segments, with an angular sweep around the point. The
+//!     result is a polygon which can be drawn as a light or vision cone.
+//!   - [`field_of_view()`] finds the cells of a tile grid visible from a cell,
+//!     using recursive shadowcasting.
+//!
+//! For a single pair of points, [`line_of_sight()`] checks whether any wall
+//! gets in the way.
+//!
+//! # Example
+//! ```
+//! use vector2d::box2d::Box2D;
+//! use vector2d::visibility::{line_of_sight, visibility_polygon};
+//! use vector2d::Vector2D;
+//!
+//! let bounds = Box2D::new(Vector2D::new(-10.0, -10.0), Vector2D::new(10.0, 10.0));
+//! // A wall to the right of the viewer, casting a shadow to the edge
+//! let walls = [(Vector2D::new(5.0, -5.0), Vector2D::new(5.0, 5.0))];
+//! let origin = Vector2D::new(0.0f64, 0.0);
+//!
+//! let polygon = visibility_polygon(origin, &walls, bounds);
+//! assert!(polygon.contains(&Vector2D::new(5.0, 5.0)));
+//! assert!(polygon.contains(&Vector2D::new(10.0, 10.0)));
+//! assert!(!polygon.contains(&Vector2D::new(10.0, 0.0)));
+//!
+//! assert!(line_of_sight(origin, Vector2D::new(0.0, 9.0), &walls));
+//! assert!(!line_of_sight(origin, Vector2D::new(9.0, 0.0), &walls));
+//! ```
+//!
+//! [`visibility_polygon()`]: fn.visibility_polygon.html
+//! [`field_of_view()`]: fn.field_of_view.html
+//! [`line_of_sight()`]: fn.line_of_sight.html
+
+use crate::box2d::Box2D;
+use crate::float::Float;
+use crate::shape::cross;
+use crate::Vector2D;
+use std::cmp::Ordering;
+use std::collections::HashSet;
+
+/// Find the region visible from `origin`, given occluding wall segments and the
+/// bounds of the area, which should contain the origin. Returns the vertices
+/// of the region in counter-clockwise order.
+///
+/// Walls are treated as infinitely thin, so they can be seen from both sides,
+/// and walls lying on a line through the origin don't block anything. Walls may
+/// cross each other. Rays are cast towards every wall end and every crossing,
+/// and every wall is tested against every ray, so this takes O(n (n + k)) time
+/// for n walls crossing each other k times.
+pub fn visibility_polygon<T: Float>(
+    origin: Vector2D<T>,
+    segments: &[(Vector2D<T>, Vector2D<T>)],
+    bounds: Box2D<T>,
+) -> Vec<Vector2D<T>> {
+    let corners = [
+        bounds.min,
+        Vector2D::new(bounds.max.x, bounds.min.y),
+        bounds.max,
+        Vector2D::new(bounds.min.x, bounds.max.y),
+    ];
+    // Orient every wall so that it runs counter-clockwise around the origin,
+    // ignoring walls seen edge-on
+    let walls: Vec<(Vector2D<T>, Vector2D<T>)> = segments
+        .iter()
+        .copied()
+        .chain((0..4).map(|i| (corners[i], corners[(i + 1) % 4])))
+        .filter_map(|(a, b)| {
+            let (a, b) = (a - origin, b - origin);
+            let turn = cross(a, b);
+            if turn > T::ZERO {
+                Some((a, b))
+            } else if turn < T::ZERO {
+                Some((b, a))
+            } else {
+                None
+            }
+        })
+        .collect();
+
+    // Sweep around the origin, stopping at the direction of every end point,
+    // and of every crossing, where the nearest wall may change
+    let mut directions: Vec<(T, Vector2D<T>)> = walls
+        .iter()
+        .flat_map(|&(a, b)| [a, b])
+        .chain(crossings(&walls))
+        .map(|d| (d.y.atan2(d.x), d))
+        .collect();
+    directions.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(Ordering::Equal));
+    directions.dedup_by(|a, b| cross(a.1, b.1) == T::ZERO && Vector2D::dot(a.1, b.1) > T::ZERO);
+
+    let mut polygon: Vec<Vector2D<T>> = Vec::with_capacity(directions.len() * 2);
+    for &(_, direction) in &directions {
+        // The nearest walls just before and just after the direction, which
+        // differ when it passes the end of a wall
+        let before = nearest_hit(&walls, direction, |a, b| {
+            cross(a, direction) > T::ZERO && cross(direction, b) >= T::ZERO
+        });
+        let after = nearest_hit(&walls, direction, |a, b| {
+            cross(a, direction) >= T::ZERO && cross(direction, b) > T::ZERO
+        });
+        for hit in before.into_iter().chain(after) {
+            let point = origin + hit;
+            if polygon.last() != Some(&point) {
+                polygon.push(point);
+            }
+        }
+    }
+    if polygon.len() > 1 && polygon.first() == polygon.last() {
+        polygon.pop();
+    }
+    polygon
+}
+
+/// Find the points where walls cross each other, other than at the origin.
+fn crossings<T: Float>(walls: &[(Vector2D<T>, Vector2D<T>)]) -> Vec<Vector2D<T>> {
+    let mut points = Vec::new();
+    for (i, &(a, b)) in walls.iter().enumerate() {
+        for &(c, d) in &walls[i + 1..] {
+            let (r, s) = (b - a, d - c);
+            let denominator = cross(r, s);
+            if denominator == T::ZERO {
+                continue;
+            }
+            // The crossing lies t of the way along the first wall and u of the
+            // way along the second
+            let (t, u) = (cross(c - a, s) / denominator, cross(c - a, r) / denominator);
+            let within = |x: T| x >= T::ZERO && x <= T::ONE;
+            if within(t) && within(u) {
+                let point = a + r * t;
+                if point != Vector2D::new(T::ZERO, T::ZERO) {
+                    points.push(point);
+                }
+            }
+        }
+    }
+    points
+}
+
+/// Find where a ray from the origin in `direction` first hits one of the walls
+/// accepted by `covers`, relative to the origin.
+fn nearest_hit<T: Float, F: Fn(Vector2D<T>, Vector2D<T>) -> bool>(
+    walls: &[(Vector2D<T>, Vector2D<T>)],
+    direction: Vector2D<T>,
+    covers: F,
+) -> Option<Vector2D<T>> {
+    let mut nearest: Option<T> = None;
+    for &(a, b) in walls {
+        if !covers(a, b) {
+            continue;
+        }
+        let t = cross(a, b - a) / cross(direction, b - a);
+        match nearest {
+            Some(n) if n <= t => {}
+            _ => nearest = Some(t),
+        }
+    }
+    nearest.map(|t| direction * t)
+}
+
+/// Check whether the straight line from `from` to `to` is clear of every wall
+/// segment. Lines which only touch a wall, such as at its end, are clear.
+pub fn line_of_sight<T: Float>(
+    from: Vector2D<T>,
+    to: Vector2D<T>,
+    segments: &[(Vector2D<T>, Vector2D<T>)],
+) -> bool {
+    let sight = to - from;
+    segments.iter().all(|&(a, b)| {
+        let wall = b - a;
+        let (side_a, side_b) = (cross(sight, a - from), cross(sight, b - from));
+        let (side_from, side_to) = (cross(wall, from - a), cross(wall, to - a));
+        let crosses = |p: T, q: T| (p < T::ZERO && q > T::ZERO) || (p > T::ZERO && q < T::ZERO);
+        !(crosses(side_a, side_b) && crosses(side_from, side_to))
+    })
+}
+
+/// Find the cells of a tile grid visible from `origin`, within `radius` cells
+/// of it, using recursive shadowcasting. `blocks_sight` returns whether a cell
+/// is opaque. Opaque cells are visible themselves, but hide whatever is
+/// behind them. The origin is always visible.
+pub fn field_of_view<F: Fn(Vector2D<i32>) -> bool>(
+    origin: Vector2D<i32>,
+    radius: i32,
+    blocks_sight: F,
+) -> HashSet<Vector2D<i32>> {
+    let mut visible = HashSet::new();
+    visible.insert(origin);
+    // Each octant maps its own rows and columns onto the grid
+    const OCTANTS: [(i32, i32, i32, i32); 8] = [
+        (1, 0, 0, 1),
+        (0, 1, 1, 0),
+        (0, -1, 1, 0),
+        (-1, 0, 0, 1),
+        (-1, 0, 0, -1),
+        (0, -1, -1, 0),
+        (0, 1, -1, 0),
+        (1, 0, 0, -1),
+    ];
+    let mut octant = Octant {
+        origin,
+        radius,
+        blocks_sight: &blocks_sight,
+        visible: &mut visible,
+        transform: (0, 0, 0, 0),
+    };
+    for &transform in &OCTANTS {
+        octant.transform = transform;
+        octant.cast(1, 1.0, 0.0);
+    }
+    visible
+}
+
+/// The state shared while scanning one octant of a field of view.
+struct Octant<'a, F> {
+    origin: Vector2D<i32>,
+    radius: i32,
+    blocks_sight: &'a F,
+    visible: &'a mut HashSet<Vector2D<i32>>,
+    transform: (i32, i32, i32, i32),
+}
+
+impl<F: Fn(Vector2D<i32>) -> bool> Octant<'_, F> {
+    /// Scan the rows from `row` outwards, between the `start` and `end` slopes,
+    /// recursing into the gaps between opaque cells.
+    fn cast(&mut self, row: i32, mut start: f64, end: f64) {
+        if start < end {
+            return;
+        }
+        let (xx, xy, yx, yy) = self.transform;
+        let mut next_start = start;
+        for distance in row..=self.radius {
+            let mut blocked = false;
+            let dy = -distance;
+            for dx in -distance..=0 {
+                // The slopes of the cell's left and right edges
+                let left = (dx as f64 - 0.5) / (dy as f64 + 0.5);
+                let right = (dx as f64 + 0.5) / (dy as f64 - 0.5);
+                if start < right {
+                    continue;
+                }
+                if end > left {
+                    break;
+                }
+
+                let cell = Vector2D::new(
+                    self.origin.x + dx * xx + dy * xy,
+                    self.origin.y + dx * yx + dy * yy,
+                );
+                if dx * dx + dy * dy <= self.radius * self.radius {
+                    self.visible.insert(cell);
+                }
+                let opaque = (self.blocks_sight)(cell);
+                if blocked {
+                    if opaque {
+                        next_start = right;
+                    } else {
+                        blocked = false;
+                        start = next_start;
+                    }
+                } else if opaque && distance < self.radius {
+                    blocked = true;
+                    self.cast(distance + 1, start, left);
+                    next_start = right;
+                }
+            }
+            if blocked {
+                break;
+            }
+        }
+    }
+}